@@ -0,0 +1,518 @@
+use crate::common::CrustyError;
+use crate::hash::{Field, HashTable, IntoField, TableConfig};
+use rand::Rng;
+
+/// Aggregate functions a HashAggregate computes for every group.
+///
+/// Sum, Min, Max and Avg read the IntField of the aggregate column, Count counts tuples of any type.
+/// Quantile(q) estimates the q-th quantile, 0 <= q <= 1, of a group's IntFields with a t-digest.
+/// Sample(k) keeps a uniform random sample of at most k values of any type per group, and emits one
+/// output row per sampled value instead of one row per group.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AggOp {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Avg,
+    Sample(usize),
+    Quantile(f64),
+}
+
+// how many centroids a t-digest keeps at most, roughly; more gives tighter quantiles
+const COMPRESSION: f64 = 100.0;
+
+// one cluster of a t-digest: the mean of the values merged into it and how many there were
+#[derive(Debug, Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+// merging t-digest: values are buffered and periodically merged into centroids sorted by mean,
+// and the k1 scale function keeps centroids near the tails small so extreme quantiles stay accurate
+#[derive(Debug, Clone, Default)]
+struct TDigest {
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+}
+
+impl TDigest {
+    // method to add one value, merging the buffer once it fills
+    fn add(&mut self, value: f64) {
+        self.buffer.push(value);
+        if self.buffer.len() >= 5 * COMPRESSION as usize {
+            self.compress();
+        }
+    }
+
+    // function that maps a quantile onto the k1 scale, where every centroid may span at most 1
+    fn scale(q: f64) -> f64 {
+        COMPRESSION / (2.0 * std::f64::consts::PI) * (2.0 * q - 1.0).asin()
+    }
+
+    // method to merge the buffered values and the centroids into a new, sorted set of centroids
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut points = std::mem::take(&mut self.centroids);
+        points.extend(self.buffer.drain(..).map(|mean| Centroid { mean, weight: 1.0 }));
+        points.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total: f64 = points.iter().map(|c| c.weight).sum();
+
+        let mut current = points[0];
+        let mut before = 0.0;
+        let mut low = Self::scale(0.0);
+        for point in points.into_iter().skip(1) {
+            let q = (before + current.weight + point.weight) / total;
+            if Self::scale(q) - low <= 1.0 {
+                current.mean += (point.mean - current.mean) * point.weight / (current.weight + point.weight);
+                current.weight += point.weight;
+            } else {
+                before += current.weight;
+                low = Self::scale(before / total);
+                self.centroids.push(current);
+                current = point;
+            }
+        }
+        self.centroids.push(current);
+    }
+
+    // method to estimate the q-th quantile by interpolating between the centroids around it
+    fn quantile(&mut self, q: f64) -> f64 {
+        self.compress();
+        let total: f64 = self.centroids.iter().map(|c| c.weight).sum();
+        let target = q * total;
+        // each centroid's weight is centered on its mean
+        let mut before = 0.0;
+        for (i, centroid) in self.centroids.iter().enumerate() {
+            let middle = before + centroid.weight / 2.0;
+            if target < middle {
+                if i == 0 {
+                    return centroid.mean;
+                }
+                let previous = self.centroids[i - 1];
+                let previous_middle = before - previous.weight / 2.0;
+                let t = (target - previous_middle) / (middle - previous_middle);
+                return previous.mean + t * (centroid.mean - previous.mean);
+            }
+            before += centroid.weight;
+        }
+        self.centroids.last().map_or(f64::NAN, |c| c.mean)
+    }
+}
+
+/// How a HashAggregate turns the group column into the key it groups by.
+///
+/// Range maps every IntField to the lower bound of its width wide bucket, so 0-9 group under 0 and
+/// 10-19 under 10, which turns aggregation into a histogram.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupKey {
+    Exact,
+    Range { width: i32 },
+}
+
+impl GroupKey {
+    // method to map a group column value to the key it groups under
+    fn key_of(&self, field: &Field) -> Result<Field, CrustyError> {
+        match (self, field) {
+            (GroupKey::Exact, _) => Ok(field.clone()),
+            (GroupKey::Range { width }, Field::IntField(value)) => value
+                .checked_sub(value.rem_euclid(*width))
+                .map(Field::IntField)
+                .ok_or_else(|| CrustyError::ValidationError(format!("Range bucket of {} is below i32::MIN", value))),
+            (GroupKey::Range { .. }, field) => {
+                Err(CrustyError::ValidationError(format!("Can't group {:?} into ranges", field)))
+            }
+        }
+    }
+}
+
+// running state of one group, finalized into the output fields once every tuple is seen
+#[derive(Debug, Clone)]
+struct Accumulator {
+    count: usize,
+    sum: i64,
+    min: i32,
+    max: i32,
+    // reservoir of at most k values for Sample(k), empty for every other op
+    reservoir: Vec<Field>,
+    // t-digest of the values for Quantile(q), empty for every other op
+    digest: TDigest,
+}
+
+impl Accumulator {
+    fn new() -> Self {
+        Accumulator { count: 0, sum: 0, min: i32::MAX, max: i32::MIN, reservoir: Vec::new(), digest: TDigest::default() }
+    }
+
+    // method to add one value of the aggregate column to the group
+    fn add(&mut self, op: AggOp, field: &Field, value: Option<i32>) {
+        self.count += 1;
+        if let AggOp::Sample(k) = op {
+            // reservoir sampling: the n-th value replaces a random slot with probability k/n
+            if self.reservoir.len() < k {
+                self.reservoir.push(field.clone());
+            } else {
+                let slot = rand::thread_rng().gen_range(0..self.count);
+                if slot < k {
+                    self.reservoir[slot] = field.clone();
+                }
+            }
+        }
+        if let Some(value) = value {
+            if let AggOp::Quantile(_) = op {
+                self.digest.add(value as f64);
+            }
+            self.sum += value as i64;
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+    }
+
+    // method to turn the state into the aggregate's output fields, one per output row of the group
+    // sums beyond i32 come out as a LongField
+    fn finalize(mut self, op: AggOp) -> Vec<Field> {
+        match op {
+            AggOp::Count => vec![self.count.into_field()],
+            AggOp::Sum => vec![self.sum.into_field()],
+            AggOp::Min => vec![Field::IntField(self.min)],
+            AggOp::Max => vec![Field::IntField(self.max)],
+            AggOp::Avg => vec![self.into_field()],
+            AggOp::Sample(_) => self.reservoir,
+            AggOp::Quantile(q) => vec![self.digest.quantile(q).into_field()],
+        }
+    }
+}
+
+/// Implementation for Accumulator's IntoField trait, emitting the average of the values added
+impl IntoField for Accumulator {
+    fn into_field(self) -> Field {
+        (self.sum as f64 / self.count as f64).into_field()
+    }
+}
+
+/// Hash aggregation that groups tuples by one of their fields and aggregates the other.
+pub struct HashAggregate {
+    child: Vec<(Field, Field)>,
+    group_index: usize,
+    agg_index: usize,
+    op: AggOp,
+    group_key: GroupKey,
+    config: TableConfig,
+}
+
+impl HashAggregate {
+    pub fn new(
+        child: Vec<(Field, Field)>,
+        group_index: usize,
+        agg_index: usize,
+        op: AggOp,
+        config: TableConfig,
+    ) -> Self {
+        Self {
+            child,
+            group_index,
+            agg_index,
+            op,
+            group_key: GroupKey::Exact,
+            config,
+        }
+    }
+
+    // method to choose how the group column maps to group keys, exact values by default
+    pub fn set_group_key(&mut self, group_key: GroupKey) {
+        self.group_key = group_key;
+    }
+
+    // method to aggregate every group, in the order each group first appears in the child
+    // returns an error if the group or aggregate column isn't 0 or 1, if Sum, Min, Max or Avg meets a value that isn't an IntField
+    // or if Sample asks for an empty sample or Quantile for a q outside 0..=1, if a range key has no positive width or meets a non IntField
+    // and if the config's hash function is Custom, see HashTable::with_capacity
+    pub fn aggregate(&mut self) -> Result<Vec<(Field, Field)>, CrustyError> {
+        // the table maps a group's key to the position of its accumulator
+        if self.group_index > 1 {
+            return Err(CrustyError::ValidationError(format!("Can't group by field {} of a two field tuple", self.group_index)));
+        }
+        if self.agg_index > 1 {
+            return Err(CrustyError::ValidationError(format!("Can't aggregate field {} of a two field tuple", self.agg_index)));
+        }
+        if self.op == AggOp::Sample(0) {
+            return Err(CrustyError::ValidationError(String::from("Can't sample zero values per group")));
+        }
+        if let AggOp::Quantile(q) = self.op {
+            if !(0.0..=1.0).contains(&q) {
+                return Err(CrustyError::ValidationError(format!("Quantile must be within 0 and 1, got {}", q)));
+            }
+        }
+        if let GroupKey::Range { width } = self.group_key {
+            if width <= 0 {
+                return Err(CrustyError::ValidationError(format!("Range width must be positive, got {}", width)));
+            }
+        }
+        let mut table = HashTable::with_capacity(self.child.len(), self.config)?;
+        let mut groups: Vec<(Field, Accumulator)> = Vec::new();
+        for tuple in self.child.iter() {
+            let column = |index| if index == 0 { &tuple.0 } else { &tuple.1 };
+            let key = &self.group_key.key_of(column(self.group_index))?;
+            let field = column(self.agg_index);
+            let value = match (self.op, field) {
+                (AggOp::Count, _) | (AggOp::Sample(_), _) => None,
+                (_, Field::IntField(value)) => Some(*value),
+                (_, field) => {
+                    return Err(CrustyError::ValidationError(format!("Can't aggregate {:?} with {:?}", field, self.op)));
+                }
+            };
+            let group = match table.get_value(std::slice::from_ref(key)) {
+                Some(&group) => group,
+                None => {
+                    table.insert(key.clone(), groups.len())?;
+                    groups.push((key.clone(), Accumulator::new()));
+                    groups.len() - 1
+                }
+            };
+            groups[group].1.add(self.op, field, value);
+        }
+        let op = self.op;
+        Ok(groups
+            .into_iter()
+            .flat_map(|(key, acc)| acc.finalize(op).into_iter().map(move |value| (key.clone(), value)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test_aggregation {
+    use super::*;
+    use crate::hash::{ExtendOption, HashFunction, HashScheme};
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    fn field(s: &str) -> Field {
+        Field::StringField(String::from(s))
+    }
+
+    fn config(scheme: HashScheme) -> TableConfig {
+        (HashFunction::FarmHash, scheme, ExtendOption::ExtendBucketSize, 0.9, 4)
+    }
+
+    // function to create six (department, credits) tuples in two groups
+    fn credits() -> Vec<(Field, Field)> {
+        vec![
+            (field("CS"), Field::IntField(4)),
+            (field("EE"), Field::IntField(3)),
+            (field("CS"), Field::IntField(-2)),
+            (field("CS"), Field::IntField(7)),
+            (field("EE"), Field::IntField(5)),
+            (field("CS"), Field::IntField(3)),
+        ]
+    }
+
+    // function to test counting the tuples of each group
+    fn test_count() {
+        let child = vec![
+            (field("CS"), field("Adam")),
+            (field("EE"), field("Ben")),
+            (field("CS"), field("Chris")),
+            (field("CS"), field("David")),
+            (field("EE"), field("Eva")),
+            (field("CS"), field("Fordham")),
+        ];
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut aggregate = HashAggregate::new(child.clone(), 0, 1, AggOp::Count, config(scheme));
+            let expected = vec![(field("CS"), Field::IntField(4)), (field("EE"), Field::IntField(2))];
+            assert_eq!(expected, aggregate.aggregate().unwrap());
+            // aggregating again doesn't count the child twice
+            assert_eq!(expected, aggregate.aggregate().unwrap());
+
+            let mut by_name = HashAggregate::new(child.clone(), 1, 0, AggOp::Count, config(scheme));
+            let counts = by_name.aggregate().unwrap();
+            assert_eq!(6, counts.len());
+            assert!(counts.iter().all(|(_, count)| *count == Field::IntField(1)));
+        }
+    }
+
+    // function to test sum, min, max and avg over the same two groups
+    fn test_int_ops() {
+        let expected = [
+            (AggOp::Sum, Field::IntField(12), Field::IntField(8)),
+            (AggOp::Min, Field::IntField(-2), Field::IntField(3)),
+            (AggOp::Max, Field::IntField(7), Field::IntField(5)),
+            (AggOp::Avg, Field::FloatField(3.0), Field::FloatField(4.0)),
+        ];
+        for (op, cs, ee) in expected {
+            let mut aggregate = HashAggregate::new(credits(), 0, 1, op, config(HashScheme::LinearProbe));
+            assert_eq!(vec![(field("CS"), cs), (field("EE"), ee)], aggregate.aggregate().unwrap(), "{:?}", op);
+        }
+
+        // sums past i32 become a LongField instead of saturating or wrapping
+        let big = vec![
+            (field("CS"), Field::IntField(i32::MAX)),
+            (field("CS"), Field::IntField(1)),
+            (field("EE"), Field::IntField(i32::MIN)),
+            (field("EE"), Field::IntField(-1)),
+        ];
+        let mut aggregate = HashAggregate::new(big, 0, 1, AggOp::Sum, config(HashScheme::RobinHood));
+        let expected = vec![
+            (field("CS"), Field::LongField(i32::MAX as i64 + 1)),
+            (field("EE"), Field::LongField(i32::MIN as i64 - 1)),
+        ];
+        assert_eq!(expected, aggregate.aggregate().unwrap());
+    }
+
+    // function to test that only count accepts a non integer aggregate column, and no op a column past the second
+    fn test_non_int_column() {
+        let child = vec![(field("CS"), field("Adam"))];
+        for op in [AggOp::Sum, AggOp::Min, AggOp::Max, AggOp::Avg] {
+            let mut aggregate = HashAggregate::new(child.clone(), 0, 1, op, config(HashScheme::Hopscotch));
+            assert!(aggregate.aggregate().is_err());
+        }
+        let mut aggregate = HashAggregate::new(child.clone(), 0, 1, AggOp::Count, config(HashScheme::Hopscotch));
+        assert_eq!(vec![(field("CS"), Field::IntField(1))], aggregate.aggregate().unwrap());
+
+        // a column past the second field is an error, not a panic
+        let mut aggregate = HashAggregate::new(child.clone(), 2, 1, AggOp::Count, config(HashScheme::Hopscotch));
+        assert!(aggregate.aggregate().is_err());
+        let mut aggregate = HashAggregate::new(child, 0, 2, AggOp::Count, config(HashScheme::Hopscotch));
+        assert!(aggregate.aggregate().is_err());
+    }
+
+    // function to test that a sample caps at k values per group, all drawn from that group's input
+    fn test_sample() {
+        let mut child: Vec<(Field, Field)> = (0..1000).map(|i| (field("CS"), Field::IntField(i))).collect();
+        child.extend((0..3).map(|i| (field("EE"), Field::IntField(-i))));
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut aggregate = HashAggregate::new(child.clone(), 0, 1, AggOp::Sample(10), config(scheme));
+            let rows = aggregate.aggregate().unwrap();
+            let cs: Vec<&Field> = rows.iter().filter(|(key, _)| *key == field("CS")).map(|(_, v)| v).collect();
+            let ee: Vec<&Field> = rows.iter().filter(|(key, _)| *key == field("EE")).map(|(_, v)| v).collect();
+            assert_eq!(10, cs.len());
+            assert!(cs.iter().all(|v| child[..1000].iter().any(|(_, input)| input == *v)));
+            let mut distinct = cs.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(10, distinct.len());
+            // a group smaller than k keeps every value
+            assert_eq!(vec![&Field::IntField(0), &Field::IntField(-1), &Field::IntField(-2)], ee);
+        }
+
+        // samples keep any value type, but can't be empty
+        let names = vec![(field("CS"), field("Adam"))];
+        let mut aggregate = HashAggregate::new(names.clone(), 0, 1, AggOp::Sample(4), config(HashScheme::Hopscotch));
+        assert_eq!(names, aggregate.aggregate().unwrap());
+        let mut aggregate = HashAggregate::new(names, 0, 1, AggOp::Sample(0), config(HashScheme::Hopscotch));
+        assert!(aggregate.aggregate().is_err());
+    }
+
+    // function to test grouping ints into width wide range buckets
+    fn test_range_group_key() {
+        let child: Vec<(Field, Field)> = (0..=25).map(|i| (Field::IntField(i), Field::IntField(i))).collect();
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut aggregate = HashAggregate::new(child.clone(), 0, 1, AggOp::Count, config(scheme));
+            aggregate.set_group_key(GroupKey::Range { width: 10 });
+            let expected = vec![
+                (Field::IntField(0), Field::IntField(10)),
+                (Field::IntField(10), Field::IntField(10)),
+                (Field::IntField(20), Field::IntField(6)),
+            ];
+            assert_eq!(expected, aggregate.aggregate().unwrap());
+        }
+
+        // negative values bucket downwards, so -1 lands in -10..=-1 rather than beside 0
+        let negative = vec![(Field::IntField(-1), Field::IntField(5)), (Field::IntField(-10), Field::IntField(7))];
+        let mut aggregate = HashAggregate::new(negative, 0, 1, AggOp::Sum, config(HashScheme::LinearProbe));
+        aggregate.set_group_key(GroupKey::Range { width: 10 });
+        assert_eq!(vec![(Field::IntField(-10), Field::IntField(12))], aggregate.aggregate().unwrap());
+
+        // ranges need a positive width and int keys
+        let mut aggregate = HashAggregate::new(child, 0, 1, AggOp::Count, config(HashScheme::LinearProbe));
+        aggregate.set_group_key(GroupKey::Range { width: 0 });
+        assert!(aggregate.aggregate().is_err());
+        let mut aggregate = HashAggregate::new(credits(), 0, 1, AggOp::Count, config(HashScheme::LinearProbe));
+        aggregate.set_group_key(GroupKey::Range { width: 10 });
+        assert!(aggregate.aggregate().is_err());
+    }
+
+    // function to test quantile estimates of a known distribution against their exact values
+    fn test_quantile() {
+        // a shuffled 0..10000 in one group, so the exact q-th quantile is about 10000 * q
+        let mut values: Vec<i32> = (0..10000).collect();
+        let mut rng = StdRng::seed_from_u64(7);
+        values.shuffle(&mut rng);
+        let child: Vec<(Field, Field)> = values.iter().map(|&v| (field("CS"), Field::IntField(v))).collect();
+        for (q, tolerance) in [(0.5, 50.0), (0.1, 50.0), (0.99, 20.0)] {
+            let mut aggregate = HashAggregate::new(child.clone(), 0, 1, AggOp::Quantile(q), config(HashScheme::RobinHood));
+            let rows = aggregate.aggregate().unwrap();
+            assert_eq!(1, rows.len());
+            let estimate = rows[0].1.unwrap_float_field();
+            assert!((estimate - 10000.0 * q).abs() < tolerance, "q {} estimated {}", q, estimate);
+        }
+
+        // small groups stay exact enough to read off directly
+        let mut aggregate = HashAggregate::new(credits(), 0, 1, AggOp::Quantile(0.5), config(HashScheme::LinearProbe));
+        assert_eq!(
+            vec![(field("CS"), Field::FloatField(3.5)), (field("EE"), Field::FloatField(4.0))],
+            aggregate.aggregate().unwrap()
+        );
+
+        // quantiles outside 0..=1 and non integer values are rejected
+        let mut aggregate = HashAggregate::new(credits(), 0, 1, AggOp::Quantile(1.5), config(HashScheme::LinearProbe));
+        assert!(aggregate.aggregate().is_err());
+        let names = vec![(field("CS"), field("Adam"))];
+        let mut aggregate = HashAggregate::new(names, 0, 1, AggOp::Quantile(0.5), config(HashScheme::LinearProbe));
+        assert!(aggregate.aggregate().is_err());
+    }
+
+    // function to test converting each accumulator type into a field
+    fn test_into_field() {
+        assert_eq!(Field::IntField(3), 3_usize.into_field());
+        assert_eq!(Field::LongField(1 << 40), (1_usize << 40).into_field());
+        assert_eq!(Field::IntField(-3), (-3_i64).into_field());
+        assert_eq!(Field::LongField(-(1 << 40)), (-(1_i64 << 40)).into_field());
+        assert_eq!(Field::FloatField(0.5), 0.5_f64.into_field());
+        let mut state = Accumulator::new();
+        for value in [4, -2, 7] {
+            state.add(AggOp::Avg, &Field::IntField(value), Some(value));
+        }
+        assert_eq!(Field::FloatField(3.0), state.into_field());
+    }
+
+    mod aggregation {
+        use super::*;
+
+        #[test]
+        fn t_count() {
+            test_count();
+        }
+
+        #[test]
+        fn t_int_ops() {
+            test_int_ops();
+        }
+
+        #[test]
+        fn t_non_int_column() {
+            test_non_int_column();
+        }
+
+        #[test]
+        fn t_sample() {
+            test_sample();
+        }
+
+        #[test]
+        fn t_range_group_key() {
+            test_range_group_key();
+        }
+
+        #[test]
+        fn t_quantile() {
+            test_quantile();
+        }
+
+        #[test]
+        fn t_into_field() {
+            test_into_field();
+        }
+    }
+}
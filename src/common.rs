@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use crate::hash::HashNode;
+use crate::hash::Field;
 use std::{fmt, io};
 use std::error::Error;
 
@@ -46,7 +46,111 @@ impl PredicateOp {
     }
 }
 
+/// Column names of the tuples produced by an operator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    names: Vec<String>,
+}
+
+impl Schema {
+    /// Create a schema from the column names, in field order.
+    pub fn new(names: Vec<String>) -> Self {
+        Self { names }
+    }
+
+    /// Column names in field order.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Label the fields of a tuple with the column names.
+    ///
+    /// # Arguments
+    ///
+    /// * `tuple` - Tuple to label, must have as many fields as the schema has names.
+    pub fn label(&self, tuple: &impl Row) -> Result<Vec<(String, Field)>, CrustyError> {
+        if self.names.len() != tuple.width() {
+            return Err(CrustyError::ValidationError(format!(
+                "Schema has {} columns but tuples have {} fields",
+                self.names.len(),
+                tuple.width()
+            )));
+        }
+        Ok(self.names.iter().cloned().zip((0..tuple.width()).filter_map(|i| tuple.field(i).cloned())).collect())
+    }
+}
+
+/// Typed access to the fields of an output row.
+pub trait Row {
+    /// Number of fields in the row.
+    fn width(&self) -> usize;
+
+    /// Field at position i, None if out of range.
+    fn field(&self, i: usize) -> Option<&Field>;
+
+    /// Integer at position i, None if out of range or not an integer field.
+    fn get_int(&self, i: usize) -> Option<i32> {
+        self.field(i).and_then(Field::as_int)
+    }
+
+    /// String at position i, None if out of range or not a string field.
+    fn get_str(&self, i: usize) -> Option<&str> {
+        self.field(i).and_then(Field::as_str)
+    }
+}
+
+impl Row for (Field, Field) {
+    fn width(&self) -> usize {
+        2
+    }
+
+    fn field(&self, i: usize) -> Option<&Field> {
+        match i {
+            0 => Some(&self.0),
+            1 => Some(&self.1),
+            _ => None,
+        }
+    }
+}
+
+impl Row for (Field, Field, Field) {
+    fn width(&self) -> usize {
+        3
+    }
+
+    fn field(&self, i: usize) -> Option<&Field> {
+        match i {
+            0 => Some(&self.0),
+            1 => Some(&self.1),
+            2 => Some(&self.2),
+            _ => None,
+        }
+    }
+}
+
+impl Row for Vec<(String, Field)> {
+    fn width(&self) -> usize {
+        self.len()
+    }
+
+    fn field(&self, i: usize) -> Option<&Field> {
+        self.get(i).map(|(_, field)| field)
+    }
+}
+
+/// A row of an operator input, two fields.
+pub type Tuple = (Field, Field);
+
+/// A row produced by an equi-join: the shared key, then the other field of the left and of the right tuple.
+pub type JoinedTuple = (Field, Field, Field);
+
+/// A row produced by a full outer join, like JoinedTuple with None for the side that had no match.
+pub type OuterJoinedTuple = (Field, Option<Field>, Option<Field>);
+
 pub trait OpIterator {
+    /// Row type the operator produces, Tuple for scans and JoinedTuple for joins.
+    type Item;
+
     /// Opens the iterator. This must be called before any of the other methods.
     fn open(&mut self) -> Result<(), CrustyError>;
 
@@ -57,7 +161,7 @@ pub trait OpIterator {
     /// # Panics
     ///
     /// Panic if iterator is not open.
-    fn next(&mut self) -> Result<Option<HashNode>, CrustyError>;
+    fn next(&mut self) -> Result<Option<Self::Item>, CrustyError>;
 
     /// Closes the iterator.
     fn close(&mut self) -> Result<(), CrustyError>;
@@ -110,4 +214,46 @@ impl From<io::Error> for CrustyError {
     }
 }
 
-impl Error for CrustyError {}
\ No newline at end of file
+impl Error for CrustyError {}
+#[cfg(test)]
+pub(crate) mod test_common {
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    /// Checks the behavior every OpIterator must share: next before open panics, the end of
+    /// the input stays None, rewind restarts the same sequence, close can be called twice and
+    /// opening again after close restarts the same sequence.
+    pub(crate) fn assert_opiterator_contract<O>(op: &mut O)
+    where
+        O: OpIterator,
+        O::Item: PartialEq + fmt::Debug,
+    {
+        assert!(catch_unwind(AssertUnwindSafe(|| op.next())).is_err(), "next before open must panic");
+
+        op.open().unwrap();
+        let mut first = Vec::new();
+        while let Some(tuple) = op.next().unwrap() {
+            first.push(tuple);
+        }
+        assert!(op.next().unwrap().is_none(), "next must stay None at the end");
+        assert!(op.next().unwrap().is_none(), "next must stay None at the end");
+
+        op.rewind().unwrap();
+        let mut second = Vec::new();
+        while let Some(tuple) = op.next().unwrap() {
+            second.push(tuple);
+        }
+        assert_eq!(first, second, "rewind must restart the same sequence");
+
+        op.close().unwrap();
+        op.close().unwrap();
+
+        op.open().unwrap();
+        let mut third = Vec::new();
+        while let Some(tuple) = op.next().unwrap() {
+            third.push(tuple);
+        }
+        assert_eq!(first, third, "opening after close must restart the same sequence");
+        op.close().unwrap();
+    }
+}
@@ -0,0 +1,239 @@
+use crate::common::CrustyError;
+use crate::hash::{CombineStrategy, ExtendOption, HashFunction, HashScheme, HashTable, Key, KeyRef};
+use std::sync::Mutex;
+
+/// Hash table that can be built from several threads at once.
+///
+/// Every bucket is a single-bucket HashTable behind its own lock, so threads inserting into
+/// different buckets don't wait on each other. A bucket extends under its own lock and keys
+/// never move between buckets, so no extend needs the other locks.
+pub struct ConcurrentHashTable {
+    buckets: Vec<Mutex<HashTable>>,
+    function: HashFunction,
+    combine: CombineStrategy,
+    // the two key words SeededSip routes keys with, drawn at random for every new table like HashTable's
+    seed: (u64, u64),
+    bucket_size: usize,
+    scheme: HashScheme,
+    h: usize,
+    extend_op: ExtendOption,
+    load_factor: f64,
+}
+
+impl ConcurrentHashTable {
+    // initialize a table of b_num locked buckets, each configured like HashTable::new
+    // returns an error for 0 buckets or for HashFunction::Custom, see HashTable::new
+    pub fn new(
+        b_size: usize,
+        b_num: usize,
+        func: HashFunction,
+        sche: HashScheme,
+        h: usize,
+        op: ExtendOption,
+        load_f: f64,
+    ) -> Result<Self, CrustyError> {
+        if b_num == 0 {
+            return Err(CrustyError::ValidationError(String::from("Can't build a table with 0 buckets")));
+        }
+        let mut table = ConcurrentHashTable {
+            buckets: Vec::new(),
+            function: func,
+            combine: CombineStrategy::default(),
+            seed: rand::random(),
+            bucket_size: b_size,
+            scheme: sche,
+            h,
+            extend_op: op,
+            load_factor: load_f,
+        };
+        table.buckets = table.empty_buckets(b_num)?;
+        Ok(table)
+    }
+
+    // method to create n empty locked buckets configured like the table's
+    fn empty_buckets(&self, n: usize) -> Result<Vec<Mutex<HashTable>>, CrustyError> {
+        (0..n)
+            .map(|_| {
+                let mut bucket = HashTable::new(self.bucket_size, 1, self.function, self.scheme, self.h,
+                                                self.extend_op, self.load_factor)?;
+                bucket.set_combine_strategy(self.combine)?;
+                Ok(Mutex::new(bucket))
+            })
+            .collect()
+    }
+
+    // method to get the bucket a key is routed to, combining the field hashes like HashTable with the table's seed
+    fn bucket_index(&self, key: KeyRef) -> usize {
+        // new refuses Custom, so the function always hashes by itself
+        let hash = self.combine.combine_all(key.iter()
+            .map(|field| self.function.hash_seeded(field, self.seed).unwrap_or_default()));
+        hash % self.buckets.len()
+    }
+
+    // method to change how the field hashes are combined, moving every entry to the bucket it routes to now
+    // returns an error if an entry can't be placed again, the table then keeps its old strategy and entries
+    pub fn set_combine_strategy(&mut self, combine: CombineStrategy) -> Result<(), CrustyError> {
+        if self.combine == combine {
+            return Ok(());
+        }
+        let old = std::mem::replace(&mut self.combine, combine);
+        let moved = self.empty_buckets(self.buckets.len()).and_then(|buckets| {
+            for bucket in self.buckets.iter() {
+                for (key, value) in bucket.lock().unwrap().to_entries() {
+                    let bucket_index = self.bucket_index((&key).into());
+                    buckets[bucket_index].lock().unwrap().insert(key, value)?;
+                }
+            }
+            Ok(buckets)
+        });
+        match moved {
+            Ok(buckets) => {
+                self.buckets = buckets;
+                Ok(())
+            }
+            Err(e) => {
+                self.combine = old;
+                Err(e)
+            }
+        }
+    }
+
+    // method to insert a <key, value>, adding the value to an existing key's
+    pub fn insert(&self, new_key: impl Into<Key>, new_value: usize) -> Result<(), CrustyError> {
+        let new_key = new_key.into();
+        let bucket_index = self.bucket_index((&new_key).into());
+        self.buckets[bucket_index].lock().unwrap().insert(new_key, new_value)
+    }
+
+    // method to get the value of a key
+    pub fn get_value<'a>(&self, key: impl Into<KeyRef<'a>>) -> Option<usize> {
+        let key = key.into();
+        let bucket_index = self.bucket_index(key);
+        self.buckets[bucket_index].lock().unwrap().get_key_value(key).map(|(_, value)| *value)
+    }
+
+    // method to get the number of keys in the table
+    pub fn len(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.lock().unwrap().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // method to finish a concurrent build and move every entry into a single HashTable
+    pub fn into_table(self) -> Result<HashTable, CrustyError> {
+        let mut table = HashTable::new(
+            self.bucket_size,
+            self.buckets.len(),
+            self.function,
+            self.scheme,
+            self.h,
+            self.extend_op,
+            self.load_factor,
+        )?;
+        table.set_combine_strategy(self.combine)?;
+        table.begin_bulk();
+        for bucket in self.buckets {
+            for (key, value) in bucket.into_inner().unwrap().to_entries() {
+                table.insert(key, value)?;
+            }
+        }
+        table.end_bulk()?;
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod test_concurrent {
+    use super::*;
+    use crate::hash::Field;
+    use std::thread;
+
+    // function to test that inserts from several threads end up like a serial build
+    fn test_concurrent_insert() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let table = ConcurrentHashTable::new(8, 4, HashFunction::FarmHash, scheme, 4,
+                                                 ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            // every thread inserts its own keys and the shared ones
+            let keys_of = |t: i32| -> Vec<(Field, Field)> {
+                (0..200).map(|i| (Field::IntField(t), Field::IntField(i)))
+                    .chain((0..100).map(|i| (Field::StringField(String::from("shared")), Field::IntField(i))))
+                    .collect()
+            };
+            thread::scope(|s| {
+                for t in 0..4 {
+                    let table = &table;
+                    s.spawn(move || {
+                        for key in keys_of(t) {
+                            table.insert(key, 1).unwrap();
+                        }
+                    });
+                }
+            });
+
+            let mut serial = HashTable::new(8, 4, HashFunction::FarmHash, scheme, 4,
+                                            ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            for t in 0..4 {
+                for key in keys_of(t) {
+                    serial.insert(key, 1).unwrap();
+                }
+            }
+            assert_eq!(900, table.len());
+            let shared = (Field::StringField(String::from("shared")), Field::IntField(7));
+            assert_eq!(Some(4), table.get_value((&shared.0, &shared.1)));
+
+            let mut expected = serial.to_entries();
+            let mut entries = table.into_table().unwrap().to_entries();
+            expected.sort();
+            entries.sort();
+            assert_eq!(expected, entries);
+        }
+
+        // the buckets have no closure to hash a custom function with
+        assert!(matches!(ConcurrentHashTable::new(8, 4, HashFunction::Custom, HashScheme::LinearProbe, 4,
+                                                  ExtendOption::ExtendBucketSize, 0.9), Err(CrustyError::ValidationError(_))));
+    }
+
+    // function to test that keys are routed with a per-table seed and the chosen combine strategy
+    fn test_routing() {
+        let keys: Vec<(Field, Field)> = (0..100).map(|i| (Field::IntField(i), Field::StringField(String::from("key")))).collect();
+        let routes = |table: &ConcurrentHashTable| -> Vec<usize> {
+            keys.iter().map(|key| table.bucket_index((&key.0, &key.1).into())).collect()
+        };
+        let new_table = || ConcurrentHashTable::new(8, 4, HashFunction::SeededSip, HashScheme::LinearProbe, 4,
+                                                    ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        // a fixed seed would send the keys to the same buckets in every table
+        let (first, second) = (new_table(), new_table());
+        assert_ne!(first.seed, second.seed);
+        assert_ne!(routes(&first), routes(&second));
+
+        let mut table = new_table();
+        for key in keys.iter() {
+            table.insert(key.clone(), 1).unwrap();
+        }
+        table.set_combine_strategy(CombineStrategy::XorShift).unwrap();
+        for (key, bucket_index) in keys.iter().zip(routes(&table)) {
+            let hash = CombineStrategy::XorShift.combine_all(
+                [&key.0, &key.1].into_iter().map(|field| HashFunction::SeededSip.hash_seeded(field, table.seed).unwrap()));
+            assert_eq!(hash % 4, bucket_index);
+            assert_eq!(Some(1), table.get_value((&key.0, &key.1)));
+        }
+        assert_eq!(100, table.len());
+        assert_eq!(100, table.into_table().unwrap().len());
+    }
+
+    mod concurrent {
+        use super::*;
+
+        #[test]
+        fn t_routing() {
+            test_routing();
+        }
+
+        #[test]
+        fn t_concurrent_insert() {
+            test_concurrent_insert();
+        }
+    }
+}
@@ -1,38 +1,57 @@
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
 use std::default::Default;
 use std::fmt;
 use std::fmt::Debug;
+use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering as AtomicOrdering};
 use serde::Serialize;
 use serde::Deserialize;
 use farmhash;
 use t1ha;
 use mur3;
+use metrohash::MetroHash64;
+use wyhash;
+use crate::common::CrustyError;
+use crate::sip::SipHasher13;
 
 /// For each of the dtypes, make sure that there is a corresponding field type.
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Clone, Hash)]
+///
+/// Floats compare, order and hash by their canonical bits, so -0.0 equals 0.0 and every NaN equals every other NaN.
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum Field {
     IntField(i32),
     StringField(String),
+    FloatField(f64),
+    // raw bytes that need not be utf8, such as digests or binary ids
+    BytesField(Vec<u8>),
+    // integers beyond the range of IntField
+    LongField(i64),
 }
 
 impl Field {
     /// Function to convert a Tuple field into bytes for serialization
     ///
-    /// This function always uses least endian byte ordering and stores strings in the format |string length|string contents|.
+    /// This function always uses least endian byte ordering and stores strings and bytes in the format |length|contents|.
     pub fn to_bytes(&self) -> Vec<u8> {
         match self {
             Field::IntField(x) => x.to_le_bytes().to_vec(),
             Field::StringField(s) => {
                 let s_len: usize = s.len();
                 let mut result = s_len.to_le_bytes().to_vec();
-                let mut s_bytes = s.clone().into_bytes();
-                let padding_len: usize = 128 - s_bytes.len();
-                let pad = vec![0; padding_len];
-                s_bytes.extend(&pad);
-                result.extend(s_bytes);
+                result.extend(s.as_bytes());
                 result
             }
+            Field::FloatField(x) => x.to_le_bytes().to_vec(),
+            Field::BytesField(b) => {
+                let mut result = b.len().to_le_bytes().to_vec();
+                result.extend(b);
+                result
+            }
+            Field::LongField(x) => x.to_le_bytes().to_vec(),
         }
     }
 
@@ -44,13 +63,83 @@ impl Field {
         }
     }
 
+    /// Unwraps long integer fields.
+    pub fn unwrap_long_field(&self) -> i64 {
+        match self {
+            Field::LongField(i) => *i,
+            _ => panic!("Expected i64"),
+        }
+    }
+
     /// Unwraps string fields.
     pub fn unwrap_string_field(&self) -> &str {
         match self {
-            Field::StringField(s) => &s,
+            Field::StringField(s) => s,
             _ => panic!("Expected String"),
         }
     }
+
+    /// Unwraps float fields.
+    pub fn unwrap_float_field(&self) -> f64 {
+        match self {
+            Field::FloatField(x) => *x,
+            _ => panic!("Expected f64"),
+        }
+    }
+
+    /// Unwraps byte fields.
+    pub fn unwrap_bytes_field(&self) -> &[u8] {
+        match self {
+            Field::BytesField(b) => b,
+            _ => panic!("Expected bytes"),
+        }
+    }
+
+    /// Float value of the field, None for other fields.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Field::FloatField(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    // bits every equal float shares, both zeros map to +0.0 and every NaN to one NaN
+    fn canonical_bits(x: f64) -> u64 {
+        if x == 0.0 {
+            0
+        } else if x.is_nan() {
+            f64::NAN.to_bits()
+        } else {
+            x.to_bits()
+        }
+    }
+
+    // position of the variant, fields of different variants order by it
+    fn rank(&self) -> u8 {
+        match self {
+            Field::IntField(_) => 0,
+            Field::StringField(_) => 1,
+            Field::FloatField(_) => 2,
+            Field::BytesField(_) => 3,
+            Field::LongField(_) => 4,
+        }
+    }
+
+    /// Integer value of the field, None for other fields.
+    pub fn as_int(&self) -> Option<i32> {
+        match self {
+            Field::IntField(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// String value of the field, None for other fields.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Field::StringField(s) => Some(s),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Field {
@@ -58,16 +147,104 @@ impl fmt::Display for Field {
         match self {
             Field::IntField(x) => write!(f, "{}", x),
             Field::StringField(x) => write!(f, "{}", x),
+            Field::FloatField(x) => write!(f, "{}", x),
+            Field::BytesField(b) => b.iter().try_for_each(|byte| write!(f, "{:02x}", byte)),
+            Field::LongField(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+impl PartialEq for Field {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Field::IntField(a), Field::IntField(b)) => a == b,
+            (Field::StringField(a), Field::StringField(b)) => a == b,
+            (Field::FloatField(a), Field::FloatField(b)) => Field::canonical_bits(*a) == Field::canonical_bits(*b),
+            (Field::BytesField(a), Field::BytesField(b)) => a == b,
+            (Field::LongField(a), Field::LongField(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Field {}
+
+impl Ord for Field {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Field::IntField(a), Field::IntField(b)) => a.cmp(b),
+            (Field::StringField(a), Field::StringField(b)) => a.cmp(b),
+            (Field::FloatField(a), Field::FloatField(b)) => {
+                let (a, b) = (f64::from_bits(Field::canonical_bits(*a)), f64::from_bits(Field::canonical_bits(*b)));
+                a.total_cmp(&b)
+            }
+            (Field::BytesField(a), Field::BytesField(b)) => a.cmp(b),
+            (Field::LongField(a), Field::LongField(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl PartialOrd for Field {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for Field {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        match self {
+            Field::IntField(i) => i.hash(state),
+            Field::StringField(s) => s.hash(state),
+            Field::FloatField(x) => Field::canonical_bits(*x).hash(state),
+            Field::BytesField(b) => b.hash(state),
+            Field::LongField(i) => i.hash(state),
+        }
+    }
+}
+
+/// Conversion of an accumulated value into a Field for output rows
+pub trait IntoField {
+    fn into_field(self) -> Field;
+}
+
+/// Implementation for usize's IntoField trait, counts beyond i32::MAX become a LongField
+impl IntoField for usize {
+    fn into_field(self) -> Field {
+        match i32::try_from(self) {
+            Ok(count) => Field::IntField(count),
+            Err(_) => Field::LongField(i64::try_from(self).unwrap_or(i64::MAX)),
+        }
+    }
+}
+
+/// Implementation for i64's IntoField trait, sums beyond i32 become a LongField
+impl IntoField for i64 {
+    fn into_field(self) -> Field {
+        match i32::try_from(self) {
+            Ok(sum) => Field::IntField(sum),
+            Err(_) => Field::LongField(self),
         }
     }
 }
 
+/// Implementation for f64's IntoField trait, for averages and other fractional results
+impl IntoField for f64 {
+    fn into_field(self) -> Field {
+        Field::FloatField(self)
+    }
+}
+
 /// Hashable trait has three hash functions
 pub trait Hashable {
     fn farm_hash(&self) -> usize;
     fn murmur_hash3(&self) -> usize;
     fn t1ha_hash(&self) -> usize;
     fn std_hash(&self) -> usize;
+    fn metro_hash(&self) -> usize;
+    fn sip_hash(&self, k0: u64, k1: u64) -> usize;
+    fn wy_hash(&self) -> usize;
 }
 
 /// Implementation for Field's Hashable trait
@@ -81,6 +258,15 @@ impl Hashable for Field {
             Field::StringField(s) => {
                 farmhash::hash64(s.as_bytes()) as usize
             }
+            Field::FloatField(x) => {
+                farmhash::hash64(&Field::canonical_bits(*x).to_be_bytes()) as usize
+            }
+            Field::BytesField(b) => {
+                farmhash::hash64(b) as usize
+            }
+            Field::LongField(i) => {
+                farmhash::hash64(&i.to_be_bytes()) as usize
+            }
         };
         result
     }
@@ -94,6 +280,15 @@ impl Hashable for Field {
             Field::StringField(s) => {
                 mur3::murmurhash3_x86_32(s.as_bytes(), 0) as usize
             }
+            Field::FloatField(x) => {
+                mur3::murmurhash3_x86_32(&Field::canonical_bits(*x).to_be_bytes(), 0) as usize
+            }
+            Field::BytesField(b) => {
+                mur3::murmurhash3_x86_32(b, 0) as usize
+            }
+            Field::LongField(i) => {
+                mur3::murmurhash3_x86_32(&i.to_be_bytes(), 0) as usize
+            }
         };
         result
     }
@@ -107,6 +302,15 @@ impl Hashable for Field {
             Field::StringField(x) => {
                 t1ha::t1ha0(x.as_bytes(), 0) as usize
             },
+            Field::FloatField(x) => {
+                t1ha::t1ha0(&Field::canonical_bits(*x).to_be_bytes(), 0) as usize
+            },
+            Field::BytesField(b) => {
+                t1ha::t1ha0(b, 0) as usize
+            },
+            Field::LongField(x) => {
+                t1ha::t1ha0(&x.to_be_bytes(), 0) as usize
+            },
         };
         result
     }
@@ -114,7 +318,7 @@ impl Hashable for Field {
     // using std::hash 64-bit functions to get hash value
     fn std_hash(&self) -> usize {
         let mut hasher = DefaultHasher::new();
-        let result= match self {
+        match self {
             Field::IntField(i) => {
                 i.hash(&mut hasher);
                 hasher.finish() as usize
@@ -123,64 +327,558 @@ impl Hashable for Field {
                 s.hash(&mut hasher);
                 hasher.finish() as usize
             },
+            Field::FloatField(x) => {
+                Field::canonical_bits(*x).hash(&mut hasher);
+                hasher.finish() as usize
+            },
+            Field::BytesField(b) => {
+                b.hash(&mut hasher);
+                hasher.finish() as usize
+            },
+            Field::LongField(i) => {
+                i.hash(&mut hasher);
+                hasher.finish() as usize
+            },
+        }
+    }
+
+    // using MetroHash 64-bit hash functions to get hash value
+    fn metro_hash(&self) -> usize {
+        let mut hasher = MetroHash64::with_seed(0);
+        match self {
+            Field::IntField(i) => hasher.write(&i.to_be_bytes()),
+            Field::StringField(s) => hasher.write(s.as_bytes()),
+            Field::FloatField(x) => hasher.write(&Field::canonical_bits(*x).to_be_bytes()),
+            Field::BytesField(b) => hasher.write(b),
+            Field::LongField(i) => hasher.write(&i.to_be_bytes()),
+        };
+        hasher.finish() as usize
+    }
+
+    // using SipHash-1-3 keyed with the table's seed to get hash value
+    fn sip_hash(&self, k0: u64, k1: u64) -> usize {
+        let mut hasher = SipHasher13::new_with_keys(k0, k1);
+        match self {
+            Field::IntField(i) => hasher.write(&i.to_be_bytes()),
+            Field::StringField(s) => hasher.write(s.as_bytes()),
+            Field::FloatField(x) => hasher.write(&Field::canonical_bits(*x).to_be_bytes()),
+            Field::BytesField(b) => hasher.write(b),
+            Field::LongField(i) => hasher.write(&i.to_be_bytes()),
+        };
+        hasher.finish() as usize
+    }
+
+    // using wyhash 64-bit hash functions to get hash value
+    fn wy_hash(&self) -> usize {
+        let result= match self {
+            Field::IntField(i) => {
+                wyhash::wyhash(&i.to_be_bytes(), 0) as usize
+            }
+            Field::StringField(s) => {
+                wyhash::wyhash(s.as_bytes(), 0) as usize
+            }
+            Field::FloatField(x) => {
+                wyhash::wyhash(&Field::canonical_bits(*x).to_be_bytes(), 0) as usize
+            }
+            Field::BytesField(b) => {
+                wyhash::wyhash(b, 0) as usize
+            }
+            Field::LongField(i) => {
+                wyhash::wyhash(&i.to_be_bytes(), 0) as usize
+            }
         };
         result
     }
 }
 
+/// Implementation for composite keys, all fields are encoded and hashed together once
+impl Hashable for [Field] {
+    fn farm_hash(&self) -> usize {
+        farmhash::hash64(&encode_fields(self)) as usize
+    }
+
+    fn murmur_hash3(&self) -> usize {
+        mur3::murmurhash3_x86_32(&encode_fields(self), 0) as usize
+    }
+
+    fn t1ha_hash(&self) -> usize {
+        t1ha::t1ha0(&encode_fields(self), 0) as usize
+    }
+
+    fn std_hash(&self) -> usize {
+        let mut hasher = DefaultHasher::new();
+        encode_fields(self).hash(&mut hasher);
+        hasher.finish() as usize
+    }
+
+    fn metro_hash(&self) -> usize {
+        let mut hasher = MetroHash64::with_seed(0);
+        hasher.write(&encode_fields(self));
+        hasher.finish() as usize
+    }
+
+    fn sip_hash(&self, k0: u64, k1: u64) -> usize {
+        let mut hasher = SipHasher13::new_with_keys(k0, k1);
+        hasher.write(&encode_fields(self));
+        hasher.finish() as usize
+    }
+
+    fn wy_hash(&self) -> usize {
+        wyhash::wyhash(&encode_fields(self), 0) as usize
+    }
+}
+
+// method to lay the fields out with their tags and lengths so ("ab", "c") and ("a", "bc") differ
+// floats are written by their canonical bits, so fields that compare equal encode alike
+fn encode_fields(fields: &[Field]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for field in fields {
+        match field {
+            Field::FloatField(x) => write_field(&mut buf, &Field::FloatField(f64::from_bits(Field::canonical_bits(*x)))),
+            _ => write_field(&mut buf, field),
+        }
+    }
+    buf
+}
+
 /// Implementation for Field's default trait
 impl Default for Field {
     fn default() -> Self { Field::IntField(0) }
 }
 
 /// Different types of hash functions
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum HashFunction {
     FarmHash,
     MurmurHash3,
     T1haHash,
     StdHash,
+    MetroHash,
+    // SipHash-1-3 keyed per table, so keys can't be picked to collide without knowing the seed
+    SeededSip,
+    WyHash,
+    // the closure a table was given through with_custom_hash, e.g. a precomputed column, held by the table
+    Custom,
+}
+
+impl HashFunction {
+    // hash a field or a composite key with this function, seeding SeededSip with (k0, k1)
+    // None for Custom, whose closure only the table built with it holds
+    pub(crate) fn hash_seeded<T: Hashable + ?Sized>(self, value: &T, seed: (u64, u64)) -> Option<usize> {
+        match self {
+            HashFunction::FarmHash => Some(value.farm_hash()),
+            HashFunction::MurmurHash3 => Some(value.murmur_hash3()),
+            HashFunction::T1haHash => Some(value.t1ha_hash()),
+            HashFunction::StdHash => Some(value.std_hash()),
+            HashFunction::MetroHash => Some(value.metro_hash()),
+            HashFunction::SeededSip => Some(value.sip_hash(seed.0, seed.1)),
+            HashFunction::WyHash => Some(value.wy_hash()),
+            HashFunction::Custom => None,
+        }
+    }
 }
 
 /// Different types of hash schemes
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum HashScheme {
     LinearProbe,
     RobinHood,
     Hopscotch,
+    // probe with a step taken from a second hash of the key, so colliding keys scatter
+    DoubleHash,
+    // every key sits in one of two slots, inserts evict the occupant to its other slot
+    Cuckoo,
+}
+
+/// What an insert does when the key is already in the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnDuplicate {
+    // add the new value to the stored one
+    #[default]
+    Accumulate,
+    // overwrite the stored value with the new one
+    Replace,
+    // ignore the new value
+    KeepFirst,
+    // reject the insert with an error
+    Error,
+}
+
+/// Different ways to merge the hashes of the key fields into one key hash
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CombineStrategy {
+    MulAdd,
+    XorShift,
+    Boost,
+}
+
+impl CombineStrategy {
+    // merge the two field hashes
+    pub(crate) fn combine(self, h0: usize, h1: usize) -> usize {
+        match self {
+            CombineStrategy::MulAdd => h0.wrapping_mul(31).wrapping_add(h1),
+            CombineStrategy::XorShift => {
+                let mut x = h0 ^ h1.rotate_left(32);
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x
+            },
+            // boost::hash_combine
+            CombineStrategy::Boost => {
+                h0 ^ h1.wrapping_add(0x9e3779b9).wrapping_add(h0 << 6).wrapping_add(h0 >> 2)
+            },
+        }
+    }
+
+    // fold the field hashes of a key of any length, a pair combines exactly like combine
+    pub(crate) fn combine_all(self, mut hashes: impl Iterator<Item = usize>) -> usize {
+        match hashes.next() {
+            Some(first) => hashes.fold(first, |h, next| self.combine(h, next)),
+            None => 0,
+        }
+    }
+}
+
+/// Implementation for CombineStrategy's default trait
+impl Default for CombineStrategy {
+    fn default() -> Self { CombineStrategy::MulAdd }
 }
 
 /// Different types of extend hash table methods
-#[derive(Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum ExtendOption {
     ExtendBucketSize,
     ExtendBucketNumber,
+    // pick bucket number when occupancy is skewed across buckets, bucket size otherwise
+    Adaptive,
+}
+
+/// Key of an entry, made of any number of fields
+///
+/// Two-field keys convert from a (Field, Field) tuple, so callers with pairs keep passing tuples.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Key(pub Vec<Field>);
+
+impl Key {
+    pub fn new(fields: Vec<Field>) -> Self {
+        Key(fields)
+    }
+
+    /// Both fields of a two-field key, None for keys of other lengths.
+    pub fn pair(&self) -> Option<(&Field, &Field)> {
+        match self.0.as_slice() {
+            [k0, k1] => Some((k0, k1)),
+            _ => None,
+        }
+    }
+
+    /// Owned fields of a two-field key, None for keys of other lengths.
+    pub fn into_pair(self) -> Option<(Field, Field)> {
+        let mut fields = self.0.into_iter();
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(k0), Some(k1), None) => Some((k0, k1)),
+            _ => None,
+        }
+    }
+}
+
+impl std::ops::Deref for Key {
+    type Target = [Field];
+
+    fn deref(&self) -> &[Field] {
+        &self.0
+    }
+}
+
+impl From<(Field, Field)> for Key {
+    fn from(key: (Field, Field)) -> Self {
+        Key(vec![key.0, key.1])
+    }
+}
+
+impl From<Vec<Field>> for Key {
+    fn from(fields: Vec<Field>) -> Self {
+        Key(fields)
+    }
+}
+
+impl From<Field> for Key {
+    fn from(field: Field) -> Self {
+        Key(vec![field])
+    }
+}
+
+impl PartialEq<(Field, Field)> for Key {
+    fn eq(&self, other: &(Field, Field)) -> bool {
+        self.pair() == Some((&other.0, &other.1))
+    }
+}
+
+impl PartialEq<Key> for (Field, Field) {
+    fn eq(&self, other: &Key) -> bool {
+        other == self
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, field) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", field)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Borrowed key to look entries up with, from a (&Field, &Field) pair or a slice of fields
+#[derive(Debug, Clone, Copy)]
+pub enum KeyRef<'a> {
+    Pair(&'a Field, &'a Field),
+    Fields(&'a [Field]),
+}
+
+impl<'a> KeyRef<'a> {
+    pub fn len(&self) -> usize {
+        match self {
+            KeyRef::Pair(..) => 2,
+            KeyRef::Fields(fields) => fields.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // method to iterate over the fields in order
+    pub fn iter(&self) -> impl Iterator<Item = &'a Field> {
+        let (pair, fields) = match *self {
+            KeyRef::Pair(k0, k1) => (Some([k0, k1]), &[][..]),
+            KeyRef::Fields(fields) => (None, fields),
+        };
+        pair.into_iter().flatten().chain(fields.iter())
+    }
+
+    // method to copy the fields into an owned key
+    pub fn to_key(&self) -> Key {
+        Key(self.iter().cloned().collect())
+    }
+}
+
+impl PartialEq<Key> for KeyRef<'_> {
+    fn eq(&self, other: &Key) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a> From<(&'a Field, &'a Field)> for KeyRef<'a> {
+    fn from(key: (&'a Field, &'a Field)) -> Self {
+        KeyRef::Pair(key.0, key.1)
+    }
+}
+
+impl<'a> From<&'a [Field]> for KeyRef<'a> {
+    fn from(fields: &'a [Field]) -> Self {
+        KeyRef::Fields(fields)
+    }
 }
 
-/// Data structure for hash nodes, contains key, value, and taken attributes
-#[derive(Debug, Clone)]
+impl<'a, const N: usize> From<&'a [Field; N]> for KeyRef<'a> {
+    fn from(fields: &'a [Field; N]) -> Self {
+        KeyRef::Fields(fields)
+    }
+}
+
+impl<'a> From<&'a Vec<Field>> for KeyRef<'a> {
+    fn from(fields: &'a Vec<Field>) -> Self {
+        KeyRef::Fields(fields)
+    }
+}
+
+impl<'a> From<&'a Key> for KeyRef<'a> {
+    fn from(key: &'a Key) -> Self {
+        KeyRef::Fields(&key.0)
+    }
+}
+
+/// Data structure for hash nodes, contains key and taken attributes, the value lives in the table's value column
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct HashNode {
-    pub(crate) key: (Field, Field),
-    pub(crate) value: usize,
+    pub(crate) key: Key,
     taken: bool,
     dis: usize,
+    // placed by insert_into_bucket, so rehashes keep it in its bucket
+    pinned: bool,
+    // left behind by remove, so probes keep going past it while inserts can reuse it
+    deleted: bool,
 }
 
 /// Implementation for HashNode's default trait
 impl Default for HashNode {
     fn default() -> HashNode {
         HashNode {
-            key: (Field::default(), Field::default()),
-            value: 0,
+            // an empty Vec doesn't allocate, so empty slots cost nothing until a key is placed
+            key: Key(Vec::new()),
             taken: false,
             dis: usize::MAX,
+            pinned: false,
+            deleted: false,
+        }
+    }
+}
+
+/// Why a table was rehashed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RehashReason {
+    // a bucket reached the load factor
+    LoadFactor,
+    // probing found no slot for the key
+    ProbeExhausted,
+    // the caller asked for it, e.g. end_bulk or a new combine strategy
+    Explicit,
+    // shrink_to_fit moved the keys into fewer slots
+    Shrink,
+}
+
+/// Reported to the on_rehash callback, dimensions are (bucket size, bucket number)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RehashEvent {
+    pub old_dimensions: (usize, usize),
+    pub new_dimensions: (usize, usize),
+    pub reason: RehashReason,
+}
+
+/// HyperLogLog sketch over 2^14 registers, standard error around 0.8%
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    const P: u32 = 14;
+
+    pub fn new() -> Self {
+        HyperLogLog { registers: vec![0; 1 << Self::P] }
+    }
+
+    // method to add a key, hashed independently of the table's hash function
+    pub fn observe<'a>(&mut self, key: impl Into<KeyRef<'a>>) {
+        let mut fields = key.into().iter().map(|field| field.farm_hash() as u64);
+        let first = fields.next().unwrap_or(0);
+        let h = slot_mix(fields.fold(first, |h, next| slot_mix(h) ^ next));
+        let register = (h >> (64 - Self::P)) as usize;
+        // rank of the first set bit in the remaining 50 bits
+        let rank = ((h << Self::P) | (1 << (Self::P - 1))).leading_zeros() as u8 + 1;
+        if self.registers[register] < rank {
+            self.registers[register] = rank;
+        }
+    }
+
+    // method to get the estimated number of distinct keys observed
+    pub fn estimate(&self) -> usize {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self.registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        // linear counting is more accurate while many registers are still empty
+        if raw <= 2.5 * m && zeros > 0 {
+            (m * (m / zeros as f64).ln()).round() as usize
+        } else {
+            raw.round() as usize
+        }
+    }
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// most entries optimize tries each scheme on
+const OPTIMIZE_SAMPLE: usize = 4096;
+// longest chain of evictions a cuckoo insert follows before extending the table
+const CUCKOO_MAX_KICKS: usize = 32;
+// most entries the Debug output of a table lists
+const DEBUG_ENTRIES: usize = 8;
+
+/// Slots visited while probing for a place to put a key, gathered since the last reset_stats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProbeStats {
+    pub total_probes: u64,
+    // most slots a single probe visited
+    pub max_probe: usize,
+    pub lookups: u64,
+}
+
+// ProbeStats behind atomics, so probes through a shared borrow can count
+#[derive(Debug, Default)]
+pub(crate) struct ProbeCounters {
+    total_probes: AtomicU64,
+    max_probe: AtomicUsize,
+    lookups: AtomicU64,
+}
+
+impl ProbeCounters {
+    fn from_stats(stats: ProbeStats) -> Self {
+        ProbeCounters {
+            total_probes: AtomicU64::new(stats.total_probes),
+            max_probe: AtomicUsize::new(stats.max_probe),
+            lookups: AtomicU64::new(stats.lookups),
+        }
+    }
+
+    // method to count one probe that visited the given number of slots
+    fn record(&self, slots: usize) {
+        self.total_probes.fetch_add(slots as u64, AtomicOrdering::Relaxed);
+        self.max_probe.fetch_max(slots, AtomicOrdering::Relaxed);
+        self.lookups.fetch_add(1, AtomicOrdering::Relaxed);
+    }
+
+    fn snapshot(&self) -> ProbeStats {
+        ProbeStats {
+            total_probes: self.total_probes.load(AtomicOrdering::Relaxed),
+            max_probe: self.max_probe.load(AtomicOrdering::Relaxed),
+            lookups: self.lookups.load(AtomicOrdering::Relaxed),
         }
     }
 }
 
+/// Callback registered through on_rehash
+pub type RehashListener = Box<dyn FnMut(&RehashEvent) + Send + Sync>;
+
+/// Field hash of a table built with with_custom_hash, shared by the tables it rehashes into
+pub type CustomHash = Arc<dyn Fn(&Field) -> u64 + Send + Sync>;
+
+/// Everything a tuner looks at in one place, probe lengths count the slots a lookup visits
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSummary {
+    pub bucket_size: usize,
+    pub bucket_number: usize,
+    pub scheme: HashScheme,
+    pub function: HashFunction,
+    pub live: usize,
+    // live keys over total slots
+    pub load_factor: f64,
+    pub max_probe: usize,
+    pub mean_probe: f64,
+    pub rehash_count: usize,
+    pub memory_bytes: usize,
+}
+
+/// Hash function, scheme, extend option, load factor and H a table is built with
+pub type TableConfig = (HashFunction, HashScheme, ExtendOption, f64, usize);
+
 /// HashTable contains vec of hash buckets
+#[allow(non_snake_case)]
 pub struct HashTable {
     pub(crate) buckets: Vec<Vec<HashNode>>,
+    // value of every slot, shaped like buckets, left empty while every value is 0
+    // so a membership-only table never allocates it
+    pub(crate) values: Vec<Vec<usize>>,
     pub(crate) taken_count: Vec<usize>,
     pub(crate) BUCKET_NUMBER: usize,
     pub(crate) BUCKET_SIZE: usize,
@@ -190,13 +888,246 @@ pub struct HashTable {
     pub(crate) extend_op: ExtendOption,
     pub(crate) hop_info: Vec<Vec<usize>>,
     pub(crate) load_factor: f64,
+    pub(crate) combine: CombineStrategy,
+    pub(crate) on_duplicate: OnDuplicate,
+    pub(crate) membership_only: bool,
+    // NaN float key fields are rejected unless set, then every NaN is stored with the same bits
+    pub(crate) allow_nan: bool,
+    // number of fields of every key in the table, set by the first key placed
+    pub(crate) key_arity: Option<usize>,
+    // float key fields in the same cell of this grid are the same key, hashed by the cell
+    pub(crate) float_epsilon: Option<f64>,
+    pub(crate) min_effective_bucket_size: usize,
+    pub(crate) futile_split_bits: usize,
+    pub(crate) rehash_listener: Option<RehashListener>,
+    pub(crate) insertion_order: Option<Vec<Key>>,
+    pub(crate) bulk: Option<Vec<(Key, usize)>>,
+    // keys of the staged bulk entries, floats by their epsilon cell, so a duplicate check doesn't scan the stage
+    pub(crate) staged_keys: HashSet<Key>,
+    pub(crate) rehash_count: usize,
+    pub(crate) distinct: Option<HyperLogLog>,
+    // the two key words SeededSip hashes with, drawn at random for every new table
+    pub(crate) seed: (u64, u64),
+    // set exactly when the function is HashFunction::Custom
+    pub(crate) custom_hash: Option<CustomHash>,
+    pub(crate) probe_counters: ProbeCounters,
 }
 
-/// Implementation for HashTable's default trait
-impl Default for HashTable {
-    fn default() -> HashTable {
+/// A key's place in a table, from HashTable::entry
+pub enum Entry<'a> {
+    Occupied(OccupiedEntry<'a>),
+    Vacant(VacantEntry<'a>),
+}
+
+/// Entry for a key already in the table, holding the slot it was found in
+pub struct OccupiedEntry<'a> {
+    table: &'a mut HashTable,
+    position: (usize, usize),
+}
+
+/// Entry for a key not in the table yet, holding the key hash to insert it with
+pub struct VacantEntry<'a> {
+    table: &'a mut HashTable,
+    key: Key,
+    hash: usize,
+}
+
+impl<'a> Entry<'a> {
+    // method to get the value, inserting default first if the key is missing
+    // returns an error if the table can't grow to place the key
+    pub fn or_insert(self, default: usize) -> Result<&'a mut usize, CrustyError> {
+        self.or_insert_with(|| default)
+    }
+
+    // method to get the value, inserting the result of default first if the key is missing
+    pub fn or_insert_with(self, default: impl FnOnce() -> usize) -> Result<&'a mut usize, CrustyError> {
+        match self {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    // method to update the value in place if the key is in the table
+    pub fn and_modify(mut self, f: impl FnOnce(&mut usize)) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+
+    // method to get the key of the entry
+    pub fn key(&self) -> &Key {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => &entry.key,
+        }
+    }
+}
+
+impl<'a> OccupiedEntry<'a> {
+    pub fn key(&self) -> &Key {
+        &self.table.buckets[self.position.0][self.position.1].key
+    }
+
+    pub fn get(&self) -> &usize {
+        self.table.value_at(self.position.0, self.position.1)
+    }
+
+    pub fn get_mut(&mut self) -> &mut usize {
+        self.table.value_at_mut(self.position.0, self.position.1)
+    }
+
+    // method to get the value borrowed for as long as the table is
+    pub fn into_mut(self) -> &'a mut usize {
+        self.table.value_at_mut(self.position.0, self.position.1)
+    }
+}
+
+impl<'a> VacantEntry<'a> {
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    // method to insert the key with the value, placing it with the table's scheme
+    // returns an error if the table can't grow to place the key, if a bulk load stages it instead,
+    // or if the table is membership only and has no value to keep
+    pub fn insert(self, value: usize) -> Result<&'a mut usize, CrustyError> {
+        let table = self.table;
+        table.check_has_values()?;
+        let (bucket_index, index) = table.insert_placed(self.hash, self.key, value)?
+            .ok_or_else(|| CrustyError::ExecutionError(String::from("Key is staged until end_bulk")))?;
+        Ok(table.value_at_mut(bucket_index, index))
+    }
+}
+
+/// Iterator from HashTable::drain, moving the entries out in bucket order
+///
+/// Dropping it before the end drops the entries left, so the table is empty either way.
+pub struct Drain<'a> {
+    table: &'a mut HashTable,
+    bucket_index: usize,
+    index: usize,
+}
+
+impl Iterator for Drain<'_> {
+    type Item = (Key, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bucket_index < self.table.buckets.len() {
+            let bucket = &mut self.table.buckets[self.bucket_index];
+            if self.index >= bucket.len() {
+                self.bucket_index += 1;
+                self.index = 0;
+                continue;
+            }
+            let node = &mut bucket[self.index];
+            self.index += 1;
+            if node.taken {
+                let node = std::mem::take(node);
+                self.table.taken_count[self.bucket_index] -= 1;
+                return Some((node.key, self.table.take_value(self.bucket_index, self.index - 1)));
+            }
+        }
+        None
+    }
+}
+
+impl Drop for Drain<'_> {
+    fn drop(&mut self) {
+        // resets the hop info, tombstones and the rest of the bookkeeping too
+        self.table.clear();
+    }
+}
+
+/// Prints the configuration and the first few entries, empty slots are left out
+impl Debug for HashTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Entries<'a>(&'a HashTable);
+        impl Debug for Entries<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut map = f.debug_map();
+                map.entries(self.0.iter().take(DEBUG_ENTRIES).map(|(key, value)| (key.to_string(), value)));
+                if self.0.len() > DEBUG_ENTRIES {
+                    map.finish_non_exhaustive()
+                } else {
+                    map.finish()
+                }
+            }
+        }
+        f.debug_struct("HashTable")
+            .field("function", &self.function)
+            .field("scheme", &self.scheme)
+            .field("extend_op", &self.extend_op)
+            .field("combine", &self.combine)
+            .field("on_duplicate", &self.on_duplicate)
+            .field("bucket_size", &self.BUCKET_SIZE)
+            .field("bucket_number", &self.BUCKET_NUMBER)
+            .field("load_factor", &self.load_factor)
+            .field("H", &self.H)
+            .field("len", &self.len())
+            .field("entries", &Entries(self))
+            .finish()
+    }
+}
+
+/// Serializes as one opaque byte string holding the binary format of to_bytes, not field by field,
+/// so the layout, hop info and taken counts round-trip; save and load go through this impl too
+impl Serialize for HashTable {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.to_bytes().map_err(|e| serde::ser::Error::custom(e.to_string()))?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+/// Deserializes the bytes of to_bytes through from_bytes, rejecting what from_bytes rejects
+impl<'de> Deserialize<'de> for HashTable {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TableVisitor;
+        impl<'de> serde::de::Visitor<'de> for TableVisitor {
+            type Value = HashTable;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "the bytes of a serialized hash table")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, bytes: &[u8]) -> Result<HashTable, E> {
+                HashTable::from_bytes(bytes).map_err(|e| E::custom(e.to_string()))
+            }
+
+            // formats without a byte type hand the bytes over as a sequence
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<HashTable, A::Error> {
+                let mut bytes = Vec::new();
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                self.visit_bytes(&bytes)
+            }
+        }
+        deserializer.deserialize_bytes(TableVisitor)
+    }
+}
+
+/// Moves the entries of the occupied slots into a std HashMap, staged bulk entries are left out
+impl From<HashTable> for HashMap<Key, usize> {
+    fn from(table: HashTable) -> Self {
+        let mut map = HashMap::new();
+        for (bucket_index, bucket) in table.buckets.into_iter().enumerate() {
+            for (index, node) in bucket.into_iter().enumerate() {
+                if node.taken {
+                    map.insert(node.key, table.values.get(bucket_index).map_or(0, |values| values[index]));
+                }
+            }
+        }
+        map
+    }
+}
+
+/// Implementation for HashTable's default trait
+impl Default for HashTable {
+    fn default() -> HashTable {
         HashTable {
             buckets: vec![],
+            values: vec![],
             taken_count: vec![],
             BUCKET_NUMBER: 0,
             BUCKET_SIZE: 0,
@@ -206,12 +1137,30 @@ impl Default for HashTable {
             extend_op: ExtendOption::ExtendBucketSize,
             hop_info: vec![],
             load_factor: 0.9,
+            combine: CombineStrategy::default(),
+            on_duplicate: OnDuplicate::default(),
+            membership_only: false,
+            allow_nan: false,
+            key_arity: None,
+            float_epsilon: None,
+            min_effective_bucket_size: 0,
+            futile_split_bits: 0,
+            rehash_listener: None,
+            distinct: None,
+            insertion_order: None,
+            bulk: None,
+            staged_keys: HashSet::new(),
+            rehash_count: 0,
+            seed: (0, 0),
+            custom_hash: None,
+            probe_counters: ProbeCounters::default(),
         }
     }
 }
 
 impl HashTable {
     // initialize a new hash table with certain BUCKET_SIZE and BUCKET_NUMBER, HashFunction and HashScheme
+    // returns an error for HashFunction::Custom, whose closure only with_custom_hash can give the table
     pub fn new(
         b_size: usize,
         b_num: usize,
@@ -220,9 +1169,28 @@ impl HashTable {
         h: usize,
         op: ExtendOption,
         load_f: f64,
+    ) -> Result<Self, CrustyError> {
+        if func == HashFunction::Custom {
+            return Err(CrustyError::ValidationError(String::from(
+                "A custom hash function needs its closure, build the table with with_custom_hash")));
+        }
+        Ok(Self::new_unchecked(b_size, b_num, func, sche, h, op, load_f))
+    }
+
+    // initialize a new hash table like new without checking the function, callers building a Custom table
+    // give it the closure right after
+    pub(crate) fn new_unchecked(
+        b_size: usize,
+        b_num: usize,
+        func: HashFunction,
+        sche: HashScheme,
+        h: usize,
+        op: ExtendOption,
+        load_f: f64,
     ) -> Self {
         Self {
             buckets: vec![vec![HashNode::default(); b_size]; b_num],
+            values: vec![],
             taken_count: vec![0; b_num],
             BUCKET_NUMBER: b_num,
             BUCKET_SIZE: b_size,
@@ -232,79 +1200,309 @@ impl HashTable {
             extend_op: op,
             hop_info: vec![vec![0; b_size]; b_num],
             load_factor: load_f,
+            combine: CombineStrategy::default(),
+            on_duplicate: OnDuplicate::default(),
+            membership_only: false,
+            allow_nan: false,
+            key_arity: None,
+            float_epsilon: None,
+            min_effective_bucket_size: 0,
+            futile_split_bits: 0,
+            rehash_listener: None,
+            distinct: None,
+            insertion_order: None,
+            bulk: None,
+            staged_keys: HashSet::new(),
+            rehash_count: 0,
+            seed: rand::random(),
+            custom_hash: None,
+            probe_counters: ProbeCounters::default(),
         }
     }
 
-    // method to get the specific bucket base on the key
-    fn get_bucket_index(&self, key: (&Field, &Field)) -> Option<usize> {
-        // using different hash functions to get the index for bucket
-        let bucket_index = match self.function {
-            // using mod 10 to prevent overflow
-            HashFunction::FarmHash => {
-                (key.0.farm_hash() % 10 + key.1.farm_hash() % 10) % self.BUCKET_NUMBER
-            },
-            HashFunction::MurmurHash3 => {
-                (key.0.murmur_hash3() % 10 + key.1.murmur_hash3() % 10) % self.BUCKET_NUMBER
-            },
-            HashFunction::T1haHash => {
-                (key.0.t1ha_hash() % 10 + key.1.t1ha_hash() % 10) % self.BUCKET_NUMBER
-            },
-            HashFunction::StdHash => {
-                (key.0.std_hash() % 10 + key.1.std_hash() % 10) % self.BUCKET_NUMBER
-            },
+    // initialize a new hash table like new, pinning the SeededSip seed to (k0, k1) so placement is reproducible
+    // returns an error for HashFunction::Custom, like new
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_seed(
+        b_size: usize,
+        b_num: usize,
+        func: HashFunction,
+        sche: HashScheme,
+        h: usize,
+        op: ExtendOption,
+        load_f: f64,
+        k0: u64,
+        k1: u64,
+    ) -> Result<Self, CrustyError> {
+        let mut table = Self::new(b_size, b_num, func, sche, h, op, load_f)?;
+        table.seed = (k0, k1);
+        Ok(table)
+    }
+
+    // initialize a new hash table like new, hashing every key field with hash, e.g. a precomputed column
+    // its config alone doesn't carry the closure, so with_capacity and the joins refuse it, and to_bytes
+    // refuses the table since the closure can't be written out
+    pub fn with_custom_hash(
+        b_size: usize,
+        b_num: usize,
+        hash: impl Fn(&Field) -> u64 + Send + Sync + 'static,
+        sche: HashScheme,
+        h: usize,
+        op: ExtendOption,
+        load_f: f64,
+    ) -> Self {
+        let mut table = Self::new_unchecked(b_size, b_num, HashFunction::Custom, sche, h, op, load_f);
+        table.custom_hash = Some(Arc::new(hash));
+        table
+    }
+
+    // initialize a new hash table with certain BUCKET_SIZE and BUCKET_NUMBER and the default configuration:
+    // StdHash, LinearProbe, H of 4, ExtendBucketSize and a load factor of 0.9
+    pub fn with_defaults(b_size: usize, b_num: usize) -> Self {
+        Self::new_unchecked(b_size, b_num, HashFunction::StdHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9)
+    }
+
+    // method to get the (function, scheme, extend option, load factor, H) the table was built with
+    pub fn config(&self) -> TableConfig {
+        (self.function, self.scheme, self.extend_op, self.load_factor, self.H)
+    }
+
+    // method to get the bytes held by the table: slots, hop info, counts and string key contents
+    pub fn memory_bytes(&self) -> usize {
+        let slots = self.BUCKET_SIZE * self.BUCKET_NUMBER;
+        let key_heap: usize = self.buckets.iter()
+            .flat_map(|bucket| bucket.iter())
+            .flat_map(|node| node.key.iter())
+            .map(|field| match field {
+                Field::StringField(s) => s.capacity(),
+                Field::BytesField(b) => b.capacity(),
+                Field::IntField(_) | Field::FloatField(_) | Field::LongField(_) => 0,
+            })
+            .sum();
+        let value_slots: usize = self.values.iter().map(Vec::len).sum();
+        slots * (std::mem::size_of::<HashNode>() + std::mem::size_of::<usize>())
+            + (self.BUCKET_NUMBER + value_slots) * std::mem::size_of::<usize>()
+            + key_heap
+    }
+
+    // method to describe the table's dimensions, occupancy and probe cost in one call
+    pub fn summary(&self) -> TableSummary {
+        let probes: Vec<usize> = self.keys().map(|key| self.probe_path(key).len()).collect();
+        let live = probes.len();
+        let slots = self.BUCKET_SIZE * self.BUCKET_NUMBER;
+        TableSummary {
+            bucket_size: self.BUCKET_SIZE,
+            bucket_number: self.BUCKET_NUMBER,
+            scheme: self.scheme,
+            function: self.function,
+            live,
+            load_factor: if slots == 0 { 0.0 } else { live as f64 / slots as f64 },
+            max_probe: probes.iter().copied().max().unwrap_or(0),
+            mean_probe: if live == 0 { 0.0 } else { probes.iter().sum::<usize>() as f64 / live as f64 },
+            rehash_count: self.rehash_count,
+            memory_bytes: self.memory_bytes(),
+        }
+    }
+
+    // method to get the probe counts gathered while placing keys, including rehashes
+    pub fn probe_stats(&self) -> ProbeStats {
+        self.probe_counters.snapshot()
+    }
+
+    // method to start gathering probe counts from zero
+    pub fn reset_stats(&mut self) {
+        self.probe_counters = ProbeCounters::default();
+    }
+
+    // method to hash a single field with the table's hash function, a float by its epsilon cell if one is set
+    fn field_hash(&self, field: &Field) -> usize {
+        let field = match self.float_epsilon {
+            Some(epsilon) => quantize_field(field, epsilon),
+            None => Cow::Borrowed(field),
+        };
+        match self.custom_hash.as_ref() {
+            Some(hash) => hash(&field) as usize,
+            // new refuses Custom, so every table without a closure has a function that hashes by itself
+            None => self.function.hash_seeded(&*field, self.seed).unwrap_or_default(),
+        }
+    }
+
+    // method to compare a key with a stored key the way the table hashes them
+    fn same_key(&self, key: KeyRef, stored: &Key) -> bool {
+        keys_match(key, stored, self.float_epsilon)
+    }
+
+    // method to get the key hash, keys of more than two fields are hashed once through Hashable for [Field]
+    // and shorter ones merge the hashes of their fields, a custom hash only takes single fields so it always merges
+    pub(crate) fn key_hash<'a>(&self, key: impl Into<KeyRef<'a>>) -> usize {
+        let key = key.into();
+        match key {
+            KeyRef::Fields(fields) if fields.len() > 2 && self.custom_hash.is_none() => self.composite_hash(fields),
+            _ => self.combine.combine_all(key.iter().map(|field| self.field_hash(field))),
+        }
+    }
+
+    // method to hash all fields of a key together, floats by their epsilon cell if one is set
+    fn composite_hash(&self, fields: &[Field]) -> usize {
+        let fields = match self.float_epsilon {
+            Some(epsilon) => Cow::Owned(fields.iter().map(|field| quantize_field(field, epsilon).into_owned()).collect()),
+            None => Cow::Borrowed(fields),
         };
+        // new refuses Custom, and tables with a closure never get here
+        self.function.hash_seeded(&*fields, self.seed).unwrap_or_default()
+    }
+
+    // method to hash a key given as a slice of fields, the same way the table buckets it
+    pub fn slice_hash(&self, key: &[Field]) -> usize {
+        self.key_hash(key)
+    }
+
+    // method to get the key hash to pass to insert_prehashed and get_prehashed,
+    // equal for every table with the same hash function and combine strategy
+    pub fn prehash<'a>(&self, key: impl Into<KeyRef<'a>>) -> u64 {
+        let key = key.into();
+        self.key_hash(key) as u64
+    }
+
+    // method to get the bucket a key hashes to, regardless of how full it is
+    fn hash_bucket_index<'a>(&self, key: impl Into<KeyRef<'a>>) -> usize {
+        let key = key.into();
+        self.bucket_of(self.key_hash(key))
+    }
+
+    // method to get the index a key hashes to inside its bucket
+    fn home_index<'a>(&self, key: impl Into<KeyRef<'a>>) -> usize {
+        let key = key.into();
+        self.home_of(self.key_hash(key))
+    }
+
+    // method to get the bucket of a key hash
+    fn bucket_of(&self, hash: usize) -> usize {
+        hash % self.BUCKET_NUMBER
+    }
+
+    // method to get the index of a key hash inside its bucket
+    fn home_of(&self, hash: usize) -> usize {
+        // remix the combined hash so the slot doesn't follow from the bucket index
+        (slot_mix(hash as u64) as usize) % self.BUCKET_SIZE
+    }
+
+    // method to get the specific bucket base on the key
+    #[cfg(test)]
+    fn get_bucket_index<'a>(&self, key: impl Into<KeyRef<'a>>) -> Option<usize> {
+        let key = key.into();
+        let bucket_index = self.hash_bucket_index(key);
         // check if the bucket is full and return bucket_index
         if self.taken_count[bucket_index] >= self.BUCKET_SIZE {
-            println!("Couldn't get bucket_index!");
             None
         } else {
             Some(bucket_index)
         }
     }
 
+    // method to get the double hashing step of a key hash, from a second mix independent of the home slot
+    // the step is coprime with the bucket size, so the probe visits every slot of the bucket
+    fn step_of(&self, hash: usize) -> usize {
+        if self.BUCKET_SIZE <= 2 {
+            return 1;
+        }
+        let mut step = 1 + (slot_mix((hash as u64).rotate_left(32) ^ 0x5851f42d4c957f2d) as usize) % (self.BUCKET_SIZE - 1);
+        while gcd(step, self.BUCKET_SIZE) != 1 {
+            step = step % (self.BUCKET_SIZE - 1) + 1;
+        }
+        step
+    }
+
+    // method to get the two cuckoo slots of a key hash, the home slot and a different one from a second mix
+    fn cuckoo_slots(&self, hash: usize) -> (usize, usize) {
+        let home = self.home_of(hash);
+        if self.BUCKET_SIZE < 2 {
+            return (home, home);
+        }
+        let offset = 1 + (slot_mix((hash as u64).rotate_left(32) ^ 0x5851f42d4c957f2d) as usize) % (self.BUCKET_SIZE - 1);
+        (home, (home + offset) % self.BUCKET_SIZE)
+    }
+
+    // method to get the probe step of a key under the table's scheme
+    fn probe_step(&self, key: KeyRef) -> usize {
+        if self.scheme == HashScheme::DoubleHash { self.step_of(self.key_hash(key)) } else { 1 }
+    }
+
     // method to use linear probe hashing to resolve collision
     fn linear_probe(
         &self,
-        key: (&Field, &Field),
+        key: KeyRef,
         target_bucket_index: usize,
         index: usize
+    ) -> Option<usize> {
+        self.probe_by_step(key, target_bucket_index, index, 1)
+    }
+
+    // method to probe from index in fixed steps, for linear probing and double hashing
+    fn probe_by_step(
+        &self,
+        key: KeyRef,
+        target_bucket_index: usize,
+        index: usize,
+        step: usize
     ) -> Option<usize> {
         let mut i = index;
+        let mut tombstone = None;
+        let mut visited = 0;
         // check the empty slot in the bucket
         for _ in 0..self.BUCKET_SIZE {
+            visited += 1;
+            // remember the first deleted slot, the key may still be further along
+            if self.buckets[target_bucket_index][i].deleted {
+                tombstone.get_or_insert(i);
+                i = (i + step) % self.BUCKET_SIZE;
+                continue;
+            }
             // if slot haven't been taken, find it
             if !self.buckets[target_bucket_index][i].taken {
                 break;
             }
             // if the key is the same then find it
-            if (&self.buckets[target_bucket_index][i].key.0,
-                &self.buckets[target_bucket_index][i].key.1) == key {
-                break;
+            if self.same_key(key, &self.buckets[target_bucket_index][i].key) {
+                self.probe_counters.record(visited);
+                return Some(i);
             }
-            i = (i + 1) % self.BUCKET_SIZE;
+            i = (i + step) % self.BUCKET_SIZE;
         }
-        Some(i)
+        self.probe_counters.record(visited);
+        // reuse a deleted slot before an empty one
+        Some(tombstone.unwrap_or(i))
     }
 
     // method to use robin hood hashing to resolve collision
     fn robin_hood(
         &self,
-        key: (&Field, &Field),
+        key: KeyRef,
         bucket_index: usize,
         ori_index: usize
     ) -> Option<(usize, usize)> {
         let mut index = ori_index;
         let mut distance = 0;
+        let mut tombstone = None;
+        let mut visited = 0;
         // check the empty slot in the bucket
         for _ in 0..self.BUCKET_SIZE {
+            visited += 1;
+            // remember the first deleted slot, the key may still be further along
+            if self.buckets[bucket_index][index].deleted {
+                tombstone.get_or_insert((index, distance));
+                distance += 1;
+                index = (index + 1) % self.BUCKET_SIZE;
+                continue;
+            }
             // if slot haven't been taken, find it
             if !self.buckets[bucket_index][index].taken {
                 break;
             }
             // if the key is the same then find it
-            if (&self.buckets[bucket_index][index].key.0,
-                &self.buckets[bucket_index][index].key.1) == key {
-                break;
+            if self.same_key(key, &self.buckets[bucket_index][index].key) {
+                self.probe_counters.record(visited);
+                return Some((index, distance));
             }
             // if the distance is larger than origin HashNode then find it
             if distance > self.buckets[bucket_index][index].dis {
@@ -313,40 +1511,105 @@ impl HashTable {
             distance += 1;
             index = (index + 1) % self.BUCKET_SIZE;
         }
-        return Some((index, distance));
+        self.probe_counters.record(visited);
+        // reuse a deleted slot before an empty or displaced one
+        Some(tombstone.unwrap_or((index, distance)))
     }
 
-    // method to get a tuple of (bucket_index, index, distance)
-    fn get_indexes(&mut self, key: (&Field, &Field)) -> Option<(usize, usize, usize)> {
-        // get target bucket index
-        let bucket_index = self.get_bucket_index(key)?;
+    // method to replay a lookup, returning every (bucket_index, index) visited until a hit or miss
+    pub fn probe_path<'a>(&self, key: impl Into<KeyRef<'a>>) -> Vec<(usize, usize)> {
+        let key = key.into();
+        let mut path = Vec::new();
+        if self.BUCKET_NUMBER == 0 || self.BUCKET_SIZE == 0 {
+            return path;
+        }
+        let bucket_index = self.hash_bucket_index(key);
+        self.probe_in(key, bucket_index, self.key_hash(key), |index| path.push((bucket_index, index)));
+        path
+    }
 
-        // using different hash functions to get the index in one bucket
-        let mut index = match self.function {
-            HashFunction::FarmHash => {
-                (key.0.farm_hash() / 10 + key.1.farm_hash() / 100) % self.BUCKET_SIZE
-            },
-            HashFunction::MurmurHash3 => {
-                (key.0.murmur_hash3() / 10 + key.1.murmur_hash3() / 100) % self.BUCKET_SIZE
+    // method to follow the scheme's probe sequence for a key inside the given bucket, from the home slot of hash,
+    // passing every index visited to visit, returns the index holding the key or None once the probe misses
+    fn probe_in(&self, key: KeyRef, bucket_index: usize, hash: usize, mut visit: impl FnMut(usize)) -> Option<usize> {
+        let bucket = self.buckets.get(bucket_index)?;
+        if bucket.is_empty() {
+            return None;
+        }
+        let home = self.home_of(hash);
+        match self.scheme {
+            HashScheme::LinearProbe | HashScheme::RobinHood | HashScheme::DoubleHash => {
+                let step = if self.scheme == HashScheme::DoubleHash { self.step_of(hash) } else { 1 };
+                let mut index = home;
+                for distance in 0..self.BUCKET_SIZE {
+                    visit(index);
+                    let node = &bucket[index];
+                    // probe on past tombstones, the key may have been placed beyond them
+                    if !node.deleted {
+                        // an empty slot, or for robin hood a node closer to its home, ends the probe
+                        if !node.taken || (self.scheme == HashScheme::RobinHood && distance > node.dis) {
+                            return None;
+                        }
+                        if self.same_key(key, &node.key) {
+                            return Some(index);
+                        }
+                    }
+                    index = (index + step) % self.BUCKET_SIZE;
+                }
+                None
             },
-            HashFunction::T1haHash => {
-                (key.0.t1ha_hash() / 10 + key.1.t1ha_hash() / 100) % self.BUCKET_SIZE
+            HashScheme::Hopscotch => {
+                // only the slots recorded in the home slot's hop info
+                for n in (0..self.H).rev() {
+                    if (self.hop_info[bucket_index][home] & (1 << n)) != 0 {
+                        let index = home + (self.H - 1 - n);
+                        visit(index);
+                        if bucket[index].taken && self.same_key(key, &bucket[index].key) {
+                            return Some(index);
+                        }
+                    }
+                }
+                None
             },
-            HashFunction::StdHash => {
-                (key.0.std_hash() / 10 + key.1.std_hash() / 100) % self.BUCKET_SIZE
+            HashScheme::Cuckoo => {
+                // exactly the two candidate slots
+                let (first, second) = self.cuckoo_slots(hash);
+                for index in if first == second { vec![first] } else { vec![first, second] } {
+                    visit(index);
+                    if bucket[index].taken && self.same_key(key, &bucket[index].key) {
+                        return Some(index);
+                    }
+                }
+                None
             },
-        };
+        }
+    }
+
+    // method to get a tuple of (bucket_index, index, distance)
+    #[cfg(test)]
+    fn get_indexes<'a>(&mut self, key: impl Into<KeyRef<'a>>) -> Option<(usize, usize, usize)> {
+        let key = key.into();
+        // get target bucket index
+        let bucket_index = self.get_bucket_index(key)?;
+        self.get_indexes_in(key, bucket_index, self.home_index(key))
+    }
+
+    // method to get a tuple of (bucket_index, index, distance) inside the given bucket, starting from home
+    fn get_indexes_in<'a>(&self, key: impl Into<KeyRef<'a>>, bucket_index: usize, home: usize) -> Option<(usize, usize, usize)> {
+        let key = key.into();
+        let mut index = home;
 
         let mut dis = 0;
-        // check if the index has been taken
-        if self.buckets[bucket_index][index].taken {
+        // check if the index has been taken, or deleted with the key possibly further along
+        if self.buckets[bucket_index][index].taken || self.buckets[bucket_index][index].deleted {
             // using different hashing scheme to solve duplicate
             match self.scheme {
                 HashScheme::LinearProbe => {
                     index = self.linear_probe(key, bucket_index, index).unwrap();
                 },
-                HashScheme::Hopscotch => {
-                    // println!("{}", index);
+                HashScheme::DoubleHash => {
+                    index = self.probe_by_step(key, bucket_index, index, self.probe_step(key)).unwrap();
+                },
+                HashScheme::Hopscotch | HashScheme::Cuckoo => {
                     return Some((bucket_index, index, dis));
                 },
                 HashScheme::RobinHood => {
@@ -360,10 +1623,8 @@ impl HashTable {
 
         // check again and return
         if self.buckets[bucket_index][index].taken &&
-            (&self.buckets[bucket_index][index].key.0 != key.0 ||
-            &self.buckets[bucket_index][index].key.1 != key.1) {
+            !self.same_key(key, &self.buckets[bucket_index][index].key) {
             // return None if couldn't find a available slot
-            println!("Couldn't get indexes.");
             None
         } else {
             // return the bucket_index, index, and distance
@@ -372,792 +1633,4646 @@ impl HashTable {
     }
 
     // method to get the mutable value
-    pub fn get_mut_value(&mut self, key: (&Field, &Field)) -> Option<&mut usize> {
-        if let Some(indexes) = self.get_indexes(key) {
-            Some(&mut self.buckets[indexes.0][indexes.1].value)
-        } else {
-            println!("Couldn't get mut_value");
-            None
+    // return None if the key is not in the table
+    pub fn get_mut_value<'a>(&mut self, key: impl Into<KeyRef<'a>>) -> Option<&mut usize> {
+        let key = key.into();
+        let (bucket_index, index) = self.find_slot(key)?;
+        Some(self.value_at_mut(bucket_index, index))
+    }
+
+    // method to get the mutable value, inserting the default value first if the key is missing
+    // returns an error if the key can't be placed, if a bulk load stages it instead,
+    // or if the table is membership only and has no value to keep
+    pub fn get_mut_or_insert(&mut self, key: impl Into<Key>, default: usize) -> Result<&mut usize, CrustyError> {
+        self.check_has_values()?;
+        let key = key.into();
+        let (bucket_index, index) = match self.find_slot(&key) {
+            Some(position) => position,
+            None => {
+                let hash = self.key_hash(&key);
+                self.insert_placed(hash, key, default)?
+                    .ok_or_else(|| CrustyError::ExecutionError(String::from("Key is staged until end_bulk")))?
+            },
+        };
+        Ok(self.value_at_mut(bucket_index, index))
+    }
+
+    // method to return an error for a membership-only table, which keeps keys but no values
+    fn check_has_values(&self) -> Result<(), CrustyError> {
+        if self.membership_only {
+            return Err(CrustyError::ValidationError(String::from("A membership-only table keeps no values")));
+        }
+        Ok(())
+    }
+
+    // method to get the entry of a key, to read, update or insert its value after a single lookup
+    pub fn entry(&mut self, key: impl Into<Key>) -> Entry<'_> {
+        let key = key.into();
+        let hash = self.key_hash(&key);
+        let position = if self.BUCKET_NUMBER == 0 { None } else { self.find_position_hashed((&key).into(), self.bucket_of(hash), hash) };
+        match position {
+            Some(position) => Entry::Occupied(OccupiedEntry { table: self, position }),
+            None => Entry::Vacant(VacantEntry { table: self, key, hash }),
+        }
+    }
+
+    // method to look a key up without panicking, for keys from untrusted input
+    // return an error if the table has no buckets, or the key has no fields or a different number of
+    // fields than the table's keys, check_invariants goes over every bucket for deeper damage
+    pub fn safe_get<'a>(&self, key: impl Into<KeyRef<'a>>) -> Result<Option<usize>, CrustyError> {
+        let key = key.into();
+        if self.BUCKET_NUMBER == 0 || self.BUCKET_SIZE == 0 || self.buckets.len() != self.BUCKET_NUMBER {
+            return Err(CrustyError::ValidationError(String::from("Table has no slots")));
+        }
+        self.check_arity(key.len())?;
+        if !self.allow_nan && key.iter().any(|field| field.as_float().is_some_and(f64::is_nan)) {
+            return Err(CrustyError::ValidationError(String::from("NaN key fields aren't allowed")));
         }
+        Ok(self.find_slot(key).map(|(bucket_index, index)| *self.value_at(bucket_index, index)))
+    }
+
+    // method to reject a key without fields, or with a different number of fields than the table's keys
+    fn check_arity(&self, arity: usize) -> Result<(), CrustyError> {
+        if arity == 0 {
+            return Err(CrustyError::ValidationError(String::from("Key has no fields")));
+        }
+        match self.key_arity {
+            Some(key_arity) if key_arity != arity => Err(CrustyError::ValidationError(format!(
+                "Key has {} fields but the table's keys have {}",
+                arity, key_arity
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    // method to get the value for a key given as a slice of fields, e.g. taken straight from a row
+    pub fn get_by_slice(&self, key: &[Field]) -> Option<usize> {
+        self.find_slot(key).map(|(bucket_index, index)| *self.value_at(bucket_index, index))
+    }
+
+    // method to insert into a chosen bucket instead of the one the key hashes to,
+    // still placing the key inside the bucket with the table's scheme, values of a key already there are added
+    // the caller is responsible for routing: the key is only found through get_from_bucket with the same bucket,
+    // rehashes and serialization keep it in that bucket, but split and lookups by key go by the key's hash
+    // returns an error for a bucket past the last one
+    pub fn insert_into_bucket(&mut self, bucket: usize, key: impl Into<Key>, value: usize) -> Result<(), CrustyError> {
+        let mut key = key.into();
+        self.check_key(&mut key)?;
+        if bucket >= self.BUCKET_NUMBER {
+            return Err(CrustyError::ValidationError(format!(
+                "Bucket {} out of range of {} buckets",
+                bucket, self.BUCKET_NUMBER
+            )));
+        }
+        let hash = self.key_hash(&key);
+        self.insert_at(Some(bucket), hash, key, value, &accumulate).map(|_| ())
+    }
+
+    // method to get the value of a key placed with insert_into_bucket
+    pub fn get_from_bucket<'a>(&self, bucket: usize, key: impl Into<KeyRef<'a>>) -> Option<&usize> {
+        let key = key.into();
+        let (bucket_index, index) = self.find_position_in(key, bucket)?;
+        Some(self.value_at(bucket_index, index))
+    }
+
+    // method to get the stored key together with its value
+    pub fn get_key_value<'a>(&self, key: impl Into<KeyRef<'a>>) -> Option<(&Key, &usize)> {
+        let key = key.into();
+        let (bucket_index, index) = self.find_slot(key)?;
+        Some((&self.buckets[bucket_index][index].key, self.value_at(bucket_index, index)))
     }
 
     // method to get the value
-    pub fn get_value(&mut self, key: (&Field, &Field)) -> Option<&usize> {
-        if let Some(indexes) = self.get_indexes(key) {
-            if self.scheme == HashScheme::Hopscotch {
-                // check th hop info
-                for n in (0..self.H).rev() {
-                    // loop through the slots base on the hop
-                    if (self.hop_info[indexes.0][indexes.1] & (1 << n as usize)) != 0 {
-                        // compare the key
-                        if &self.buckets[indexes.0][indexes.1 + (self.H - 1 - n)].key.0 != key.0 &&
-                            &self.buckets[indexes.0][indexes.1 + (self.H - 1 - n)].key.1 != key.1 {
-                            return Some(&self.buckets[indexes.0][indexes.1  + (self.H - 1 - n)].value);
-                        }
-                    }
-                }
-                return None;
-            } else {
-                return Some(&self.buckets[indexes.0][indexes.1].value);
-            }
+    pub fn get_value<'a>(&self, key: impl Into<KeyRef<'a>>) -> Option<&usize> {
+        let key = key.into();
+        let (bucket_index, index) = self.find_slot(key)?;
+        Some(self.value_at(bucket_index, index))
+    }
+
+    // method to remove a key, returning its value
+    // the slot is left as a tombstone that lookups skip and inserts reuse, so keys probed past it stay reachable,
+    // except under robin hood, where the keys after it shift back a slot so lookups can still stop at richer nodes
+    // staged bulk entries and keys placed by insert_into_bucket in another bucket aren't found
+    pub fn remove<'a>(&mut self, key: impl Into<KeyRef<'a>>) -> Option<usize> {
+        let key = key.into();
+        let (bucket_index, index) = self.find_slot(key)?;
+        if self.scheme == HashScheme::Hopscotch {
+            // drop the key from its home's neighborhood
+            let home = self.home_index(key);
+            self.hop_info[bucket_index][home] &= !(1 << (self.H - 1 - (index - home)));
+        }
+        let value = self.take_value(bucket_index, index);
+        if self.scheme == HashScheme::RobinHood {
+            self.robin_hood_remove(bucket_index, index);
         } else {
-            println!("Couldn't get value");
-            return None;
+            self.buckets[bucket_index][index] = HashNode { deleted: true, ..HashNode::default() };
+        }
+        self.taken_count[bucket_index] -= 1;
+        let epsilon = self.float_epsilon;
+        if let Some(order) = self.insertion_order.as_mut() {
+            order.retain(|k| !keys_match(key, k, epsilon));
         }
+        Some(value)
     }
 
-    // method to use hopscotch hashing to insert
-    // return 0 if ok, 1 if need to resize
-    fn hopscotch_insert(&mut self, new_key: (Field, Field), new_value: usize, indexes: (usize, usize)){
+    // method to take the node at index out of a robin hood bucket, moving each following node that isn't
+    // in its home slot back by one until an empty slot or a node at home
+    fn robin_hood_remove(&mut self, bucket_index: usize, index: usize) -> HashNode {
+        let removed = std::mem::take(&mut self.buckets[bucket_index][index]);
+        let mut hole = index;
+        loop {
+            let next = (hole + 1) % self.BUCKET_SIZE;
+            let bucket = &mut self.buckets[bucket_index];
+            if next == index || !bucket[next].taken || bucket[next].dis == 0 {
+                break;
+            }
+            let mut node = std::mem::take(&mut bucket[next]);
+            node.dis -= 1;
+            bucket[hole] = node;
+            let value = self.take_value(bucket_index, next);
+            self.set_value(bucket_index, hole, value);
+            hole = next;
+        }
+        removed
+    }
+
+    // method to use hopscotch hashing to insert, extending the table when the neighborhood has no room
+    fn hopscotch_insert(&mut self, pinned: Option<usize>, hash: usize, new_key: Key, new_value: usize,
+                        indexes: (usize, usize), merge: &dyn Fn(&mut usize, usize) -> Result<(), CrustyError>) -> Result<(usize, usize), CrustyError> {
         let bucket_index = indexes.0;
         let index = indexes.1;
-        let mut empty = false;
+        // same key somewhere in the hop chain, then update value instead of looking for room
+        for n in (0..self.H).rev() {
+            if (self.hop_info[bucket_index][index] & (1 << n)) != 0 &&
+                self.same_key((&new_key).into(), &self.buckets[bucket_index][index + (self.H - 1 - n)].key) {
+                self.merge_value(bucket_index, index + (self.H - 1 - n), new_value, merge)?;
+                return Ok((bucket_index, index + (self.H - 1 - n)));
+            }
+        }
         // hop is full
         if self.hop_info[bucket_index][index] >= self.H.pow(2) {
-            println!("No available swaps");
-            self.extend();
-            self.insert(new_key.clone(), new_value);
-            return
+            self.extend(RehashReason::ProbeExhausted)?;
+            return self.insert_node_at(pinned, hash, new_key.clone(), new_value, merge);
         }
 
         // look through neighborhood for empty space or same key
-        let end_of_H = std::cmp::min(index + self.H, self.BUCKET_SIZE);
-        for i in index..end_of_H {
-            if self.buckets[bucket_index][i].taken == false {  // slot is empty, insert the node
+        let end_of_hop = std::cmp::min(index + self.H, self.BUCKET_SIZE);
+        for i in index..end_of_hop {
+            if !self.buckets[bucket_index][i].taken {  // slot is empty, insert the node
+                self.probe_counters.record(i - index + 1);
                 // put entry in empty space
-                self.buckets[bucket_index][i] = HashNode { key: new_key.clone(), value: new_value, taken: true, dis: 0};
+                self.buckets[bucket_index][i] = HashNode { key: new_key.clone(), taken: true, dis: 0, pinned: pinned.is_some(), deleted: false};
+                self.set_value(bucket_index, i, new_value);
                 self.hop_info[bucket_index][index] |= 0b_1 << (self.H - 1 - (i - index));
                 self.taken_count[bucket_index] += 1;
-                return
-            } else if self.buckets[bucket_index][i].key == new_key { // same key, then update value
-                self.buckets[bucket_index][i].value += new_value;
-                return
+                return Ok((bucket_index, i));
+            } else if self.same_key((&new_key).into(), &self.buckets[bucket_index][i].key) { // same key, then update value
+                self.probe_counters.record(i - index + 1);
+                self.merge_value(bucket_index, i, new_value, merge)?;
+                return Ok((bucket_index, i));
             }
         }
 
+        self.probe_counters.record(end_of_hop - index);
         // if no room in neighborhood, look through the rest of the table for an empty space to swap with
         // empty_index -> potentially empty index, start_index -> interval starting index, candidate_index -> swap candidate index
-        for mut empty_index in end_of_H..self.BUCKET_SIZE {
-            if self.buckets[bucket_index][empty_index].taken == false {  // find empty slot
+        for mut empty_index in end_of_hop..self.BUCKET_SIZE {
+            if !self.buckets[bucket_index][empty_index].taken {  // find empty slot
                 let mut start_index = empty_index - (self.H - 1);
                 'inner: loop {
+                    // start_index only moves right before continue 'inner, which restarts this loop over the new range
+                    #[allow(clippy::mut_range_bound)]
                     for candidate_index in start_index..(start_index + self.H) {
                         if self.hop_info[bucket_index][candidate_index] > 0 {
                             // check every digit in H
                             for n in (0..self.H).rev() {
-                                if (self.hop_info[bucket_index][candidate_index] & (1 << n as usize)) != 0 {
+                                if (self.hop_info[bucket_index][candidate_index] & (1 << n)) != 0 {
                                     // no available slot before the empty
                                     if candidate_index + (self.H - 1 - n) >= empty_index {
-                                        println!("No available swaps");
-                                        self.extend();
-                                        self.insert(new_key.clone(), new_value);
-                                        return
+                                        self.extend(RehashReason::ProbeExhausted)?;
+                                        return self.insert_node_at(pinned, hash, new_key.clone(), new_value, merge);
                                     }
                                     // swap the target with empty slot
                                     self.buckets[bucket_index][empty_index] = self.buckets[bucket_index][candidate_index + (self.H - 1 - n)].clone();
                                     self.buckets[bucket_index][empty_index].taken = true;
                                     self.buckets[bucket_index][candidate_index + (self.H - 1 - n)] = HashNode::default();
+                                    let value = self.take_value(bucket_index, candidate_index + (self.H - 1 - n));
+                                    self.set_value(bucket_index, empty_index, value);
                                     self.hop_info[bucket_index][candidate_index] -= usize::pow(2, n as u32);
-                                    // if empty_index - candidate_index > 3 {
-                                    //     println!("????????");
-                                    //     panic!();
-                                    // }
                                     self.hop_info[bucket_index][candidate_index] += usize::pow(2,  (self.H - 1 - (empty_index - candidate_index)) as u32);
                                     empty_index = candidate_index + (self.H - 1 - n);
                                     break;
                                 }
                             }
-                            start_index = empty_index.checked_sub((self.H - 1)).unwrap_or(0);
+                            start_index = empty_index.saturating_sub(self.H - 1);
 
                             if empty_index - index < self.H {
                                 // we are now within the neighborhood, so put new entry in empty space
-                                self.buckets[bucket_index][empty_index] = HashNode { key: new_key.clone(), value: new_value, taken: true, dis: 0};
-                                self.hop_info[bucket_index][index] |= 1 << (self.H - 1 - (empty_index - index) as usize);
+                                self.buckets[bucket_index][empty_index] = HashNode { key: new_key.clone(), taken: true, dis: 0, pinned: pinned.is_some(), deleted: false};
+                                self.set_value(bucket_index, empty_index, new_value);
+                                self.hop_info[bucket_index][index] |= 1 << (self.H - 1 - (empty_index - index));
                                 self.taken_count[bucket_index] += 1;
-                                return
+                                return Ok((bucket_index, empty_index));
                             } else {
                                 // look for another swap to move empty closer (or into) neighborhood
                                 continue 'inner
                             }
                         }
                     }
-                    // can't swap anything with empty space, need to resize
-                    println!("Can't swap it into the neighborhood! Extended!");
-                    self.extend();
-                    self.insert(new_key.clone(), new_value);
-                    return
+                    // can't swap anything into the neighborhood, need to resize
+                    self.extend(RehashReason::ProbeExhausted)?;
+                    return self.insert_node_at(pinned, hash, new_key.clone(), new_value, merge);
                 }
             }
         }
-        println!("No empty space!");
-        self.extend();
-        self.insert(new_key.clone(), new_value);
-        return
+        // no empty space
+        self.extend(RehashReason::ProbeExhausted)?;
+        self.insert_node_at(pinned, hash, new_key.clone(), new_value, merge)
     }
 
-    // method to insert a new HashNode
-    pub fn insert(&mut self, new_key: (Field, Field), new_value: usize) {
-        // extent the hash table once reach the load limit
-        for i in 0..self.BUCKET_NUMBER {
-            if (self.BUCKET_SIZE as f64 * self.load_factor).floor() as usize <= self.taken_count[i] {
-                println!("Rehash b/c load factor");
-                self.extend();
-                println!("Rehash finished");
-                self.insert(new_key.clone(), new_value);
+    // method to use cuckoo hashing to insert, evicting occupants to their other slot and extending the table
+    // once a chain of evictions runs past CUCKOO_MAX_KICKS, the key left without a slot is placed after extending
+    fn cuckoo_insert(&mut self, bucket_index: usize, hash: usize, new_key: Key, new_value: usize, pinned: bool,
+                     merge: &dyn Fn(&mut usize, usize) -> Result<(), CrustyError>) -> Result<(usize, usize), CrustyError> {
+        let (first, second) = self.cuckoo_slots(hash);
+        // same key in one of its slots, then update value
+        let epsilon = self.float_epsilon;
+        for index in [first, second] {
+            let node = &self.buckets[bucket_index][index];
+            if node.taken && keys_match((&new_key).into(), &node.key, epsilon) {
+                self.merge_value(bucket_index, index, new_value, merge)?;
+                return Ok((bucket_index, index));
             }
         }
-
-        // get the tuple of (bucket_index, index)
-        if let Some(indexes) =
-        self.get_indexes((&new_key.0, &new_key.1)){
-            if self.scheme == HashScheme::Hopscotch { // using helper method to insert w/ hopscotch
-                self.hopscotch_insert(new_key.clone(), new_value, (indexes.0, indexes.1));
-            } else if self.buckets[indexes.0][indexes.1].key == new_key { // check if the the key is already existed in the table
-                // add new value to the old one
-                self.buckets[indexes.0][indexes.1].value += new_value;
-            } else if self.buckets[indexes.0][indexes.1].taken == false { // if not been taken
-                // directly insert the new value
-                self.buckets[indexes.0][indexes.1] = HashNode {key: new_key, value: new_value, taken: true, dis: indexes.2};
-                self.taken_count[indexes.0] += 1;
-            } else { // robin hood situation
-                // insert the new node and then original node
-                let ori_node = self.buckets[indexes.0][indexes.1].clone();
-                self.buckets[indexes.0][indexes.1] = HashNode {key: new_key, value: new_value, taken: true, dis: indexes.2};
-                self.insert(ori_node.key, ori_node.value);
+        let mut homeless = HashNode { key: new_key, taken: true, dis: 0, pinned, deleted: false };
+        let mut homeless_value = new_value;
+        let mut slots = (first, second);
+        let mut evicted_from = None;
+        // the new key's slot, None while it is the homeless one
+        let mut placed = None;
+        for _ in 0..CUCKOO_MAX_KICKS {
+            if let Some(free) = [slots.0, slots.1].into_iter().find(|&index| !self.buckets[bucket_index][index].taken) {
+                self.buckets[bucket_index][free] = homeless;
+                self.set_value(bucket_index, free, homeless_value);
+                self.taken_count[bucket_index] += 1;
+                return Ok((bucket_index, placed.unwrap_or(free)));
             }
-        } else {
-            println!("Rehash b/c can't get index");
-            self.extend();
-            println!("Rehash finished");
-            self.insert(new_key.clone(), new_value);
-        };
+            // take the slot the homeless key wasn't just evicted from
+            let target = if evicted_from == Some(slots.0) { slots.1 } else { slots.0 };
+            std::mem::swap(&mut homeless, &mut self.buckets[bucket_index][target]);
+            let evicted_value = self.take_value(bucket_index, target);
+            self.set_value(bucket_index, target, homeless_value);
+            homeless_value = evicted_value;
+            placed = match placed {
+                None => Some(target),
+                Some(index) if index == target => None,
+                Some(index) => Some(index),
+            };
+            evicted_from = Some(target);
+            slots = self.cuckoo_slots(self.key_hash(&homeless.key));
+        }
+        // the chain is too long, likely a cycle, so grow and place the evicted key there
+        // the extend moves a new key that already has a slot, so it's looked up again afterwards
+        let moved = placed.map(|index| self.buckets[bucket_index][index].key.clone());
+        self.extend(RehashReason::ProbeExhausted)?;
+        let homeless_hash = self.key_hash(&homeless.key);
+        let position = self.insert_node_at(homeless.pinned.then_some(bucket_index), homeless_hash, homeless.key, homeless_value, merge)?;
+        match moved {
+            Some(key) => {
+                let bucket_index = if pinned { bucket_index } else { self.bucket_of(hash) };
+                self.find_position_hashed((&key).into(), bucket_index, hash)
+                    .ok_or_else(|| CrustyError::ExecutionError(String::from("Key went missing while extending")))
+            },
+            None => Ok(position),
+        }
     }
 
-    // method to extend the bucket number / bucket size and then rehash the table
-    fn extend(&mut self) {
-        assert!(self.buckets.len() > 0);
-        let mut new_self = match self.extend_op {
-            // extend the bucket size to twice of the original bucket size
-            ExtendOption::ExtendBucketSize => {
-                Self {
-                    buckets: vec![vec![HashNode::default(); self.BUCKET_SIZE * 2]; self.BUCKET_NUMBER],
-                    taken_count: vec![0; self.BUCKET_NUMBER],
-                    BUCKET_SIZE: self.BUCKET_SIZE * 2,
-                    BUCKET_NUMBER: self.BUCKET_NUMBER,
-                    function: self.function,
-                    scheme: self.scheme,
-                    H: self.H,
-                    extend_op: self.extend_op,
-                    hop_info: vec![vec![0; self.BUCKET_SIZE * 2]; self.BUCKET_NUMBER],
-                    load_factor: self.load_factor,
-                }
-            },
-            // extend the bucket number to twice of than original bucket number
-            ExtendOption::ExtendBucketNumber => {
-                Self {
-                    buckets: vec![vec![HashNode::default(); self.BUCKET_SIZE]; self.BUCKET_NUMBER * 2],
-                    taken_count: vec![0; self.BUCKET_NUMBER * 2],
-                    BUCKET_SIZE: self.BUCKET_SIZE,
-                    BUCKET_NUMBER: self.BUCKET_NUMBER * 2,
-                    function: self.function,
-                    scheme: self.scheme,
-                    H: self.H,
-                    extend_op: self.extend_op,
-                    hop_info: vec![vec![0; self.BUCKET_SIZE]; self.BUCKET_NUMBER * 2],
-                    load_factor: self.load_factor,
-                }
-            }
-        };
+    // method to insert a new HashNode, recording the key if insertion order is kept
+    // returns an error for a duplicate key under OnDuplicate::Error, or if the table can't grow to place the key
+    pub fn insert(&mut self, new_key: impl Into<Key>, new_value: usize) -> Result<(), CrustyError> {
+        let new_key = new_key.into();
+        let hash = self.key_hash(&new_key);
+        self.insert_prehashed(hash as u64, new_key, new_value)
+    }
 
-        // insert the <key, value> to new hash table
-        for bucket in self.buckets.iter() {
-            for node in bucket.iter() {
-                if node.taken {
-                    new_self.insert(node.key.clone(), node.value.clone());
-                }
+    // method to insert with a key hash computed by prehash, skipping the hashing
+    // the hash must be the key's, or later lookups and rehashes won't find the key where it was put
+    pub fn insert_prehashed(&mut self, hash: u64, new_key: impl Into<Key>, new_value: usize) -> Result<(), CrustyError> {
+        self.insert_placed(hash as usize, new_key.into(), new_value).map(|_| ())
+    }
+
+    // method to insert with a computed key hash, returning the slot the key ends up in
+    // or None if a bulk load staged it instead
+    fn insert_placed(&mut self, hash: usize, mut new_key: Key, new_value: usize) -> Result<Option<(usize, usize)>, CrustyError> {
+        self.check_key(&mut new_key)?;
+        // keys staged by a bulk load count as in the table
+        if self.on_duplicate == OnDuplicate::Error && !self.membership_only {
+            let staged = self.bulk.is_some() && self.staged_keys.contains(&cell_key(&new_key, self.float_epsilon));
+            if staged || self.find_position_hashed((&new_key).into(), self.bucket_of(hash), hash).is_some() {
+                return Err(CrustyError::ExecutionError(String::from("Key is already in the table")));
             }
         }
-        *self = new_self;
+        // staged until end_bulk
+        if let Some(staged) = self.bulk.as_mut() {
+            self.staged_keys.insert(cell_key(&new_key, self.float_epsilon));
+            staged.push((new_key, new_value));
+            return Ok(None);
+        }
+        // keys only, so there is nothing to merge
+        if self.membership_only {
+            return self.insert_at(None, hash, new_key, 0, &|_, _| Ok(())).map(Some);
+        }
+        let position = match self.on_duplicate {
+            OnDuplicate::Accumulate => self.insert_at(None, hash, new_key, new_value, &accumulate),
+            OnDuplicate::Replace => self.insert_at(None, hash, new_key, new_value, &|old, new| {
+                *old = new;
+                Ok(())
+            }),
+            OnDuplicate::KeepFirst | OnDuplicate::Error => self.insert_at(None, hash, new_key, new_value, &|_, _| Ok(())),
+        }?;
+        Ok(Some(position))
     }
-}
 
-#[cfg(test)]
-mod test_hash {
-    use super::*;
+    // method to get the value of a key with a key hash computed by prehash, skipping the hashing
+    pub fn get_prehashed<'a>(&self, hash: u64, key: impl Into<KeyRef<'a>>) -> Option<usize> {
+        let key = key.into();
+        if self.BUCKET_NUMBER == 0 {
+            return None;
+        }
+        self.find_position_hashed(key, self.bucket_of(hash as usize), hash as usize)
+            .map(|(bucket_index, index)| *self.value_at(bucket_index, index))
+    }
 
-    // function to test extend
-    pub fn test_extend() {
-        let mut table = HashTable::new(
-            5,
-            1,
-            HashFunction::FarmHash,
-            HashScheme::LinearProbe,
-            4,
-            ExtendOption::ExtendBucketSize,
-            0.75,
-        );
+    // method to insert every item, reserving room for the iterator's size hint first so placing them
+    // doesn't rehash along the way, duplicate keys combine as they do through insert
+    pub fn insert_many<K: Into<Key>>(&mut self, items: impl IntoIterator<Item = (K, usize)>) -> Result<(), CrustyError> {
+        let items = items.into_iter();
+        self.reserve(items.size_hint().0)?;
+        for (key, value) in items {
+            self.insert(key, value)?;
+        }
+        Ok(())
+    }
 
-        let name = Field::StringField(String::from("Adam"));
-        let course_taken = Field::IntField(0);
-        table.insert((name, course_taken), 1);
+    // method to choose what inserting an existing key does
+    pub fn set_on_duplicate(&mut self, on_duplicate: OnDuplicate) {
+        self.on_duplicate = on_duplicate;
+    }
 
-        let name = Field::StringField(String::from("Ben"));
-        let course_taken = Field::IntField(1);
-        table.insert((name, course_taken), 1);
+    // method to only track which keys are in the table, inserts store 0 and never merge values
+    // nodes carry no value, and a table whose values are all 0 allocates no value column, so a membership-only
+    // table takes less memory unless a mutable value is asked for
+    pub fn set_membership_only(&mut self, membership_only: bool) {
+        self.membership_only = membership_only;
+    }
 
-        let name = Field::StringField(String::from("Chris"));
-        let course_taken = Field::IntField(1);
-        table.insert((name, course_taken), 1);
+    // method to accept keys with NaN float fields, which insert and safe_get reject by default
+    // every NaN is stored with the same bits, so NaN keys match each other like any other equal keys
+    pub fn set_allow_nan(&mut self, allow_nan: bool) {
+        self.allow_nan = allow_nan;
+    }
 
-        // Before first rehash: 5 * 0.75 = 3.75
-        assert_eq!(3, table.taken_count[0]);
-        assert_eq!(5, table.BUCKET_SIZE);
+    // method to reject a key with a NaN float field, or give its NaNs the canonical bits if they're allowed,
+    // and a key whose number of fields doesn't match the table's, the first key sets it
+    fn check_key(&mut self, key: &mut Key) -> Result<(), CrustyError> {
+        self.check_arity(key.len())?;
+        self.key_arity = Some(key.len());
+        for field in key.0.iter_mut() {
+            if let Field::FloatField(x) = field {
+                if x.is_nan() {
+                    if !self.allow_nan {
+                        return Err(CrustyError::ValidationError(String::from("NaN key fields aren't allowed")));
+                    }
+                    *x = f64::NAN;
+                }
+            }
+        }
+        Ok(())
+    }
 
-        let name = Field::StringField(String::from("David"));
-        let course_taken = Field::IntField(1);
-        table.insert((name, course_taken), 1);
+    // method to treat float key fields as equal when they round to the same multiple of epsilon,
+    // so 1.0000001 finds 1.0 under an epsilon of 1e-3, None compares floats exactly again
+    // values within epsilon on either side of a cell boundary still differ
+    // returns an error for an epsilon that isn't positive and finite, or once the table holds keys
+    pub fn set_float_epsilon(&mut self, epsilon: Option<f64>) -> Result<(), CrustyError> {
+        if epsilon.is_some_and(|epsilon| !(epsilon > 0.0 && epsilon.is_finite())) {
+            return Err(CrustyError::ValidationError(String::from("Float epsilon must be positive and finite")));
+        }
+        // keys already placed or staged are hashed by their exact value, and may merge under the new epsilon
+        if !self.is_empty() || !self.staged_keys.is_empty() {
+            return Err(CrustyError::ExecutionError(String::from("Can't change the float epsilon of a table holding keys")));
+        }
+        self.float_epsilon = epsilon;
+        Ok(())
+    }
 
-        // After first rehash: 10 * 0.75 = 7.5
-        assert_eq!(4, table.taken_count[0]);
-        assert_eq!(10, table.BUCKET_SIZE);
+    // method to check if a key is in the table
+    pub fn contains_key<'a>(&self, key: impl Into<KeyRef<'a>>) -> bool {
+        let key = key.into();
+        self.find_slot(key).is_some()
+    }
 
-        let name = Field::StringField(String::from("Eva"));
-        let course_taken = Field::IntField(85);
-        table.insert((name, course_taken), 1);
+    // method to get the number of keys in the table, staged bulk entries aren't counted until end_bulk
+    pub fn len(&self) -> usize {
+        self.taken_count.iter().sum()
+    }
 
-        let name = Field::StringField(String::from("Frank"));
-        let course_taken = Field::IntField(16);
-        table.insert((name, course_taken), 1);
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-        let name = Field::StringField(String::from("Grant"));
-        let course_taken = Field::IntField(63);
-        table.insert((name, course_taken), 1);
+    // method to empty the table for reuse, keeping its dimensions and configuration
+    // tracked insertion order, distinct counts and staged bulk entries are emptied as well
+    pub fn clear(&mut self) {
+        for bucket in self.buckets.iter_mut() {
+            bucket.fill(HashNode::default());
+        }
+        self.values.clear();
+        self.taken_count.fill(0);
+        for hops in self.hop_info.iter_mut() {
+            hops.fill(0);
+        }
+        if let Some(order) = self.insertion_order.as_mut() {
+            order.clear();
+        }
+        if let Some(staged) = self.bulk.as_mut() {
+            staged.clear();
+        }
+        self.staged_keys.clear();
+        if self.distinct.is_some() {
+            self.distinct = Some(HyperLogLog::new());
+        }
+    }
 
-        // before second rehash
-        assert_eq!(7, table.taken_count[0]);
-        assert_eq!(10, table.BUCKET_SIZE);
+    // method to move every entry out of the table, which is empty once the iterator is dropped
+    // staged bulk entries aren't yielded, they're emptied like clear does
+    pub fn drain(&mut self) -> Drain<'_> {
+        Drain { table: self, bucket_index: 0, index: 0 }
+    }
 
-        let name = Field::StringField(String::from("Hilton"));
-        let course_taken = Field::IntField(11);
-        table.insert((name, course_taken), 1);
+    // method to insert a new HashNode, combining the value of a duplicate key through merge (max, min, ...)
+    // inserts during a bulk load go straight into the table, since end_bulk replays staged entries through
+    // insert and the table's on_duplicate, which would drop merge
+    pub fn insert_with(&mut self, new_key: impl Into<Key>, new_value: usize,
+                       merge: impl Fn(&mut usize, usize)) -> Result<(), CrustyError> {
+        let mut new_key = new_key.into();
+        self.check_key(&mut new_key)?;
+        let hash = self.key_hash(&new_key);
+        self.insert_at(None, hash, new_key, new_value, &|old, new| {
+            merge(old, new);
+            Ok(())
+        }).map(|_| ())
+    }
 
-        // after second rehash
-        assert_eq!(8, table.taken_count[0]);
-        assert_eq!(20, table.BUCKET_SIZE);
+    // method to insert a new HashNode into the pinned bucket, or the bucket the key hashes to,
+    // returning the slot the key ends up in
+    fn insert_at(&mut self, pinned: Option<usize>, hash: usize, new_key: Key, new_value: usize,
+                 merge: &dyn Fn(&mut usize, usize) -> Result<(), CrustyError>) -> Result<(usize, usize), CrustyError> {
+        if let Some(sketch) = self.distinct.as_mut() {
+            sketch.observe(&new_key);
+        }
+        if self.insertion_order.is_none() {
+            return self.insert_node_at(pinned, hash, new_key, new_value, merge);
+        }
+        let before: usize = self.taken_count.iter().sum();
+        let position = self.insert_node_at(pinned, hash, new_key.clone(), new_value, merge)?;
+        // only a key that wasn't in the table before adds a live node
+        if self.taken_count.iter().sum::<usize>() > before {
+            if let Some(order) = self.insertion_order.as_mut() {
+                order.push(new_key);
+            }
+        }
+        Ok(position)
     }
 
-    // function to test hopscotch
-    pub fn test_hopscotch() {
-        let mut table = HashTable::new(
-            13,
-            1,
-            HashFunction::FarmHash,
-            HashScheme::Hopscotch,
-            4,
-            ExtendOption::ExtendBucketSize,
-            1.0,
-        );
-        table.buckets[0][0].taken = true;
-        table.buckets[0][0].key = (Field::StringField(String::from("M")), Field::IntField(0));
-        table.buckets[0][1].taken = true;
-        table.buckets[0][1].key = (Field::StringField(String::from("M")), Field::IntField(1));
-        table.buckets[0][3].taken = true;
-        table.buckets[0][3].key = (Field::StringField(String::from("M")), Field::IntField(3));
-        table.hop_info[0][3] = 4; // 0100
-        table.buckets[0][4].taken = true;
-        table.buckets[0][4].key = (Field::StringField(String::from("M")), Field::IntField(4));
-        table.buckets[0][5].taken = true;
-        table.hop_info[0][5] = 10; // 1010
-        table.buckets[0][6].taken = true;
-        table.buckets[0][6].key = (Field::StringField(String::from("M")), Field::IntField(6));
-        table.buckets[0][7].taken = true;
-        table.buckets[0][7].key = (Field::StringField(String::from("M")), Field::IntField(7));
-        table.hop_info[0][7] = 4; // 0100
-        table.buckets[0][8].taken = true;
-        table.buckets[0][8].key = (Field::StringField(String::from("M")), Field::IntField(8));
-        table.buckets[0][9].taken = true;
-        table.buckets[0][9].key = (Field::StringField(String::from("M")), Field::IntField(9));
-        table.hop_info[0][9] = 4; // 0100
-        table.buckets[0][10].taken = true;
-        table.buckets[0][10].key = (Field::StringField(String::from("M")), Field::IntField(10));
-        table.buckets[0][11].taken = true;
-        table.buckets[0][11].key = (Field::StringField(String::from("M")), Field::IntField(11));
-        table.taken_count[0] = 11;
+    // method to iterate over the entries in bucket order
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &usize)> {
+        self.buckets.iter()
+            .enumerate()
+            .flat_map(move |(bucket_index, bucket)| bucket.iter()
+                .enumerate()
+                .filter(|(_, node)| node.taken)
+                .map(move |(index, node)| (&node.key, self.value_at(bucket_index, index))))
+    }
 
-        let name = Field::StringField(String::from("Mark"));
-        let course_taken = Field::IntField(8);
-        // assert_eq!(table.get_indexes((&name, &course_taken)).unwrap().1, 3);
-        table.insert((name, course_taken), 1);
-        assert_eq!(table.hop_info[0][9], 1);
-        assert_eq!(table.hop_info[0][7], 1);
-        assert_eq!(table.hop_info[0][5], 3);
-        assert_eq!(table.hop_info[0][3], 6);
-        assert_eq!(table.buckets[0][12].taken, true);
-        assert_eq!(table.taken_count[0], 12);
+    // method to iterate over the entries in bucket order with mutable values
+    // allocates the value column first if the table has none, like get_mut_value
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Key, &mut usize)> {
+        self.ensure_values();
+        self.buckets.iter()
+            .zip(self.values.iter_mut())
+            .flat_map(|(bucket, values)| bucket.iter().zip(values.iter_mut()))
+            .filter(|(node, _)| node.taken)
+            .map(|(node, value)| (&node.key, value))
     }
 
-    // function to test hopscotch
-    pub fn test_hopscotch2() {
-        let mut table = HashTable::new(
-            100,
-            1,
-            HashFunction::FarmHash,
-            HashScheme::Hopscotch,
-            10,
-            ExtendOption::ExtendBucketSize,
-            0.75,
-        );
+    // method to iterate over the keys in bucket order
+    pub fn keys(&self) -> impl Iterator<Item = &Key> {
+        self.iter().map(|(key, _)| key)
+    }
 
-        let name1 = Field::StringField(String::from("Adamdsf"));
-        let course_taken1 = Field::IntField(0);
-        table.insert((name1, course_taken1), 1);
+    // method to start a bulk load, inserts are staged without load-factor checks or extends
+    // staged entries aren't visible to lookups until end_bulk
+    pub fn begin_bulk(&mut self) {
+        if self.bulk.is_none() {
+            self.bulk = Some(Vec::new());
+        }
+    }
 
-        let name2 = Field::StringField(String::from("Bensdfsdfds"));
-        let course_taken2 = Field::IntField(1);
-        table.insert((name2, course_taken2), 1);
+    // method to finish a bulk load, rehashing once into a table sized for every staged entry
+    // on an error the entries that weren't placed stay staged, so end_bulk can be called again
+    pub fn end_bulk(&mut self) -> Result<(), CrustyError> {
+        match self.bulk.as_ref() {
+            Some(staged) if !staged.is_empty() => {},
+            _ => {
+                self.bulk = None;
+                return Ok(());
+            },
+        }
+        self.check_buckets()?;
+        // upper bound on the live keys
+        let total = self.taken_count.iter().sum::<usize>() + self.bulk.as_ref().map_or(0, Vec::len);
+        let (b_size, b_num) = self.capacity_dimensions(total);
+        if (b_size, b_num) != (self.BUCKET_SIZE, self.BUCKET_NUMBER) {
+            self.rebuild(b_size, b_num, RehashReason::Explicit)?;
+        }
+        // the stage is taken out so insert places the entries instead of staging them again
+        // and the staged keys are dropped so insert doesn't take each entry for a duplicate of itself
+        let mut staged = self.bulk.take().unwrap_or_default().into_iter();
+        self.staged_keys.clear();
+        while let Some((key, value)) = staged.next() {
+            if let Err(error) = self.insert(key.clone(), value) {
+                let rest: Vec<(Key, usize)> = std::iter::once((key, value)).chain(staged).collect();
+                self.staged_keys = rest.iter().map(|(key, _)| cell_key(key, self.float_epsilon)).collect();
+                self.bulk = Some(rest);
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
 
-        let name3 = Field::StringField(String::from("Chrissdfds"));
-        let course_taken3 = Field::IntField(1);
-        table.insert((name3, course_taken3), 1);
+    // method to return an error for a table without buckets, which has nowhere to place a key
+    fn check_buckets(&self) -> Result<(), CrustyError> {
+        if self.buckets.is_empty() {
+            return Err(CrustyError::ExecutionError(String::from("The table has no buckets")));
+        }
+        Ok(())
+    }
 
-        let name4 = Field::StringField(String::from("Daviddf"));
-        let course_taken4 = Field::IntField(1);
-        table.insert((name4, course_taken4), 1);
+    // method to get the (bucket size, bucket number) that holds total entries without extending,
+    // growing along the table's extend option and never shrinking
+    fn capacity_dimensions(&self, total: usize) -> (usize, usize) {
+        self.capacity_dimensions_from(total, (self.BUCKET_SIZE, self.BUCKET_NUMBER))
+    }
 
-        let name5 = Field::StringField(String::from("Evadsfsdfsdfsdfsd"));
-        let course_taken5 = Field::IntField(85);
-        table.insert((name5, course_taken5), 1);
+    // method to get the (bucket size, bucket number) that holds total entries without extending,
+    // growing along the table's extend option from the given smallest dimensions
+    fn capacity_dimensions_from(&self, total: usize, (min_size, min_num): (usize, usize)) -> (usize, usize) {
+        // bucket occupancy is roughly Poisson, so size the buckets until one of them passing its
+        // load limit is unlikely across the whole table
+        let fits = |b_size: usize, b_num: usize| {
+            let limit = (b_size as f64 * self.load_factor).floor() as usize;
+            b_num as f64 * overflow_chance(total as f64 / b_num as f64, limit) <= 0.01
+        };
+        match self.extend_op {
+            // there is no skew to measure yet, so grow the buckets
+            ExtendOption::ExtendBucketSize | ExtendOption::Adaptive => {
+                let b_size = smallest_passing(min_size, |b_size| fits(b_size, min_num));
+                (b_size, min_num)
+            },
+            ExtendOption::ExtendBucketNumber => {
+                // a bucket holding only a few keys overflows somewhere once there are enough of them,
+                // so keep room for a handful and for a whole hopscotch neighborhood
+                let mut b_size = std::cmp::max(min_size, std::cmp::max(16, self.H));
+                while ((b_size as f64 * self.load_factor).floor() as usize) < 8 {
+                    b_size = b_size.saturating_mul(2);
+                }
+                let b_num = smallest_passing(min_num, |b_num| fits(b_size, b_num));
+                (b_size, b_num)
+            },
+        }
+    }
 
-        let name6 = Field::StringField(String::from("Franksdf"));
-        let course_taken6 = Field::IntField(16);
-        table.insert((name6, course_taken6), 1);
+    // method to get the smallest dimensions along the extend option, the bucket size a hopscotch
+    // neighborhood needs or a single bucket, keys pinned to a bucket keep the bucket number
+    fn smallest_dimensions(&self, pinned: bool) -> (usize, usize) {
+        match self.extend_op {
+            ExtendOption::ExtendBucketSize | ExtendOption::Adaptive => {
+                let min_size = if self.scheme == HashScheme::Hopscotch { self.H } else { 1 };
+                (min_size, self.BUCKET_NUMBER)
+            },
+            ExtendOption::ExtendBucketNumber => (self.BUCKET_SIZE, if pinned { self.BUCKET_NUMBER } else { 1 }),
+        }
+    }
 
-        let name7 = Field::StringField(String::from("Grantsdf"));
-        let course_taken7 = Field::IntField(63);
-        table.insert((name7, course_taken7), 1);
+    // initialize a hash table sized to hold capacity keys without rehashing
+    // returns an error for HashFunction::Custom, like new
+    pub fn with_capacity(capacity: usize, config: TableConfig) -> Result<Self, CrustyError> {
+        let (func, sche, op, load_f, h) = config;
+        // grow a small table of one bucket to fit the capacity
+        let base = Self::new(std::cmp::max(16, h), 1, func, sche, h, op, load_f)?;
+        let (b_size, b_num) = base.capacity_dimensions(capacity);
+        Self::new(b_size, b_num, func, sche, h, op, load_f)
+    }
 
-        let name8 = Field::StringField(String::from("Hilton"));
-        let course_taken8 = Field::IntField(11);
-        table.insert((name8, course_taken8), 1);
+    // initialize a hash table holding the given entries, duplicate keys are combined by insert
+    // returns an error for an entry insert rejects, such as a key of another arity, or for HashFunction::Custom
+    pub fn from_entries<K: Clone + Into<Key>>(entries: &[(K, usize)], config: TableConfig) -> Result<Self, CrustyError> {
+        let mut table = Self::with_capacity(entries.len(), config)?;
+        for (key, value) in entries {
+            table.insert(key.clone(), *value)?;
+        }
+        Ok(table)
+    }
 
-        let name9 = Field::StringField(String::from("Idamsdfsdf"));
-        let course_taken9 = Field::IntField(23);
-        table.insert((name9, course_taken9), 1);
+    // initialize a hash table holding every pair of a std HashMap
+    // returns an error for a pair insert rejects, such as a key of another arity, or for HashFunction::Custom
+    pub fn from_hashmap<K: Into<Key>>(map: HashMap<K, usize>, config: TableConfig) -> Result<Self, CrustyError> {
+        let mut table = Self::with_capacity(map.len(), config)?;
+        for (key, value) in map {
+            table.insert(key, value)?;
+        }
+        Ok(table)
+    }
 
-        let name10 = Field::StringField(String::from("Jendf"));
-        let course_taken10 = Field::IntField(656);
-        table.insert((name10, course_taken10), 1);
+    // method to copy the entries out in bucket order, the counterpart to from_entries
+    pub fn to_entries(&self) -> Vec<(Key, usize)> {
+        self.iter().map(|(key, value)| (key.clone(), *value)).collect()
+    }
 
-        let name11 = Field::StringField(String::from("Khrissdfs"));
-        let course_taken11 = Field::IntField(989);
-        table.insert((name11, course_taken11), 1);
+    // method to start logging keys in the order they are first inserted
+    // keys already in the table are logged in bucket order
+    pub fn keep_insertion_order(&mut self) {
+        if self.insertion_order.is_none() {
+            let existing = self.buckets.iter()
+                .flat_map(|bucket| bucket.iter())
+                .filter(|node| node.taken)
+                .map(|node| node.key.clone())
+                .collect();
+            self.insertion_order = Some(existing);
+        }
+    }
 
-        let name12 = Field::StringField(String::from("Lavid"));
-        let course_taken12 = Field::IntField(45);
-        // assert_eq!(table.get_indexes((&name12, &course_taken12)).unwrap().1, 8);
-        table.insert((name12, course_taken12), 1);
+    // method to start estimating the distinct keys inserted with a HyperLogLog sketch
+    // keys already in the table are counted too, staged keys once end_bulk places them
+    pub fn track_distinct(&mut self) {
+        if self.distinct.is_none() {
+            let mut sketch = HyperLogLog::new();
+            for key in self.keys() {
+                sketch.observe(key);
+            }
+            self.distinct = Some(sketch);
+        }
+    }
 
-        let name13 = Field::StringField(String::from("Mva"));
-        let course_taken13 = Field::IntField(9879);
-        table.insert((name13, course_taken13), 1);
+    // method to get the estimated number of distinct keys inserted
+    // falls back to the exact count unless track_distinct has been called
+    pub fn approx_distinct(&self) -> usize {
+        match self.distinct.as_ref() {
+            Some(sketch) => sketch.estimate(),
+            None => self.taken_count.iter().sum(),
+        }
+    }
 
-        let name14 = Field::StringField(String::from("Nrank"));
-        let course_taken14 = Field::IntField(454);
-        table.insert((name14, course_taken14), 1);
+    // method to iterate over the entries in the order their keys were first inserted
+    // yields nothing unless keep_insertion_order has been called
+    pub fn iter_ordered(&self) -> impl Iterator<Item = (&Key, &usize)> {
+        self.insertion_order.iter()
+            .flat_map(|order| order.iter())
+            .filter_map(move |key| self.find_slot(key))
+            .map(|(bucket_index, index)| (&self.buckets[bucket_index][index].key, self.value_at(bucket_index, index)))
+    }
 
-        let name15 = Field::StringField(String::from("Osdafhj"));
-        let course_taken15 = Field::StringField(String::from("Ohajd"));
-        table.insert((name15, course_taken15), 1);
+    // method to get the value in a slot, 0 while the table keeps no value column
+    fn value_at(&self, bucket_index: usize, index: usize) -> &usize {
+        self.values.get(bucket_index).map_or(&0, |values| &values[index])
+    }
 
-        let name16 = Field::StringField(String::from("Podfh"));
-        let course_taken16 = Field::StringField(String::from("Pdfki"));
-        table.insert((name16, course_taken16), 1);
+    // method to get the value in a slot to change, allocating the value column first if the table has none
+    fn value_at_mut(&mut self, bucket_index: usize, index: usize) -> &mut usize {
+        self.ensure_values();
+        &mut self.values[bucket_index][index]
+    }
 
-        let name17 = Field::StringField(String::from("Qkdsfai"));
-        let course_taken17 = Field::StringField(String::from("Qjidif"));
-        table.insert((name17, course_taken17), 1);
+    // method to set the value in a slot, where a 0 needs no value column
+    fn set_value(&mut self, bucket_index: usize, index: usize, value: usize) {
+        if value != 0 || !self.values.is_empty() {
+            *self.value_at_mut(bucket_index, index) = value;
+        }
+    }
 
-        let name18 = Field::StringField(String::from("Rjksdf"));
-        let course_taken18 = Field::StringField(String::from("Rkdsfi"));
-        table.insert((name18, course_taken18), 1);
+    // method to take the value out of a slot, leaving 0 behind
+    fn take_value(&mut self, bucket_index: usize, index: usize) -> usize {
+        self.values.get_mut(bucket_index).map_or(0, |values| std::mem::take(&mut values[index]))
+    }
 
-        let name19 = Field::StringField(String::from("Sjkdfi"));
-        let course_taken19 = Field::StringField(String::from("Sjkdfi"));
-        table.insert((name19, course_taken19), 1);
+    // method to combine new_value into the value in a slot through merge
+    fn merge_value(&mut self, bucket_index: usize, index: usize, new_value: usize,
+                   merge: &dyn Fn(&mut usize, usize) -> Result<(), CrustyError>) -> Result<(), CrustyError> {
+        let mut value = *self.value_at(bucket_index, index);
+        merge(&mut value, new_value)?;
+        self.set_value(bucket_index, index, value);
+        Ok(())
+    }
 
-        let name20 = Field::StringField(String::from("Thsdud"));
-        let course_taken20 = Field::StringField(String::from("Thjksdfi"));
-        table.insert((name20, course_taken20), 1);
+    // method to allocate a zeroed value column shaped like the buckets, unless there is one
+    fn ensure_values(&mut self) {
+        if self.values.is_empty() {
+            self.values = vec![vec![0; self.BUCKET_SIZE]; self.BUCKET_NUMBER];
+        }
     }
 
-    // function to test insert with robin hood scheme
-    pub fn test_insert_robin_hood() {
-        let mut table = HashTable::new(
-            4,
-            1,
-            HashFunction::StdHash,
-            HashScheme::RobinHood,
-            4,
-            ExtendOption::ExtendBucketSize,
-            0.9,
-        );
+    // method to find the occupied node holding the key without changing the table
+    fn find_node<'a>(&self, key: impl Into<KeyRef<'a>>) -> Option<&HashNode> {
+        let key = key.into();
+        let (bucket_index, index) = self.find_slot(key)?;
+        Some(&self.buckets[bucket_index][index])
+    }
 
-        // HN1 -> 0
-        let name = Field::StringField(String::from("Adam"));
-        let course_taken = Field::IntField(1);
-        // let indexes = table.get_indexes((&name, &course_taken)).unwrap();
-        // assert_eq!(indexes.1, 0);
-        // assert_eq!(indexes.2, 0);
-        table.insert((name, course_taken), 1);
-        assert_eq!(table.buckets[0][0].key, (Field::StringField(String::from("Adam")), Field::IntField(1)));
-        assert_eq!(table.buckets[0][0].dis, 0);
+    // method to find the (bucket_index, index) of the node holding the key
+    fn find_slot<'a>(&self, key: impl Into<KeyRef<'a>>) -> Option<(usize, usize)> {
+        let key = key.into();
+        if self.BUCKET_NUMBER == 0 {
+            return None;
+        }
+        let hash = self.key_hash(key);
+        self.find_position_hashed(key, self.bucket_of(hash), hash)
+    }
 
-        // HN2 -> 1
-        let name = Field::StringField(String::from("Adam"));
-        let course_taken = Field::IntField(2);
-        // let indexes = table.get_indexes((&name, &course_taken)).unwrap();
-        // assert_eq!(indexes.1, 1);
-        // assert_eq!(indexes.2, 0);
-        table.insert((name, course_taken), 1);
-        assert_eq!(table.buckets[0][1].key, (Field::StringField(String::from("Adam")), Field::IntField(2)));
-        assert_eq!(table.buckets[0][1].dis, 0);
-        assert_eq!(table.buckets[0][1].taken, true);
+    // method to find the (bucket_index, index) of the node holding the key inside the given bucket
+    fn find_position_in<'a>(&self, key: impl Into<KeyRef<'a>>, bucket_index: usize) -> Option<(usize, usize)> {
+        let key = key.into();
+        self.find_position_hashed(key, bucket_index, self.key_hash(key))
+    }
 
-        // HN3 -> 1 -> 2
-        let name = Field::StringField(String::from("Adam"));
-        let course_taken = Field::IntField(6);
-        let indexes3 = table.get_indexes((&name, &course_taken)).unwrap();
-        assert_eq!(indexes3.1, 2);
-        assert_eq!(indexes3.2, 1);
-        table.insert((name, course_taken), 1);
-        assert_eq!(table.buckets[0][2].key, (Field::StringField(String::from("Adam")), Field::IntField(6)));
-        assert_eq!(table.buckets[0][2].dis, 1);
-        assert_eq!(table.buckets[0][2].taken, true);
+    // method to find the (bucket_index, index) of the node holding the key inside the given bucket,
+    // probing from the home slot of the key hash the caller already has
+    fn find_position_hashed(&self, key: KeyRef, bucket_index: usize, hash: usize) -> Option<(usize, usize)> {
+        self.probe_in(key, bucket_index, hash, |_| {})
+            .map(|index| (bucket_index, index))
+    }
 
-        // HN4 -> 0 -> 2
-        let name = Field::StringField(String::from("Adam"));
-        let course_taken = Field::IntField(0);
-        let indexes3 = table.get_indexes((&name, &course_taken)).unwrap();
-        assert_eq!(indexes3.1, 1);
-        assert_eq!(indexes3.2, 1);
-        table.insert((name, course_taken), 1);
-        assert_eq!(table.buckets[0][1].key, (Field::StringField(String::from("Adam")), Field::IntField(0)));
-        assert_eq!(table.buckets[0][1].dis, 1);
-        assert_eq!(table.buckets[0][1].taken, true);
+    // method to get the number of taken slots at which a bucket triggers an extend
+    fn load_limit(&self) -> usize {
+        (self.BUCKET_SIZE as f64 * self.load_factor).floor() as usize
+    }
 
-        // HN2 -> 1 -> 3
-        assert_eq!(table.buckets[0][3].key, (Field::StringField(String::from("Adam")), Field::IntField(2)));
-        assert_eq!(table.buckets[0][3].dis, 2);
-        assert_eq!(table.buckets[0][3].taken, true);
+    // method to check if any bucket is within one insert of the load-factor threshold
+    pub fn should_extend(&self) -> bool {
+        let limit = self.load_limit();
+        self.taken_count.iter().any(|&taken| taken + 1 >= limit)
+    }
+
+    // method to insert a new HashNode into the pinned bucket, or the bucket the key hashes to,
+    // combining the value with an existing key's through merge
+    // pinned bucket indexes stay valid across extends since the bucket number never shrinks
+    // returns the (bucket_index, index) the key ends up in
+    fn insert_node_at(&mut self, pinned: Option<usize>, hash: usize, new_key: Key, new_value: usize,
+                      merge: &dyn Fn(&mut usize, usize) -> Result<(), CrustyError>) -> Result<(usize, usize), CrustyError> {
+        // extent the hash table once the target bucket reaches the load limit
+        if self.BUCKET_NUMBER > 0 &&
+            self.load_limit() <= self.taken_count[pinned.unwrap_or_else(|| self.bucket_of(hash))] {
+            self.extend(RehashReason::LoadFactor)?;
+            // the key is placed by the call on the extended table
+            return self.insert_node_at(pinned, hash, new_key, new_value, merge);
+        }
+
+        // get the tuple of (bucket_index, index), unless the bucket is full
+        let bucket_index = pinned.unwrap_or_else(|| self.bucket_of(hash));
+        let indexes = if self.taken_count[bucket_index] < self.BUCKET_SIZE {
+            self.get_indexes_in(&new_key, bucket_index, self.home_of(hash))
+        } else {
+            None
+        };
+        if self.scheme == HashScheme::Cuckoo && indexes.is_some() {
+            return self.cuckoo_insert(bucket_index, hash, new_key, new_value, pinned.is_some(), merge);
+        }
+        if let Some(indexes) = indexes {
+            if self.scheme == HashScheme::Hopscotch { // using helper method to insert w/ hopscotch
+                return self.hopscotch_insert(pinned, hash, new_key, new_value, (indexes.0, indexes.1), merge);
+            } else if self.buckets[indexes.0][indexes.1].taken &&
+                self.same_key((&new_key).into(), &self.buckets[indexes.0][indexes.1].key) { // check if the the key is already existed in the table
+                // merge new value into the old one
+                self.merge_value(indexes.0, indexes.1, new_value, merge)?;
+                return Ok((indexes.0, indexes.1));
+            } else if !self.buckets[indexes.0][indexes.1].taken { // if not been taken
+                // directly insert the new value
+                self.buckets[indexes.0][indexes.1] = HashNode {key: new_key, taken: true, dis: indexes.2, pinned: pinned.is_some(), deleted: false};
+                self.set_value(indexes.0, indexes.1, new_value);
+                self.taken_count[indexes.0] += 1;
+                return Ok((indexes.0, indexes.1));
+            } else { // robin hood situation
+                // insert the new node and then original node
+                let ori_node = self.buckets[indexes.0][indexes.1].clone();
+                let ori_value = self.take_value(indexes.0, indexes.1);
+                self.buckets[indexes.0][indexes.1] = HashNode {key: new_key, taken: true, dis: indexes.2, pinned: pinned.is_some(), deleted: false};
+                self.set_value(indexes.0, indexes.1, new_value);
+                let ori_pinned = ori_node.pinned.then_some(indexes.0);
+                let ori_hash = self.key_hash(&ori_node.key);
+                // the displaced nodes go back into this bucket, which is below its load limit and has a free
+                // slot, so placing them never extends and the new key stays where it was put
+                self.insert_node_at(ori_pinned, ori_hash, ori_node.key, ori_value, merge)?;
+                return Ok((indexes.0, indexes.1));
+            }
+        }
+        self.extend(RehashReason::ProbeExhausted)?;
+        self.insert_node_at(pinned, hash, new_key, new_value, merge)
+    }
+
+    // method to extend the bucket number / bucket size and then rehash the table
+    // returns an error once the table is too large to double
+    fn extend(&mut self, reason: RehashReason) -> Result<(), CrustyError> {
+        self.check_buckets()?;
+        let extend_op = match self.extend_op {
+            ExtendOption::Adaptive if self.is_skewed() => ExtendOption::ExtendBucketNumber,
+            ExtendOption::Adaptive => ExtendOption::ExtendBucketSize,
+            op => op,
+        };
+        let (b_size, b_num) = match extend_op {
+            // extend the bucket size to twice of the original bucket size
+            ExtendOption::ExtendBucketSize | ExtendOption::Adaptive => (self.BUCKET_SIZE.saturating_mul(2), self.BUCKET_NUMBER),
+            // extend the bucket number to twice of than original bucket number
+            ExtendOption::ExtendBucketNumber => {
+                // a bucket that can only hold one entry before extending never settles, so keep room for two
+                // and for a whole hopscotch neighborhood
+                let mut b_size = std::cmp::max(self.BUCKET_SIZE, self.min_effective_bucket_size);
+                while ((b_size as f64 * self.load_factor).floor() as usize) < 2 ||
+                    (self.scheme == HashScheme::Hopscotch && b_size < self.H) {
+                    b_size += 1;
+                }
+                // n keys of a well-hashed bucket all stay together with chance 2^(1 - n), so count
+                // those bits until staying together by chance is too unlikely to believe
+                let (keys, splits) = self.split_fullest_bucket();
+                if splits {
+                    self.futile_split_bits = 0;
+                } else {
+                    self.futile_split_bits += keys.saturating_sub(1);
+                }
+                if self.futile_split_bits < 20 {
+                    (b_size, self.BUCKET_NUMBER.saturating_mul(2))
+                } else {
+                    // the fullest bucket's keys keep staying together, so more buckets don't help
+                    self.futile_split_bits = 0;
+                    (std::cmp::max(b_size, self.BUCKET_SIZE.saturating_mul(2)), self.BUCKET_NUMBER)
+                }
+            },
+        };
+        if b_size.checked_mul(b_num).is_none() || (b_size, b_num) == (self.BUCKET_SIZE, self.BUCKET_NUMBER) {
+            return Err(CrustyError::ExecutionError(String::from("Couldn't extend the table to place the key")));
+        }
+        self.rebuild(b_size, b_num, reason)
+    }
+
+    // method to make room for additional more keys, rehashing once now so inserting them doesn't have to
+    // hopscotch and cuckoo inserts may still extend when they run out of displacements
+    // returns an error if the table can't grow that large
+    pub fn reserve(&mut self, additional: usize) -> Result<(), CrustyError> {
+        self.check_buckets()?;
+        let (b_size, b_num) = self.capacity_dimensions(self.len().saturating_add(additional));
+        if (b_size, b_num) == (self.BUCKET_SIZE, self.BUCKET_NUMBER) {
+            return Ok(());
+        }
+        if b_size.checked_mul(b_num).is_none() {
+            return Err(CrustyError::ExecutionError(String::from("Couldn't reserve room for the keys")));
+        }
+        self.rebuild(b_size, b_num, RehashReason::Explicit)
+    }
+
+    // method to rehash into the smallest dimensions along the extend option that hold the current keys,
+    // keeping the table as it is when it's already that small or the keys don't fit the smaller table
+    // keys pinned by insert_into_bucket keep the bucket number from shrinking
+    // returns an error if the rebuild fails, which leaves the table as it was
+    pub fn shrink_to_fit(&mut self) -> Result<(), CrustyError> {
+        self.check_buckets()?;
+        let pinned = self.buckets.iter()
+            .flat_map(|bucket| bucket.iter())
+            .any(|node| node.taken && node.pinned);
+        let (b_size, b_num) = self.capacity_dimensions_from(self.len(), self.smallest_dimensions(pinned));
+        // fewer buckets may need wider ones, so compare the slots
+        if b_size * b_num >= self.BUCKET_SIZE * self.BUCKET_NUMBER {
+            return Ok(());
+        }
+        self.rebuild(b_size, b_num, RehashReason::Shrink)
+    }
+
+    // method to set the smallest bucket size ExtendBucketNumber keeps while adding buckets
+    pub fn set_min_effective_bucket_size(&mut self, min_size: usize) {
+        self.min_effective_bucket_size = min_size;
+    }
+
+    // method to get the key count of the fullest bucket and if doubling the bucket number splits its keys
+    fn split_fullest_bucket(&self) -> (usize, bool) {
+        let fullest = match (0..self.BUCKET_NUMBER).max_by_key(|&i| self.taken_count[i]) {
+            Some(fullest) => fullest,
+            None => return (0, true),
+        };
+        // a key in bucket i moves to bucket i or i + BUCKET_NUMBER
+        let moved = self.buckets[fullest].iter()
+            .filter(|node| node.taken &&
+                self.key_hash(&node.key) % (self.BUCKET_NUMBER * 2) != fullest)
+            .count();
+        let keys = self.taken_count[fullest];
+        (keys, moved > 0 && moved < keys)
+    }
+
+    // method to check if the fullest bucket holds at least twice the average occupancy
+    fn is_skewed(&self) -> bool {
+        let total: usize = self.taken_count.iter().sum();
+        let fullest = self.taken_count.iter().copied().max().unwrap_or(0);
+        total > 0 && fullest * self.BUCKET_NUMBER >= 2 * total
+    }
+
+    // method to rehash every entry into a new table with the given dimensions
+    fn rebuild(&mut self, b_size: usize, b_num: usize, reason: RehashReason) -> Result<(), CrustyError> {
+        let mut new_self = Self::new_unchecked(
+            b_size,
+            b_num,
+            self.function,
+            self.scheme,
+            self.H,
+            self.extend_op,
+            self.load_factor,
+        );
+        new_self.combine = self.combine;
+        new_self.seed = self.seed;
+        new_self.custom_hash = self.custom_hash.clone();
+        // probes made placing the keys again count too
+        new_self.probe_counters = ProbeCounters::from_stats(self.probe_stats());
+        new_self.on_duplicate = self.on_duplicate;
+        new_self.membership_only = self.membership_only;
+        new_self.allow_nan = self.allow_nan;
+        new_self.key_arity = self.key_arity;
+        new_self.float_epsilon = self.float_epsilon;
+        new_self.min_effective_bucket_size = self.min_effective_bucket_size;
+        new_self.futile_split_bits = self.futile_split_bits;
+        // rehashes nested in this one are counted and reported too
+        new_self.rehash_count = self.rehash_count;
+        new_self.rehash_listener = self.rehash_listener.take();
+
+        // insert the <key, value> to new hash table, keys are distinct so a match is never merged into
+        // keys pinned by insert_into_bucket stay in the same bucket
+        let mut placed = Ok(());
+        'place: for (bucket_index, bucket) in self.buckets.iter().enumerate() {
+            for (index, node) in bucket.iter().enumerate() {
+                if node.taken {
+                    let pinned = node.pinned.then_some(bucket_index);
+                    let hash = new_self.key_hash(&node.key);
+                    let value = *self.value_at(bucket_index, index);
+                    placed = new_self.insert_node_at(pinned, hash, node.key.clone(), value, &|old, new| {
+                        *old = new;
+                        Ok(())
+                    }).map(|_| ());
+                    if placed.is_err() {
+                        break 'place;
+                    }
+                }
+            }
+        }
+        // a failed rebuild leaves this table as it was
+        if placed.is_err() {
+            self.rehash_listener = new_self.rehash_listener.take();
+            return placed;
+        }
+        // a shrink that had to extend while placing the keys saves nothing, so it's dropped as well
+        if reason == RehashReason::Shrink &&
+            new_self.BUCKET_SIZE * new_self.BUCKET_NUMBER >= self.BUCKET_SIZE * self.BUCKET_NUMBER {
+            self.rehash_listener = new_self.rehash_listener.take();
+            return Ok(());
+        }
+        new_self.insertion_order = self.insertion_order.take();
+        new_self.bulk = self.bulk.take();
+        new_self.staged_keys = std::mem::take(&mut self.staged_keys);
+        new_self.distinct = self.distinct.take();
+        new_self.rehash_count += 1;
+        let event = RehashEvent {
+            old_dimensions: (self.BUCKET_SIZE, self.BUCKET_NUMBER),
+            new_dimensions: (b_size, b_num),
+            reason,
+        };
+        *self = new_self;
+        if let Some(listener) = self.rehash_listener.as_mut() {
+            listener(&event);
+        }
+        Ok(())
+    }
+
+    // method to register a callback fired after every rehash, replacing any earlier one
+    pub fn on_rehash(&mut self, listener: impl FnMut(&RehashEvent) + Send + Sync + 'static) {
+        self.rehash_listener = Some(Box::new(listener));
+    }
+
+    // method to get the number of rehashes since the table was built, one per extend or explicit rebuild
+    pub fn rehash_count(&self) -> usize {
+        self.rehash_count
+    }
+
+    // method to rebuild into the scheme with the cheapest lookups for the current entries
+    // every scheme is tried on the entries, or on a sample of at most OPTIMIZE_SAMPLE of them in
+    // proportionally fewer buckets, preferring no extends, then the shortest mean and max probe
+    pub fn optimize(&mut self) {
+        let live = self.iter().count();
+        if live == 0 {
+            return;
+        }
+        let step = live.div_ceil(OPTIMIZE_SAMPLE);
+        let trial_buckets = std::cmp::max(1, self.BUCKET_NUMBER / step);
+        let cost = |scheme: HashScheme| {
+            let mut trial = Self::new_unchecked(self.BUCKET_SIZE, trial_buckets, self.function, scheme,
+                                                self.H, self.extend_op, self.load_factor);
+            trial.combine = self.combine;
+            trial.seed = self.seed;
+            trial.custom_hash = self.custom_hash.clone();
+            trial.allow_nan = self.allow_nan;
+            trial.float_epsilon = self.float_epsilon;
+            // a trial that can't place the sample is never picked
+            let placed = self.iter().step_by(step).all(|(key, value)| trial.insert(key.clone(), *value).is_ok());
+            if !placed {
+                return (usize::MAX, f64::INFINITY, usize::MAX);
+            }
+            let summary = trial.summary();
+            (summary.rehash_count, summary.mean_probe, summary.max_probe)
+        };
+        let current = cost(self.scheme);
+        let mut best = (self.scheme, current);
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch, HashScheme::DoubleHash,
+                       HashScheme::Cuckoo] {
+            let candidate = cost(scheme);
+            if candidate.partial_cmp(&best.1) == Some(std::cmp::Ordering::Less) {
+                best = (scheme, candidate);
+            }
+        }
+        if best.0 != self.scheme {
+            let scheme = self.scheme;
+            self.scheme = best.0;
+            if self.rebuild(self.BUCKET_SIZE, self.BUCKET_NUMBER, RehashReason::Explicit).is_err() {
+                self.scheme = scheme;
+            }
+        }
+    }
+
+    // method to change how the field hashes are combined, rehashing the existing entries
+    // returns an error if the entries can't be placed again, the table then keeps its old strategy
+    pub fn set_combine_strategy(&mut self, combine: CombineStrategy) -> Result<(), CrustyError> {
+        if self.combine != combine {
+            let old = std::mem::replace(&mut self.combine, combine);
+            if !self.buckets.is_empty() {
+                if let Err(e) = self.rebuild(self.BUCKET_SIZE, self.BUCKET_NUMBER, RehashReason::Explicit) {
+                    self.combine = old;
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // method to add every entry of another table into this one, accumulating values of shared keys
+    pub fn merge(&mut self, other: HashTable) -> Result<(), CrustyError> {
+        for (bucket_index, bucket) in other.buckets.into_iter().enumerate() {
+            for (index, node) in bucket.into_iter().enumerate() {
+                if node.taken {
+                    self.insert(node.key, other.values.get(bucket_index).map_or(0, |values| values[index]))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // method to partition the entries into n standalone tables by key hash, the inverse of merge
+    // returns an error for n of 0, or if a sub-table can't grow to place its keys
+    pub fn split(mut self, n: usize) -> Result<Vec<HashTable>, CrustyError> {
+        if n == 0 {
+            return Err(CrustyError::ValidationError(String::from("Can't split a table into 0 tables")));
+        }
+        // size every sub-table for its share of the keys, which are placed again without their pins
+        let (b_size, b_num) = self.capacity_dimensions_from(self.len().div_ceil(n), self.smallest_dimensions(false));
+        let mut tables: Vec<HashTable> = (0..n)
+            .map(|_| {
+                let mut table = Self::new_unchecked(
+                    b_size,
+                    b_num,
+                    self.function,
+                    self.scheme,
+                    self.H,
+                    self.extend_op,
+                    self.load_factor,
+                );
+                table.combine = self.combine;
+                table.seed = self.seed;
+                table.custom_hash = self.custom_hash.clone();
+                table.on_duplicate = self.on_duplicate;
+                table.membership_only = self.membership_only;
+                table.allow_nan = self.allow_nan;
+                table.key_arity = self.key_arity;
+                table.float_epsilon = self.float_epsilon;
+                table.min_effective_bucket_size = self.min_effective_bucket_size;
+                if self.insertion_order.is_some() {
+                    table.keep_insertion_order();
+                }
+                if self.distinct.is_some() {
+                    table.track_distinct();
+                }
+                table
+            })
+            .collect();
+
+        // walk the occupied slots rather than looking keys up again, so keys pinned to another bucket move too
+        let mut entries: Vec<(Key, usize)> = Vec::new();
+        for bucket_index in 0..self.buckets.len() {
+            for index in 0..self.buckets[bucket_index].len() {
+                if self.buckets[bucket_index][index].taken {
+                    let node = std::mem::take(&mut self.buckets[bucket_index][index]);
+                    entries.push((node.key, self.take_value(bucket_index, index)));
+                }
+            }
+        }
+        // keep the insertion order of every sub-table consistent with this one
+        if let Some(order) = self.insertion_order.take() {
+            let position: HashMap<&Key, usize> = order.iter().enumerate().map(|(i, key)| (key, i)).collect();
+            entries.sort_by_key(|(key, _)| position.get(key).copied().unwrap_or(usize::MAX));
+        }
+        for (key, value) in entries {
+            // the bucket is the hash modulo the bucket number, so splitting by the same low bits would leave
+            // each sub-table only the buckets of its residue, the high bits of the remixed hash are independent
+            let target = (slot_mix(self.key_hash(&key) as u64) >> 32) as usize % n;
+            tables[target].insert(key, value)?;
+        }
+        Ok(tables)
+    }
+
+    // method to normalize the accumulated counts into probabilities that sum to 1.0
+    pub fn to_distribution(&self) -> Vec<(Key, f64)> {
+        let total: usize = self.iter().map(|(_, value)| value).sum();
+        if total == 0 {
+            return Vec::new();
+        }
+        self.iter()
+            .map(|(key, value)| (key.clone(), *value as f64 / total as f64))
+            .collect()
+    }
+
+    // method to score how evenly the keys are spread over the buckets
+    // chi-squared of bucket occupancy against a uniform spread, per degree of freedom:
+    // about 1.0 for a well-mixed hash function, far larger when the data hashes badly
+    pub fn distribution_quality(&self) -> f64 {
+        let total: usize = self.taken_count.iter().sum();
+        if self.BUCKET_NUMBER < 2 || total == 0 {
+            return 0.0;
+        }
+        let expected = total as f64 / self.BUCKET_NUMBER as f64;
+        let chi_squared: f64 = self.taken_count.iter()
+            .map(|&taken| (taken as f64 - expected).powi(2) / expected)
+            .sum();
+        chi_squared / (self.BUCKET_NUMBER - 1) as f64
+    }
+
+    // method to compute |A∩B| / |A∪B| over the key sets of two tables, 1.0 when both are empty
+    pub fn jaccard(&self, other: &HashTable) -> f64 {
+        let size = self.taken_count.iter().sum::<usize>();
+        let other_size = other.taken_count.iter().sum::<usize>();
+        let shared = self.buckets.iter()
+            .flat_map(|bucket| bucket.iter())
+            .filter(|node| node.taken && other.find_node(&node.key).is_some())
+            .count();
+        let union = size + other_size - shared;
+        if union == 0 {
+            return 1.0;
+        }
+        shared as f64 / union as f64
+    }
+
+    // method to merge partial tables pairwise, level by level, into one table
+    // returns an error for no tables, which leave nothing to take the settings of the result from
+    pub fn reduce(tables: Vec<HashTable>) -> Result<HashTable, CrustyError> {
+        let mut level = tables;
+        while level.len() > 1 {
+            let mut next = Vec::with_capacity(level.len().div_ceil(2));
+            let mut tables = level.into_iter();
+            while let Some(left) = tables.next() {
+                let merged = match tables.next() {
+                    Some(right) => {
+                        // fold the smaller table into the larger one
+                        let (mut large, small) =
+                            if left.taken_count.iter().sum::<usize>() < right.taken_count.iter().sum::<usize>() {
+                                (right, left)
+                            } else {
+                                (left, right)
+                            };
+                        large.merge(small)?;
+                        large
+                    },
+                    None => left,
+                };
+                next.push(merged);
+            }
+            level = next;
+        }
+        level.pop().ok_or_else(|| CrustyError::ValidationError(String::from("No tables to reduce")))
+    }
+
+    // method to get the keys of the worst cluster in the table, in slot order
+    //
+    // for Hopscotch this is the neighborhood holding the most entries,
+    // for the other schemes the longest run of occupied slots (wrapping around the bucket)
+    pub fn longest_chain(&self) -> Vec<Key> {
+        let mut best: Vec<(usize, usize)> = Vec::new();
+        for (bucket_index, bucket) in self.buckets.iter().enumerate() {
+            if self.scheme == HashScheme::Hopscotch {
+                for index in 0..self.BUCKET_SIZE {
+                    let hop = self.hop_info[bucket_index][index];
+                    if hop.count_ones() as usize > best.len() {
+                        best = (0..self.H).rev()
+                            .filter(|n| hop & (1 << n) != 0)
+                            .map(|n| (bucket_index, index + (self.H - 1 - n)))
+                            .collect();
+                    }
+                }
+                continue;
+            }
+            // start right after a free slot so no run is split by the wrap around
+            let start = match bucket.iter().position(|node| !node.taken) {
+                Some(free) => free + 1,
+                None => 0,
+            };
+            let mut run = Vec::new();
+            for offset in 0..self.BUCKET_SIZE {
+                let index = (start + offset) % self.BUCKET_SIZE;
+                if bucket[index].taken {
+                    run.push((bucket_index, index));
+                    if run.len() > best.len() {
+                        best = run.clone();
+                    }
+                } else {
+                    run.clear();
+                }
+            }
+        }
+        best.into_iter()
+            .map(|(bucket_index, index)| self.buckets[bucket_index][index].key.clone())
+            .collect()
+    }
+
+    // method to serialize the table into a compact binary format
+    //
+    // layout: |header|records|hop entries|insertion order|, where the header holds the dimensions and configuration
+    // (and the seed of a SeededSip table), the duplicate policy, the membership, NaN and epsilon settings,
+    // each record holds the position, distance, value, pinned or deleted flag, field count and key fields of an
+    // occupied or deleted node,
+    // each hop entry holds the position and bits of a non-zero hop info,
+    // and the insertion order, only there when it is kept, holds every key in order.
+    // staged bulk entries, the rehash listener, distinct estimate and probe counters aren't written.
+    // a table with a custom hash function is refused, its closure can't be written out
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CrustyError> {
+        let function_tag = self.function.to_tag()?;
+        let mut records = Vec::new();
+        let mut record_count = 0;
+        for (bucket_index, bucket) in self.buckets.iter().enumerate() {
+            for (index, node) in bucket.iter().enumerate() {
+                if node.taken || node.deleted {
+                    write_usize(&mut records, bucket_index);
+                    write_usize(&mut records, index);
+                    write_usize(&mut records, node.dis);
+                    write_usize(&mut records, *self.value_at(bucket_index, index));
+                    records.push(if node.deleted { 2 } else { node.pinned as u8 });
+                    write_usize(&mut records, node.key.len());
+                    for field in node.key.iter() {
+                        write_field(&mut records, field);
+                    }
+                    record_count += 1;
+                }
+            }
+        }
+
+        let mut hops = Vec::new();
+        let mut hop_count = 0;
+        for (bucket_index, bucket) in self.hop_info.iter().enumerate() {
+            for (index, hop) in bucket.iter().enumerate() {
+                if *hop != 0 {
+                    write_usize(&mut hops, bucket_index);
+                    write_usize(&mut hops, index);
+                    write_usize(&mut hops, *hop);
+                    hop_count += 1;
+                }
+            }
+        }
+
+        let mut body = Vec::new();
+        write_usize(&mut body, self.BUCKET_SIZE);
+        write_usize(&mut body, self.BUCKET_NUMBER);
+        body.push(function_tag);
+        body.push(self.scheme.to_tag());
+        body.push(self.extend_op.to_tag());
+        body.push(self.combine.to_tag());
+        // only a seeded function's placement depends on the seed
+        if self.function == HashFunction::SeededSip {
+            body.extend(self.seed.0.to_le_bytes());
+            body.extend(self.seed.1.to_le_bytes());
+        }
+        write_usize(&mut body, self.H);
+        body.extend(self.load_factor.to_le_bytes());
+        body.push(self.on_duplicate.to_tag());
+        let flags = u8::from(self.membership_only)
+            | u8::from(self.allow_nan) << 1
+            | u8::from(self.float_epsilon.is_some()) << 2
+            | u8::from(self.insertion_order.is_some()) << 3;
+        body.push(flags);
+        if let Some(epsilon) = self.float_epsilon {
+            body.extend(epsilon.to_le_bytes());
+        }
+        write_usize(&mut body, record_count);
+        write_usize(&mut body, hop_count);
+        body.extend(records);
+        body.extend(hops);
+        if let Some(order) = self.insertion_order.as_ref() {
+            write_usize(&mut body, order.len());
+            for key in order.iter() {
+                write_usize(&mut body, key.len());
+                for field in key.iter() {
+                    write_field(&mut body, field);
+                }
+            }
+        }
+
+        // the checksum in the header covers everything after it
+        let mut result = TABLE_MAGIC.to_vec();
+        result.extend(farmhash::hash64(&body).to_le_bytes());
+        result.extend(body);
+        Ok(result)
+    }
+
+    // method to place any staged bulk entries and then serialize, so the blob holds every inserted key
+    // tombstones left by remove are dropped by rehashing in place, which leaves keys where a fresh insert would
+    pub fn freeze_to_bytes(&mut self) -> Result<Vec<u8>, CrustyError> {
+        self.end_bulk()?;
+        if self.buckets.iter().flatten().any(|node| node.deleted) {
+            self.rebuild(self.BUCKET_SIZE, self.BUCKET_NUMBER, RehashReason::Explicit)?;
+        }
+        self.to_bytes()
+    }
+
+    // method to write the table to a file with bincode, through the serde impl
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), CrustyError> {
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        bincode::serialize_into(file, self).map_err(|e| bincode_error(*e))
+    }
+
+    // method to read a table written by save
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, CrustyError> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        bincode::deserialize_from(file).map_err(|e| bincode_error(*e))
+    }
+
+    // method to restore a table from the binary format produced by to_bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CrustyError> {
+        let mut reader = ByteReader::new(bytes);
+        if reader.take(TABLE_MAGIC.len())? != TABLE_MAGIC {
+            return Err(CrustyError::ValidationError(String::from("Not a serialized hash table")));
+        }
+        let checksum = reader.read_usize()? as u64;
+        if farmhash::hash64(reader.remaining()) != checksum {
+            return Err(CrustyError::ValidationError(String::from("Table checksum mismatch")));
+        }
+        let b_size = reader.read_usize()?;
+        let b_num = reader.read_usize()?;
+        let func = HashFunction::from_tag(reader.read_u8()?)?;
+        let sche = HashScheme::from_tag(reader.read_u8()?)?;
+        let op = ExtendOption::from_tag(reader.read_u8()?)?;
+        let combine = CombineStrategy::from_tag(reader.read_u8()?)?;
+        let seed = if func == HashFunction::SeededSip {
+            (reader.read_u64()?, reader.read_u64()?)
+        } else {
+            (0, 0)
+        };
+        let h = reader.read_usize()?;
+        let load_f = f64::from_le_bytes(reader.take(8)?.try_into().unwrap());
+        let on_duplicate = OnDuplicate::from_tag(reader.read_u8()?)?;
+        let flags = reader.read_u8()?;
+        if flags >> 4 != 0 {
+            return Err(CrustyError::ValidationError(format!("Unknown table flags {:#x}", flags)));
+        }
+        let float_epsilon = if flags & 4 != 0 {
+            Some(f64::from_le_bytes(reader.take(8)?.try_into().unwrap()))
+        } else {
+            None
+        };
+        let record_count = reader.read_usize()?;
+        let hop_count = reader.read_usize()?;
+
+        // the header is untrusted, so bound what it makes us allocate before allocating it
+        if b_size == 0 || b_num == 0 {
+            return Err(CrustyError::ValidationError(format!(
+                "A table needs at least one bucket of one slot, got {} buckets of {} slots",
+                b_num, b_size
+            )));
+        }
+        if h == 0 {
+            return Err(CrustyError::ValidationError(String::from("Neighborhood of 0 slots")));
+        }
+        if load_f.is_nan() || load_f <= 0.0 {
+            return Err(CrustyError::ValidationError(format!("Load factor {} is not positive", load_f)));
+        }
+        if h > usize::BITS as usize {
+            return Err(CrustyError::ValidationError(format!(
+                "Neighborhood of {} slots doesn't fit {} bit hop info",
+                h,
+                usize::BITS
+            )));
+        }
+        let slot_limit = MIN_SLOT_LIMIT.max(bytes.len().saturating_mul(MAX_SLOTS_PER_BYTE));
+        if b_size.checked_mul(b_num).is_none_or(|slots| slots > slot_limit) {
+            return Err(CrustyError::ValidationError(format!(
+                "{} buckets of {} slots are too many for a {} byte table",
+                b_num,
+                b_size,
+                bytes.len()
+            )));
+        }
+
+        let mut table = HashTable::with_seed(b_size, b_num, func, sche, h, op, load_f, seed.0, seed.1)?;
+        table.combine = combine;
+        table.on_duplicate = on_duplicate;
+        table.membership_only = flags & 1 != 0;
+        table.allow_nan = flags & 2 != 0;
+        table.set_float_epsilon(float_epsilon)?;
+        for _ in 0..record_count {
+            let (bucket_index, index) = reader.read_position(b_num, b_size)?;
+            let dis = reader.read_usize()?;
+            let value = reader.read_usize()?;
+            let (pinned, deleted) = match reader.read_u8()? {
+                0 => (false, false),
+                1 => (true, false),
+                2 => (false, true),
+                _ => return Err(CrustyError::ValidationError(String::from("Invalid pinned flag"))),
+            };
+            let key_len = reader.read_usize()?;
+            let mut key = Vec::new();
+            for _ in 0..key_len {
+                key.push(reader.read_field()?);
+            }
+            let key = Key(key);
+            if table.buckets[bucket_index][index].taken || table.buckets[bucket_index][index].deleted {
+                return Err(CrustyError::ValidationError(String::from("Duplicate record position")));
+            }
+            if !deleted {
+                table.check_arity(key.len())?;
+                table.key_arity = Some(key.len());
+            }
+            table.buckets[bucket_index][index] = HashNode { key, taken: !deleted, dis, pinned, deleted };
+            table.set_value(bucket_index, index, value);
+            if !deleted {
+                table.taken_count[bucket_index] += 1;
+            }
+        }
+        for _ in 0..hop_count {
+            let (bucket_index, index) = reader.read_position(b_num, b_size)?;
+            table.hop_info[bucket_index][index] = reader.read_usize()?;
+        }
+        if flags & 8 != 0 {
+            let order_len = reader.read_usize()?;
+            let mut order = Vec::new();
+            for _ in 0..order_len {
+                let key_len = reader.read_usize()?;
+                let mut key = Vec::new();
+                for _ in 0..key_len {
+                    key.push(reader.read_field()?);
+                }
+                order.push(Key(key));
+            }
+            // keys pinned by insert_into_bucket aren't found through their hash, so look for those among the nodes
+            let placed = |key: &Key| table.find_node(key).is_some()
+                || table.buckets.iter().flatten().any(|node| node.taken && node.pinned && node.key == *key);
+            if order.len() != table.len() || !order.iter().all(placed) {
+                return Err(CrustyError::ValidationError(String::from("Insertion order doesn't match the keys")));
+            }
+            table.insertion_order = Some(order);
+        }
+        if !reader.is_empty() {
+            return Err(CrustyError::ValidationError(String::from("Trailing bytes after table")));
+        }
+        table.check_invariants()?;
+        Ok(table)
+    }
+
+    // method to check that the bookkeeping of the table agrees with its buckets
+    pub fn check_invariants(&self) -> Result<(), CrustyError> {
+        let invalid = |msg: String| Err(CrustyError::ValidationError(msg));
+        if self.buckets.len() != self.BUCKET_NUMBER
+            || self.taken_count.len() != self.BUCKET_NUMBER
+            || self.hop_info.len() != self.BUCKET_NUMBER
+            || !(self.values.is_empty() || self.values.len() == self.BUCKET_NUMBER) {
+            return invalid(String::from("Bucket number doesn't match the table"));
+        }
+        for (bucket_index, bucket) in self.buckets.iter().enumerate() {
+            if bucket.len() != self.BUCKET_SIZE || self.hop_info[bucket_index].len() != self.BUCKET_SIZE
+                || self.values.get(bucket_index).is_some_and(|values| values.len() != self.BUCKET_SIZE) {
+                return invalid(format!("Bucket {} doesn't match the bucket size", bucket_index));
+            }
+            let taken = bucket.iter().filter(|node| node.taken).count();
+            if taken != self.taken_count[bucket_index] {
+                return invalid(format!("Bucket {} holds {} nodes but counts {}",
+                                       bucket_index, taken, self.taken_count[bucket_index]));
+            }
+            for (index, node) in bucket.iter().enumerate() {
+                if node.taken && !node.pinned && self.hash_bucket_index(&node.key) != bucket_index {
+                    return invalid(format!("Node at {}:{} is in the wrong bucket", bucket_index, index));
+                }
+                if node.taken && self.scheme == HashScheme::Cuckoo {
+                    let (first, second) = self.cuckoo_slots(self.key_hash(&node.key));
+                    if index != first && index != second {
+                        return invalid(format!("Node at {}:{} is in neither of its cuckoo slots", bucket_index, index));
+                    }
+                }
+            }
+            // every hop bit must point to an occupied slot in the neighborhood
+            for (index, hop) in self.hop_info[bucket_index].iter().enumerate() {
+                for n in 0..self.H {
+                    if hop & (1 << n) != 0 {
+                        let target = index + (self.H - 1 - n);
+                        if target >= self.BUCKET_SIZE || !bucket[target].taken {
+                            return invalid(format!("Hop info at {}:{} points to an empty slot", bucket_index, index));
+                        }
+                    }
+                }
+            }
+            // every key must be found where it is by following the scheme's probe sequence
+            for (index, node) in bucket.iter().enumerate() {
+                if node.taken && self.find_position_in(&node.key, bucket_index) != Some((bucket_index, index)) {
+                    return invalid(format!("Node at {}:{} isn't reachable by a lookup", bucket_index, index));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// magic bytes at the start of every serialized table
+const TABLE_MAGIC: &[u8; 4] = b"RHT3";
+// a serialized table may restore to at most this many slots per byte of input, or MIN_SLOT_LIMIT,
+// so a crafted header can't make from_bytes allocate without bound; sparser tables must shrink_to_fit first
+const MAX_SLOTS_PER_BYTE: usize = 1024;
+const MIN_SLOT_LIMIT: usize = 1 << 20;
+
+impl HashFunction {
+    // tag used for the binary format, a custom hash has none since its closure can't be written out
+    fn to_tag(self) -> Result<u8, CrustyError> {
+        match self {
+            HashFunction::FarmHash => Ok(0),
+            HashFunction::MurmurHash3 => Ok(1),
+            HashFunction::T1haHash => Ok(2),
+            HashFunction::StdHash => Ok(3),
+            HashFunction::MetroHash => Ok(4),
+            HashFunction::SeededSip => Ok(5),
+            HashFunction::WyHash => Ok(6),
+            HashFunction::Custom => Err(CrustyError::ValidationError(String::from(
+                "Can't serialize a table with a custom hash function"))),
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<Self, CrustyError> {
+        match tag {
+            0 => Ok(HashFunction::FarmHash),
+            1 => Ok(HashFunction::MurmurHash3),
+            2 => Ok(HashFunction::T1haHash),
+            3 => Ok(HashFunction::StdHash),
+            4 => Ok(HashFunction::MetroHash),
+            5 => Ok(HashFunction::SeededSip),
+            6 => Ok(HashFunction::WyHash),
+            _ => Err(CrustyError::ValidationError(format!("Unknown hash function tag {}", tag))),
+        }
+    }
+}
+
+impl HashScheme {
+    // tag used for the binary format
+    fn to_tag(self) -> u8 {
+        match self {
+            HashScheme::LinearProbe => 0,
+            HashScheme::RobinHood => 1,
+            HashScheme::Hopscotch => 2,
+            HashScheme::DoubleHash => 3,
+            HashScheme::Cuckoo => 4,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<Self, CrustyError> {
+        match tag {
+            0 => Ok(HashScheme::LinearProbe),
+            1 => Ok(HashScheme::RobinHood),
+            2 => Ok(HashScheme::Hopscotch),
+            3 => Ok(HashScheme::DoubleHash),
+            4 => Ok(HashScheme::Cuckoo),
+            _ => Err(CrustyError::ValidationError(format!("Unknown hash scheme tag {}", tag))),
+        }
+    }
+}
+
+impl ExtendOption {
+    // tag used for the binary format
+    fn to_tag(self) -> u8 {
+        match self {
+            ExtendOption::ExtendBucketSize => 0,
+            ExtendOption::ExtendBucketNumber => 1,
+            ExtendOption::Adaptive => 2,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<Self, CrustyError> {
+        match tag {
+            0 => Ok(ExtendOption::ExtendBucketSize),
+            1 => Ok(ExtendOption::ExtendBucketNumber),
+            2 => Ok(ExtendOption::Adaptive),
+            _ => Err(CrustyError::ValidationError(format!("Unknown extend option tag {}", tag))),
+        }
+    }
+}
+
+impl OnDuplicate {
+    // tag used for the binary format
+    fn to_tag(self) -> u8 {
+        match self {
+            OnDuplicate::Accumulate => 0,
+            OnDuplicate::Replace => 1,
+            OnDuplicate::KeepFirst => 2,
+            OnDuplicate::Error => 3,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<Self, CrustyError> {
+        match tag {
+            0 => Ok(OnDuplicate::Accumulate),
+            1 => Ok(OnDuplicate::Replace),
+            2 => Ok(OnDuplicate::KeepFirst),
+            3 => Ok(OnDuplicate::Error),
+            _ => Err(CrustyError::ValidationError(format!("Unknown duplicate policy tag {}", tag))),
+        }
+    }
+}
+
+impl CombineStrategy {
+    // tag used for the binary format
+    fn to_tag(self) -> u8 {
+        match self {
+            CombineStrategy::MulAdd => 0,
+            CombineStrategy::XorShift => 1,
+            CombineStrategy::Boost => 2,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<Self, CrustyError> {
+        match tag {
+            0 => Ok(CombineStrategy::MulAdd),
+            1 => Ok(CombineStrategy::XorShift),
+            2 => Ok(CombineStrategy::Boost),
+            _ => Err(CrustyError::ValidationError(format!("Unknown combine strategy tag {}", tag))),
+        }
+    }
+}
+
+// write a usize as 8 little endian bytes
+fn write_usize(buf: &mut Vec<u8>, x: usize) {
+    buf.extend((x as u64).to_le_bytes());
+}
+
+// function to get the greatest common divisor of two numbers
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+// function to get the chance a bucket with mean keys per bucket (Poisson) ends up with more than limit
+fn overflow_chance(mean: f64, limit: usize) -> f64 {
+    let k = limit + 1;
+    if mean <= 0.0 {
+        return 0.0;
+    } else if mean >= k as f64 {
+        return 1.0;
+    } else if k as f64 > mean + 40.0 * mean.sqrt() + 40.0 {
+        return 0.0;
+    }
+    // P(X = k), then the terms after it shrink by mean / i
+    let ln_fact: f64 = (1..=k).map(|i| (i as f64).ln()).sum();
+    let mut term = (k as f64 * mean.ln() - mean - ln_fact).exp();
+    let mut chance = 0.0;
+    let mut i = k;
+    while term > chance * 1e-12 {
+        chance += term;
+        i += 1;
+        term *= mean / i as f64;
+    }
+    chance
+}
+
+// function to get the smallest n from lo up that passes, for a pass that stays true once it holds
+fn smallest_passing(lo: usize, passes: impl Fn(usize) -> bool) -> usize {
+    let mut lo = std::cmp::max(lo, 1);
+    let mut hi = lo;
+    while !passes(hi) {
+        if hi == usize::MAX {
+            return hi;
+        }
+        hi = hi.saturating_mul(2);
+    }
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if passes(mid) { hi = mid; } else { lo = mid + 1; }
+    }
+    hi
+}
+
+// function to compare a key with a stored key, with an epsilon floats in the same epsilon cell are equal
+fn keys_match(key: KeyRef, stored: &Key, epsilon: Option<f64>) -> bool {
+    match epsilon {
+        None => key == *stored,
+        Some(epsilon) => key.len() == stored.len() && key.iter().zip(stored.iter())
+            .all(|(a, b)| quantize_field(a, epsilon) == quantize_field(b, epsilon)),
+    }
+}
+
+// function to get a copy of the key with its float fields replaced by their epsilon cell,
+// so keys that keys_match finds equal are equal copies
+fn cell_key(key: &Key, epsilon: Option<f64>) -> Key {
+    match epsilon {
+        Some(epsilon) => Key(key.iter().map(|field| quantize_field(field, epsilon).into_owned()).collect()),
+        None => key.clone(),
+    }
+}
+
+// function to turn a bincode error into a CrustyError, keeping io failures apart from bad tables
+fn bincode_error(error: bincode::ErrorKind) -> CrustyError {
+    match error {
+        bincode::ErrorKind::Io(error) => error.into(),
+        error => CrustyError::ValidationError(error.to_string()),
+    }
+}
+
+// function to add a duplicate key's value to the stored one, a sum past usize::MAX is an error instead of wrapping
+fn accumulate(old: &mut usize, new: usize) -> Result<(), CrustyError> {
+    *old = old.checked_add(new)
+        .ok_or_else(|| CrustyError::ExecutionError(String::from("Accumulated value overflows usize")))?;
+    Ok(())
+}
+
+// function to replace a float field by the index of its epsilon cell, other fields stay as they are
+fn quantize_field(field: &Field, epsilon: f64) -> Cow<'_, Field> {
+    match field {
+        Field::FloatField(x) => Cow::Owned(Field::FloatField((x / epsilon).round())),
+        _ => Cow::Borrowed(field),
+    }
+}
+
+// splitmix64 finalizer, spreads every input bit over the whole output
+fn slot_mix(h: u64) -> u64 {
+    let mut x = h.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+// write a field as |type tag|byte length|Field::to_bytes|
+pub(crate) fn write_field(buf: &mut Vec<u8>, field: &Field) {
+    let bytes = field.to_bytes();
+    let tag = match field {
+        Field::IntField(_) => 0,
+        Field::StringField(_) => 1,
+        Field::FloatField(_) => 2,
+        Field::BytesField(_) => 3,
+        Field::LongField(_) => 4,
+    };
+    buf.push(tag);
+    buf.extend((bytes.len() as u32).to_le_bytes());
+    buf.extend(bytes);
+}
+
+/// Cursor over a serialized table, every read fails with a ValidationError once the input runs out.
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        ByteReader { bytes, pos: 0 }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], CrustyError> {
+        if self.bytes.len() - self.pos < n {
+            return Err(CrustyError::ValidationError(String::from("Unexpected end of table bytes")));
+        }
+        let res = &self.bytes[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(res)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, CrustyError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u64(&mut self) -> Result<u64, CrustyError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_usize(&mut self) -> Result<usize, CrustyError> {
+        Ok(self.read_u64()? as usize)
+    }
+
+    // read a (bucket_index, index) pair and check it lies inside the table
+    fn read_position(&mut self, b_num: usize, b_size: usize) -> Result<(usize, usize), CrustyError> {
+        let bucket_index = self.read_usize()?;
+        let index = self.read_usize()?;
+        if bucket_index >= b_num || index >= b_size {
+            return Err(CrustyError::ValidationError(String::from("Position out of table bounds")));
+        }
+        Ok((bucket_index, index))
+    }
+
+    // read a field written by write_field
+    pub(crate) fn read_field(&mut self) -> Result<Field, CrustyError> {
+        let tag = self.read_u8()?;
+        let len = u32::from_le_bytes(self.take(4)?.try_into().unwrap()) as usize;
+        let bytes = self.take(len)?;
+        match tag {
+            0 if len == 4 => Ok(Field::IntField(i32::from_le_bytes(bytes.try_into().unwrap()))),
+            1 if len >= 8 => {
+                let s_len = u64::from_le_bytes(bytes[..8].try_into().unwrap()) as usize;
+                let s_bytes = bytes[8..].get(..s_len).ok_or_else(|| {
+                    CrustyError::ValidationError(String::from("String field longer than its record"))
+                })?;
+                String::from_utf8(s_bytes.to_vec())
+                    .map(Field::StringField)
+                    .map_err(|_| CrustyError::ValidationError(String::from("String field is not utf8")))
+            }
+            2 if len == 8 => Ok(Field::FloatField(f64::from_le_bytes(bytes.try_into().unwrap()))),
+            3 if len >= 8 => {
+                let b_len = u64::from_le_bytes(bytes[..8].try_into().unwrap()) as usize;
+                bytes[8..].get(..b_len)
+                    .map(|b| Field::BytesField(b.to_vec()))
+                    .ok_or_else(|| CrustyError::ValidationError(String::from("Bytes field longer than its record")))
+            }
+            4 if len == 8 => Ok(Field::LongField(i64::from_le_bytes(bytes.try_into().unwrap()))),
+            _ => Err(CrustyError::ValidationError(format!("Malformed field with tag {}", tag))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_hash {
+    use super::*;
+
+    // function to test extend
+    pub fn test_extend() {
+        let mut table = HashTable::new(
+            5,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.75,
+        ).unwrap();
+
+        let name = Field::StringField(String::from("Adam"));
+        let course_taken = Field::IntField(0);
+        table.insert((name, course_taken), 1).unwrap();
+
+        let name = Field::StringField(String::from("Ben"));
+        let course_taken = Field::IntField(1);
+        table.insert((name, course_taken), 1).unwrap();
+
+        let name = Field::StringField(String::from("Chris"));
+        let course_taken = Field::IntField(1);
+        table.insert((name, course_taken), 1).unwrap();
+
+        // Before first rehash: 5 * 0.75 = 3.75
+        assert_eq!(3, table.taken_count[0]);
+        assert_eq!(5, table.BUCKET_SIZE);
+
+        let name = Field::StringField(String::from("David"));
+        let course_taken = Field::IntField(1);
+        table.insert((name, course_taken), 1).unwrap();
+
+        // After first rehash: 10 * 0.75 = 7.5
+        assert_eq!(4, table.taken_count[0]);
+        assert_eq!(10, table.BUCKET_SIZE);
+
+        let name = Field::StringField(String::from("Eva"));
+        let course_taken = Field::IntField(85);
+        table.insert((name, course_taken), 1).unwrap();
+
+        let name = Field::StringField(String::from("Frank"));
+        let course_taken = Field::IntField(16);
+        table.insert((name, course_taken), 1).unwrap();
+
+        let name = Field::StringField(String::from("Grant"));
+        let course_taken = Field::IntField(63);
+        table.insert((name, course_taken), 1).unwrap();
+
+        // before second rehash
+        assert_eq!(7, table.taken_count[0]);
+        assert_eq!(10, table.BUCKET_SIZE);
+
+        let name = Field::StringField(String::from("Hilton"));
+        let course_taken = Field::IntField(11);
+        table.insert((name, course_taken), 1).unwrap();
+
+        // after second rehash
+        assert_eq!(8, table.taken_count[0]);
+        assert_eq!(20, table.BUCKET_SIZE);
+    }
+
+    // function to test hopscotch
+    pub fn test_hopscotch() {
+        let mut table = HashTable::new(
+            13,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::Hopscotch,
+            4,
+            ExtendOption::ExtendBucketSize,
+            1.0,
+        ).unwrap();
+        table.buckets[0][0].taken = true;
+        table.buckets[0][0].key = (Field::StringField(String::from("M")), Field::IntField(0)).into();
+        table.buckets[0][1].taken = true;
+        table.buckets[0][1].key = (Field::StringField(String::from("M")), Field::IntField(1)).into();
+        table.buckets[0][3].taken = true;
+        table.buckets[0][3].key = (Field::StringField(String::from("M")), Field::IntField(3)).into();
+        table.hop_info[0][3] = 4; // 0100
+        table.buckets[0][4].taken = true;
+        table.buckets[0][4].key = (Field::StringField(String::from("M")), Field::IntField(4)).into();
+        table.buckets[0][5].taken = true;
+        table.hop_info[0][5] = 10; // 1010
+        table.buckets[0][6].taken = true;
+        table.buckets[0][6].key = (Field::StringField(String::from("M")), Field::IntField(6)).into();
+        table.buckets[0][7].taken = true;
+        table.buckets[0][7].key = (Field::StringField(String::from("M")), Field::IntField(7)).into();
+        table.hop_info[0][7] = 4; // 0100
+        table.buckets[0][8].taken = true;
+        table.buckets[0][8].key = (Field::StringField(String::from("M")), Field::IntField(8)).into();
+        table.buckets[0][9].taken = true;
+        table.buckets[0][9].key = (Field::StringField(String::from("M")), Field::IntField(9)).into();
+        table.hop_info[0][9] = 4; // 0100
+        table.buckets[0][10].taken = true;
+        table.buckets[0][10].key = (Field::StringField(String::from("M")), Field::IntField(10)).into();
+        table.buckets[0][11].taken = true;
+        table.buckets[0][11].key = (Field::StringField(String::from("M")), Field::IntField(11)).into();
+        table.taken_count[0] = 11;
+
+        let name = Field::StringField(String::from("Mark"));
+        let course_taken = Field::IntField(8);
+        // assert_eq!(table.get_indexes((&name, &course_taken)).unwrap().1, 3);
+        table.insert((name, course_taken), 1).unwrap();
+        assert_eq!(table.hop_info[0][9], 1);
+        assert_eq!(table.hop_info[0][7], 1);
+        assert_eq!(table.hop_info[0][5], 3);
+        assert_eq!(table.hop_info[0][3], 6);
+        assert!(table.buckets[0][12].taken);
+        assert_eq!(table.taken_count[0], 12);
+    }
+
+    // function to test hopscotch
+    pub fn test_hopscotch2() {
+        let mut table = HashTable::new(
+            100,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::Hopscotch,
+            10,
+            ExtendOption::ExtendBucketSize,
+            0.75,
+        ).unwrap();
+
+        let name1 = Field::StringField(String::from("Adamdsf"));
+        let course_taken1 = Field::IntField(0);
+        table.insert((name1, course_taken1), 1).unwrap();
+
+        let name2 = Field::StringField(String::from("Bensdfsdfds"));
+        let course_taken2 = Field::IntField(1);
+        table.insert((name2, course_taken2), 1).unwrap();
+
+        let name3 = Field::StringField(String::from("Chrissdfds"));
+        let course_taken3 = Field::IntField(1);
+        table.insert((name3, course_taken3), 1).unwrap();
+
+        let name4 = Field::StringField(String::from("Daviddf"));
+        let course_taken4 = Field::IntField(1);
+        table.insert((name4, course_taken4), 1).unwrap();
+
+        let name5 = Field::StringField(String::from("Evadsfsdfsdfsdfsd"));
+        let course_taken5 = Field::IntField(85);
+        table.insert((name5, course_taken5), 1).unwrap();
+
+        let name6 = Field::StringField(String::from("Franksdf"));
+        let course_taken6 = Field::IntField(16);
+        table.insert((name6, course_taken6), 1).unwrap();
+
+        let name7 = Field::StringField(String::from("Grantsdf"));
+        let course_taken7 = Field::IntField(63);
+        table.insert((name7, course_taken7), 1).unwrap();
+
+        let name8 = Field::StringField(String::from("Hilton"));
+        let course_taken8 = Field::IntField(11);
+        table.insert((name8, course_taken8), 1).unwrap();
+
+        let name9 = Field::StringField(String::from("Idamsdfsdf"));
+        let course_taken9 = Field::IntField(23);
+        table.insert((name9, course_taken9), 1).unwrap();
+
+        let name10 = Field::StringField(String::from("Jendf"));
+        let course_taken10 = Field::IntField(656);
+        table.insert((name10, course_taken10), 1).unwrap();
+
+        let name11 = Field::StringField(String::from("Khrissdfs"));
+        let course_taken11 = Field::IntField(989);
+        table.insert((name11, course_taken11), 1).unwrap();
+
+        let name12 = Field::StringField(String::from("Lavid"));
+        let course_taken12 = Field::IntField(45);
+        // assert_eq!(table.get_indexes((&name12, &course_taken12)).unwrap().1, 8);
+        table.insert((name12, course_taken12), 1).unwrap();
+
+        let name13 = Field::StringField(String::from("Mva"));
+        let course_taken13 = Field::IntField(9879);
+        table.insert((name13, course_taken13), 1).unwrap();
+
+        let name14 = Field::StringField(String::from("Nrank"));
+        let course_taken14 = Field::IntField(454);
+        table.insert((name14, course_taken14), 1).unwrap();
+
+        let name15 = Field::StringField(String::from("Osdafhj"));
+        let course_taken15 = Field::StringField(String::from("Ohajd"));
+        table.insert((name15, course_taken15), 1).unwrap();
+
+        let name16 = Field::StringField(String::from("Podfh"));
+        let course_taken16 = Field::StringField(String::from("Pdfki"));
+        table.insert((name16, course_taken16), 1).unwrap();
+
+        let name17 = Field::StringField(String::from("Qkdsfai"));
+        let course_taken17 = Field::StringField(String::from("Qjidif"));
+        table.insert((name17, course_taken17), 1).unwrap();
+
+        let name18 = Field::StringField(String::from("Rjksdf"));
+        let course_taken18 = Field::StringField(String::from("Rkdsfi"));
+        table.insert((name18, course_taken18), 1).unwrap();
+
+        let name19 = Field::StringField(String::from("Sjkdfi"));
+        let course_taken19 = Field::StringField(String::from("Sjkdfi"));
+        table.insert((name19, course_taken19), 1).unwrap();
+
+        let name20 = Field::StringField(String::from("Thsdud"));
+        let course_taken20 = Field::StringField(String::from("Thjksdfi"));
+        table.insert((name20, course_taken20), 1).unwrap();
+    }
+
+    // function to test insert with robin hood scheme
+    pub fn test_insert_robin_hood() {
+        let mut table = HashTable::new(
+            4,
+            1,
+            HashFunction::StdHash,
+            HashScheme::RobinHood,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        // slots follow from the remixed key hash, so pick the courses whose keys have the homes the test needs
+        let name = Field::StringField(String::from("Adam"));
+        let courses_with_home = |home: usize| -> Vec<i32> {
+            (0..).filter(|&i| table.home_index((&name, &Field::IntField(i))) == home).take(2).collect()
+        };
+        let (home0, home1) = (courses_with_home(0), courses_with_home(1));
+
+        // HN1 -> 0
+        let course_taken = Field::IntField(home0[0]);
+        table.insert((name.clone(), course_taken.clone()), 1).unwrap();
+        assert_eq!(table.buckets[0][0].key, (name.clone(), course_taken));
+        assert_eq!(table.buckets[0][0].dis, 0);
+
+        // HN2 -> 1
+        let course_taken = Field::IntField(home1[0]);
+        table.insert((name.clone(), course_taken.clone()), 1).unwrap();
+        assert_eq!(table.buckets[0][1].key, (name.clone(), course_taken));
+        assert_eq!(table.buckets[0][1].dis, 0);
+        assert!(table.buckets[0][1].taken);
+
+        // HN3 -> 1 -> 2
+        let course_taken = Field::IntField(home1[1]);
+        let indexes3 = table.get_indexes((&name, &course_taken)).unwrap();
+        assert_eq!(indexes3.1, 2);
+        assert_eq!(indexes3.2, 1);
+        table.insert((name.clone(), course_taken.clone()), 1).unwrap();
+        assert_eq!(table.buckets[0][2].key, (name.clone(), course_taken));
+        assert_eq!(table.buckets[0][2].dis, 1);
+        assert!(table.buckets[0][2].taken);
+
+        // HN4 -> 0 -> 1, taking the slot of HN2 which sits closer to its home
+        let course_taken = Field::IntField(home0[1]);
+        let indexes4 = table.get_indexes((&name, &course_taken)).unwrap();
+        assert_eq!(indexes4.1, 1);
+        assert_eq!(
+            table.linear_probe((&name, &course_taken).into(), 0, 0).unwrap(),
+            3);
     }
 
     // function to test basic functionality of Field
     pub fn test_field() {
         let f_int = Field::IntField(1);
         let f_str = Field::StringField(String::from("Hello"));
-        assert_eq!(1, f_int.unwrap_int_field());
-        assert_eq!("Hello", f_str.unwrap_string_field());
+        assert_eq!(1, f_int.unwrap_int_field());
+        assert_eq!("Hello", f_str.unwrap_string_field());
+    }
+
+    // function to test basic functionality of user defined enum
+    pub fn test_my_enum() {
+        let s = HashFunction::FarmHash;
+        match s{
+            HashFunction::MurmurHash3 => { println!("Murmur3") },
+            HashFunction::T1haHash => { println!("T1") },
+            HashFunction::FarmHash => { println!("Farm") },
+            HashFunction::StdHash => { println!("Std") },
+            HashFunction::MetroHash => { println!("Metro") },
+            HashFunction::SeededSip => { println!("SeededSip") },
+            HashFunction::WyHash => { println!("Wy") },
+            HashFunction::Custom => { println!("Custom") },
+        };
+    }
+
+    // function to test std hash function for Field
+    pub fn test_std_hash() {
+        let f_int = Field::IntField(1);
+        let f_str = Field::StringField(String::from("Hello"));
+        assert_eq!(1742378985846435984_usize, f_int.std_hash());
+        assert_eq!(12991522711919756218_usize, f_str.std_hash());
+    }
+
+    // function to test farm hash function for Field
+    pub fn test_farm_hash() {
+        let f_int = Field::IntField(1);
+        let f_str = Field::StringField(String::from("Hello"));
+        assert_eq!(538479481099171624_usize, f_int.farm_hash());
+        assert_eq!(15404698994557526151_usize, f_str.farm_hash());
+    }
+
+    // function to test murmur3 hash function for Field
+    pub fn test_murmur3_hash() {
+        let f_int = Field::IntField(1);
+        let f_str = Field::StringField(String::from("Hello"));
+        assert_eq!(854115492_usize, f_int.murmur_hash3());
+        assert_eq!(316307400_usize, f_str.murmur_hash3());
+    }
+
+    // function to test t1ha function for Field
+    pub fn test_t1ha_hash() {
+        let f_int = Field::IntField(1);
+        let f_str = Field::StringField(String::from("Hello"));
+        assert_eq!(4348539232621042483_usize, f_int.t1ha_hash());
+        assert_eq!(3284986864571460951_usize, f_str.t1ha_hash());
+    }
+
+    // function to test metro hash function for Field
+    pub fn test_metro_hash() {
+        let f_int = Field::IntField(1);
+        let f_str = Field::StringField(String::from("Hello"));
+        assert_eq!(4114779683113857913, f_int.metro_hash());
+        assert_eq!(747896648237494155, f_str.metro_hash());
+    }
+
+    // function to test wyhash function for Field
+    pub fn test_wy_hash() {
+        let f_int = Field::IntField(1);
+        let f_str = Field::StringField(String::from("Hello"));
+        assert_eq!(18214790767025056489, f_int.wy_hash());
+        assert_eq!(5150427183875209253, f_str.wy_hash());
+    }
+
+    // function to test initialization and modification of HashNode
+    pub fn test_hash_node() {
+        // init a node object with default
+        let mut node = HashNode::default();
+        assert!(node.key.is_empty());
+        assert_eq!(0, node.key.0.capacity());
+        assert!(!node.taken);
+        assert_eq!(usize::MAX, node.dis);
+
+        let name = Field::StringField(String::from("Mark"));
+        let course_taken = Field::IntField(6);
+        let hash_key = (name, course_taken);
+
+        // modify the node object
+        node = HashNode {key: hash_key.into(), taken: true, dis: 0, pinned: false, deleted: false};
+        assert_eq!((Field::StringField(String::from("Mark")), Field::IntField(6)), node.key);
+        assert!(node.taken);
+        assert_eq!(0, node.dis);
+    }
+
+    // function to test initialization of HashTable
+    pub fn test_table_new() {
+        let table = HashTable::new(
+            10,
+            2,
+            HashFunction::StdHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        assert_eq!(2, table.BUCKET_NUMBER);
+        assert_eq!(10, table.BUCKET_SIZE);
+        assert_eq!(vec![0; 2],table.taken_count);
+        assert_eq!(2, table.buckets.len());
+        assert_eq!(10, table.buckets[0].len());
+        assert!(!table.buckets[0][0].taken);
+        assert!(table.buckets[0][0].key.is_empty());
+        assert_eq!(0, *table.value_at(0, 0));
+        // no value column until a value other than 0 is stored
+        assert!(table.values.is_empty());
+        assert_eq!(4, table.H);
+    }
+
+    // function to test the defaults filled in by with_defaults
+    pub fn test_with_defaults() {
+        let mut table = HashTable::with_defaults(8, 2);
+        assert!((HashFunction::StdHash, HashScheme::LinearProbe, ExtendOption::ExtendBucketSize, 0.9, 4) == table.config());
+        assert_eq!((8, 2), (table.BUCKET_SIZE, table.BUCKET_NUMBER));
+        for i in 0..50 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        assert_eq!(50, table.len());
+    }
+
+    // function to test get_bucket_index
+    pub fn test_get_bucket_index() {
+        let table = HashTable::new(
+            10,
+            2,
+            HashFunction::MurmurHash3,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+
+        let name = Field::StringField(String::from("Mark"));
+        let course_taken = Field::IntField(6);
+        let hash_key = (&name, &course_taken);
+        assert_eq!(table.get_bucket_index(hash_key).unwrap(), 1);
+    }
+
+    // function to test linear_probe
+    pub fn test_linear_probe() {
+        let mut table = HashTable::new(
+            10,
+            1,
+            HashFunction::StdHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        table.buckets[0][0].taken = true;
+
+        let name = Field::StringField(String::from("Mark"));
+        let course_taken = Field::IntField(6);
+        assert_eq!(
+            table.linear_probe((&name, &course_taken).into(), 0, 0).unwrap(),
+            1);
+
+        table.buckets[0][1].key = (name, course_taken).into();
+        table.buckets[0][1].taken = true;
+        let name = Field::StringField(String::from("Mark"));
+        let course_taken = Field::IntField(6);
+        assert_eq!(
+            table.linear_probe((&name, &course_taken).into(), 0, 0).unwrap(),
+            1);
+
+        let name2 = Field::StringField(String::from("Jack"));
+        let course_taken2 = Field::IntField(3);
+        table.buckets[0][1].key = (name2, course_taken2).into();
+        table.buckets[0][1].taken = true;
+        assert_eq!(
+            table.linear_probe((&name, &course_taken).into(), 0, 0).unwrap(),
+            2);
+    }
+
+    // function to test get_index
+    pub fn test_get_indexes() {
+        let mut table = HashTable::new(
+            10,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        let name = Field::StringField(String::from("Mark"));
+        let course_taken = Field::IntField(6);
+
+        // table.buckets[0][9].taken = true;
+        // table.buckets[0][0].taken = true;
+
+        // an empty bucket places the key at its home slot, which follows from the remixed key hash
+        let indexes = table.get_indexes((&name, &course_taken));
+        assert_eq!(0, indexes.unwrap().0);
+        assert_eq!(table.home_index((&name, &course_taken)), indexes.unwrap().1);
+        assert_eq!(0, indexes.unwrap().2);
+    }
+
+    // function to test get_mut_value
+    pub fn test_get_mut_value() {
+        let mut table = HashTable::new(
+            10,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+
+        let name = Field::StringField(String::from("Mark"));
+        let course_taken = Field::IntField(6);
+        let indexes = table.get_indexes((&name, &course_taken)).unwrap();
+        table.buckets[indexes.0][indexes.1].key = (name, course_taken).into();
+        table.set_value(indexes.0, indexes.1, 1);
+        table.buckets[indexes.0][indexes.1].taken = true;
+        table.taken_count[indexes.0] += 1;
+
+        let v = table.get_mut_value(
+            (&Field::StringField(String::from("Mark")), &Field::IntField(6))).unwrap();
+        let expected_v = 1_usize;
+        assert_eq!(&expected_v, v);
+        assert!(table.get_mut_value(
+            (&Field::StringField(String::from("Mark")), &Field::IntField(7))).is_none());
+    }
+
+    // function to test get_value
+    pub fn test_get_value() {
+        let mut table = HashTable::new(
+            10,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+
+        let name = Field::StringField(String::from("Mark"));
+        let course_taken = Field::IntField(6);
+        let indexes = table.get_indexes((&name, &course_taken)).unwrap();
+        table.buckets[indexes.0][indexes.1].key = (name, course_taken).into();
+        table.set_value(indexes.0, indexes.1, 1);
+        table.buckets[indexes.0][indexes.1].taken = true;
+        table.taken_count[indexes.0] += 1;
+
+        let v = table.get_mut_value(
+            (&Field::StringField(String::from("Mark")), &Field::IntField(6))).unwrap();
+        let expected_v = 1_usize;
+        assert_eq!(&expected_v, v);
+    }
+
+    // function to test insert
+    pub fn test_insert() {
+        let mut table = HashTable::new(
+            10,
+            2,
+            HashFunction::T1haHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+
+        let name1 = Field::StringField(String::from("Mark"));
+        let course_taken1 = Field::IntField(6);
+        let indexes1 = table.get_indexes((&name1, &course_taken1)).unwrap();
+
+        table.insert((name1, course_taken1), 1).unwrap();
+        assert_eq!(Field::StringField(String::from("Mark")), table.buckets[indexes1.0][indexes1.1].key[0]);
+        assert_eq!(Field::IntField(6), table.buckets[indexes1.0][indexes1.1].key[1]);
+        assert_eq!(1, *table.value_at(indexes1.0, indexes1.1));
+        assert!(table.buckets[indexes1.0][indexes1.1].taken);
+        assert_eq!(1, table.taken_count[indexes1.0]);
+
+        let name1_2 = Field::StringField(String::from("Mark"));
+        let course_taken1_2 = Field::IntField(6);
+        table.insert((name1_2, course_taken1_2), 1).unwrap();
+        assert_eq!(Field::StringField(String::from("Mark")), table.buckets[indexes1.0][indexes1.1].key[0]);
+        assert_eq!(Field::IntField(6), table.buckets[indexes1.0][indexes1.1].key[1]);
+        assert_eq!(2, *table.value_at(indexes1.0, indexes1.1));
+        assert!(table.buckets[indexes1.0][indexes1.1].taken);
+        assert_eq!(1, table.taken_count[indexes1.0]);
+    }
+
+    // function to test robin_hood
+    pub fn test_robin_hood() {
+        let mut table = HashTable::new(
+            10,
+            1,
+            HashFunction::StdHash,
+            HashScheme::RobinHood,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+
+        // HN1 -> 0
+        let name = Field::StringField(String::from("Adam"));
+        let course_taken = Field::IntField(6);
+        let node = HashNode {key: (name, course_taken).into(), taken: true, dis: 0, pinned: false, deleted: false};
+        table.buckets[0][0] = node;
+
+        // HN2 -> 0 -> 1
+        let name = Field::StringField(String::from("Ben"));
+        let course_taken = Field::IntField(12);
+        assert_eq!(
+            table.robin_hood((&name, &course_taken).into(), 0, 0).unwrap(),
+            (1_usize, 1_usize));
+        let node = HashNode {key: (name, course_taken).into(), taken: true, dis: 1, pinned: false, deleted: false};
+        table.buckets[0][1] = node;
+
+        // HN3 -> 1 -> 2
+        let name = Field::StringField(String::from("Chris"));
+        let course_taken = Field::IntField(1);
+        assert_eq!(
+            table.robin_hood((&name, &course_taken).into(), 0, 1).unwrap(),
+            (2_usize, 1_usize));
+        let node = HashNode {key: (name, course_taken).into(), taken: true, dis: 1, pinned: false, deleted: false};
+        table.buckets[0][2] = node;
+
+        // HN4 -> 0 -> 2
+        let name = Field::StringField(String::from("David"));
+        let course_taken = Field::IntField(3);
+        assert_eq!(
+            table.robin_hood((&name, &course_taken).into(), 0, 0).unwrap(),
+            (2_usize, 2_usize));
+        let node = HashNode {key: (name, course_taken).into(), taken: true, dis: 2, pinned: false, deleted: false};
+        table.buckets[0][2] = node;
+
+        // HN3 -> 1 -> 3
+        let name = Field::StringField(String::from("Chris"));
+        let course_taken = Field::IntField(1);
+        assert_eq!(
+            table.robin_hood((&name, &course_taken).into(), 0, 1).unwrap(),
+            (3_usize, 2_usize));
+        let node = HashNode {key: (name, course_taken).into(), taken: true, dis: 2, pinned: false, deleted: false};
+        table.buckets[0][3] = node;
+    }
+
+    // function to test the compact binary round trip of a table
+    pub fn test_table_to_bytes() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut table = HashTable::new(
+                10,
+                2,
+                HashFunction::FarmHash,
+                scheme,
+                4,
+                ExtendOption::ExtendBucketSize,
+                0.9,
+            ).unwrap();
+            let names = ["Adam", "Ben", "Chris", "David", "Eva"];
+            for (i, name) in names.iter().enumerate() {
+                table.insert((Field::StringField(String::from(*name)), Field::IntField(i as i32)), i + 1).unwrap();
+            }
+
+            let bytes = table.to_bytes().unwrap();
+            let restored = HashTable::from_bytes(&bytes).unwrap();
+            assert_eq!(table.BUCKET_SIZE, restored.BUCKET_SIZE);
+            assert_eq!(table.BUCKET_NUMBER, restored.BUCKET_NUMBER);
+            assert_eq!(table.taken_count, restored.taken_count);
+            assert_eq!(table.hop_info, restored.hop_info);
+            for (i, name) in names.iter().chain(["Frank"].iter()).enumerate() {
+                let key = (Field::StringField(String::from(*name)), Field::IntField(i as i32));
+                let expected = table.get_value((&key.0, &key.1)).copied();
+                assert_eq!(expected, restored.get_value((&key.0, &key.1)).copied());
+            }
+        }
+        assert!(HashTable::from_bytes(b"RHT1").is_err());
+    }
+
+    // function to test iterating entries in insertion order
+    pub fn test_insertion_order() {
+        let mut table = HashTable::new(
+            5,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.75,
+        ).unwrap();
+        table.keep_insertion_order();
+
+        let names = ["Hilton", "Adam", "Frank", "Ben", "Eva", "Chris"];
+        for (i, name) in names.iter().enumerate() {
+            table.insert((Field::StringField(String::from(*name)), Field::IntField(i as i32)), 1).unwrap();
+            // re-inserting an earlier key must not move it
+            table.insert((Field::StringField(String::from(names[0])), Field::IntField(0)), 1).unwrap();
+        }
+        // the table was extended along the way
+        assert_eq!(10, table.BUCKET_SIZE);
+
+        let ordered: Vec<Key> = table.iter_ordered().map(|(key, _)| key.clone()).collect();
+        let expected: Vec<(Field, Field)> = names.iter().enumerate()
+            .map(|(i, name)| (Field::StringField(String::from(*name)), Field::IntField(i as i32)))
+            .collect();
+        assert_eq!(expected, ordered);
+    }
+
+    // function to test that combine strategies spread keys differently but keep them all retrievable
+    pub fn test_combine_strategy() {
+        let keys: Vec<(Field, Field)> = (0..200)
+            .map(|i| (Field::StringField(format!("name{}", i)), Field::IntField(i)))
+            .collect();
+        let mut occupancies = Vec::new();
+        for combine in [CombineStrategy::MulAdd, CombineStrategy::XorShift, CombineStrategy::Boost] {
+            let mut table = HashTable::new(
+                64,
+                16,
+                HashFunction::FarmHash,
+                HashScheme::LinearProbe,
+                4,
+                ExtendOption::ExtendBucketSize,
+                0.9,
+            ).unwrap();
+            table.set_combine_strategy(combine).unwrap();
+            for key in keys.iter() {
+                table.insert(key.clone(), 1).unwrap();
+            }
+            assert_eq!(64, table.BUCKET_SIZE);
+            for key in keys.iter() {
+                assert_eq!(Some(&1), table.get_value((&key.0, &key.1)));
+            }
+            occupancies.push(table.taken_count.clone());
+
+            // switching a filled table rehashes every entry under the new strategy
+            let mut switched = HashTable::from_entries(&table.to_entries(), table.config()).unwrap();
+            switched.set_combine_strategy(combine).unwrap();
+            switched.set_combine_strategy(CombineStrategy::XorShift).unwrap();
+            assert!(switched.combine == CombineStrategy::XorShift);
+            assert!(keys.iter().all(|key| switched.get_value((&key.0, &key.1)) == Some(&1)));
+            switched.check_invariants().unwrap();
+        }
+        assert_ne!(occupancies[0], occupancies[1]);
+        assert_ne!(occupancies[0], occupancies[2]);
+        assert_ne!(occupancies[1], occupancies[2]);
+    }
+
+    // function to test that longest_chain returns exactly the colliding keys
+    pub fn test_longest_chain() {
+        let mut table = HashTable::new(
+            64,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        let dep = Field::StringField(String::from("CS"));
+        let home = |table: &HashTable, i: i32| table.home_index((&dep, &Field::IntField(i)));
+
+        // four keys sharing home index 10 and two keys far away from it
+        let colliding: Vec<(Field, Field)> = (0..).filter(|i| home(&table, *i) == 10)
+            .take(4)
+            .map(|i| (dep.clone(), Field::IntField(i)))
+            .collect();
+        let lonely: Vec<(Field, Field)> = [30, 50].iter()
+            .map(|slot| (0..).find(|i| home(&table, *i) == *slot).unwrap())
+            .map(|i| (dep.clone(), Field::IntField(i)))
+            .collect();
+        for key in lonely.iter().chain(colliding.iter()) {
+            table.insert(key.clone(), 1).unwrap();
+        }
+
+        assert_eq!(colliding, table.longest_chain());
+    }
+
+    // function to test reducing partial tables into one
+    pub fn test_reduce() {
+        let new_table = || HashTable::new(
+            8,
+            2,
+            HashFunction::MurmurHash3,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.75,
+        ).unwrap();
+        // 300 rows over 50 distinct keys
+        let rows: Vec<(Field, Field)> = (0..300)
+            .map(|i| (Field::StringField(String::from("CS")), Field::IntField(i % 50)))
+            .collect();
+
+        let mut single = new_table();
+        for row in rows.iter() {
+            single.insert(row.clone(), 1).unwrap();
+        }
+        let partials: Vec<HashTable> = rows.chunks(75)
+            .map(|chunk| {
+                let mut table = new_table();
+                for row in chunk {
+                    table.insert(row.clone(), 1).unwrap();
+                }
+                table
+            })
+            .collect();
+        assert_eq!(4, partials.len());
+
+        let reduced = HashTable::reduce(partials).unwrap();
+        assert_eq!(50, reduced.taken_count.iter().sum::<usize>());
+        for i in 0..50 {
+            let key = (Field::StringField(String::from("CS")), Field::IntField(i));
+            assert_eq!(Some(&6), reduced.get_value((&key.0, &key.1)));
+            assert_eq!(
+                single.get_value((&key.0, &key.1)),
+                reduced.get_value((&key.0, &key.1)));
+        }
+        assert!(matches!(HashTable::reduce(vec![]), Err(CrustyError::ValidationError(_))));
+    }
+
+    // function to test converting accumulated values into fields
+    pub fn test_into_field() {
+        assert_eq!(Field::IntField(0), 0_usize.into_field());
+        assert_eq!(Field::IntField(42), 42_usize.into_field());
+        assert_eq!(Field::IntField(i32::MAX), (i32::MAX as usize).into_field());
+        assert_eq!(Field::LongField(i32::MAX as i64 + 1), (i32::MAX as usize + 1).into_field());
+        assert_eq!(Field::LongField(i64::MAX), usize::MAX.into_field());
+        assert_eq!(Field::FloatField(2.5), 2.5_f64.into_field());
+        assert_eq!(Field::FloatField(f64::NAN), f64::NAN.into_field());
+    }
+
+    // function to test that corrupted serialized tables are rejected
+    pub fn test_corrupted_bytes() {
+        let mut table = HashTable::new(
+            10,
+            2,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        for (i, name) in ["Adam", "Ben", "Chris", "David"].iter().enumerate() {
+            table.insert((Field::StringField(String::from(*name)), Field::IntField(i as i32)), 1).unwrap();
+        }
+        let bytes = table.to_bytes().unwrap();
+        assert!(HashTable::from_bytes(&bytes).is_ok());
+
+        // flipping any byte after the header breaks the checksum
+        let mut flipped = bytes.clone();
+        let last = flipped.len() - 1;
+        flipped[last] ^= 0xff;
+        assert!(matches!(HashTable::from_bytes(&flipped), Err(CrustyError::ValidationError(_))));
+
+        // a node moved into the wrong bucket is caught by the invariants even with a valid checksum
+        let (bucket_index, index) = (0..2).flat_map(|b| (0..10).map(move |i| (b, i)))
+            .find(|(b, i)| table.buckets[*b][*i].taken)
+            .unwrap();
+        let node = std::mem::take(&mut table.buckets[bucket_index][index]);
+        table.buckets[1 - bucket_index][index] = node;
+        table.taken_count[bucket_index] -= 1;
+        table.taken_count[1 - bucket_index] += 1;
+        assert!(table.check_invariants().is_err());
+        assert!(matches!(HashTable::from_bytes(&table.to_bytes().unwrap()), Err(CrustyError::ValidationError(_))));
+    }
+
+    // function to test that a round trip keeps the insert settings and insertion order, and that a header
+    // claiming more slots than the input could hold or a neighborhood wider than the hop info is rejected
+    pub fn test_bytes_settings() {
+        let mut table = HashTable::new(8, 2, HashFunction::FarmHash, HashScheme::Hopscotch, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        table.set_on_duplicate(OnDuplicate::KeepFirst);
+        table.set_allow_nan(true);
+        table.set_float_epsilon(Some(0.5)).unwrap();
+        table.keep_insertion_order();
+        for name in ["Eva", "Adam", "Chris"] {
+            table.insert((Field::StringField(String::from(name)), Field::FloatField(1.0)), 1).unwrap();
+        }
+        let mut restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+        let order: Vec<&Key> = restored.iter_ordered().map(|(key, _)| key).collect();
+        assert_eq!(table.iter_ordered().map(|(key, _)| key).collect::<Vec<_>>(), order);
+        // KeepFirst and the epsilon survive: 1.1 is the stored 1.0 and its value stays
+        let eva = (Field::StringField(String::from("Eva")), Field::FloatField(1.1));
+        restored.insert(eva.clone(), 5).unwrap();
+        assert_eq!(Some(&1), restored.get_value((&eva.0, &eva.1)));
+        assert!(restored.insert((Field::StringField(String::from("Ben")), Field::FloatField(f64::NAN)), 1).is_ok());
+
+        let mut members = HashTable::new(8, 2, HashFunction::FarmHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        members.set_membership_only(true);
+        members.insert((Field::IntField(1), Field::IntField(2)), 1).unwrap();
+        assert!(HashTable::from_bytes(&members.to_bytes().unwrap()).unwrap().membership_only);
+
+        // rewrite a header field at offset, with the checksum fixed up so only the bounds can reject it
+        let forge = |offset: usize, value: u64| {
+            let mut bytes = members.to_bytes().unwrap();
+            bytes[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+            let checksum = farmhash::hash64(&bytes[12..]);
+            bytes[4..12].copy_from_slice(&checksum.to_le_bytes());
+            HashTable::from_bytes(&bytes)
+        };
+        assert!(forge(12, 8).is_ok());
+        assert!(matches!(forge(12, 1 << 40), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(forge(20, u64::MAX), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(forge(32, 65), Err(CrustyError::ValidationError(_))));
+        // zero buckets, zero slots per bucket and an empty neighborhood
+        assert!(matches!(forge(12, 0), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(forge(20, 0), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(forge(32, 0), Err(CrustyError::ValidationError(_))));
+        // a load factor that is NaN, zero or negative
+        for load_f in [f64::NAN, 0.0, -0.9] {
+            assert!(matches!(forge(40, load_f.to_bits()), Err(CrustyError::ValidationError(_))));
+        }
+        assert!(forge(40, 0.5f64.to_bits()).is_ok());
+    }
+
+    // function to test read-modify-write through get_mut_or_insert
+    pub fn test_get_mut_or_insert() {
+        let mut table = HashTable::new(
+            10,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        let key = (Field::StringField(String::from("Mark")), Field::IntField(6));
+
+        *table.get_mut_or_insert(key.clone(), 0).unwrap() += 1;
+        assert_eq!(Some(&1), table.get_value((&key.0, &key.1)));
+
+        *table.get_mut_or_insert(key.clone(), 0).unwrap() += 1;
+        assert_eq!(Some(&2), table.get_value((&key.0, &key.1)));
+        assert_eq!(1, table.taken_count.iter().sum::<usize>());
+
+        // a bulk load only stages a missing key, so there is no value to hand out yet
+        table.begin_bulk();
+        let staged = (Field::StringField(String::from("Nina")), Field::IntField(7));
+        assert!(table.get_mut_or_insert(staged.clone(), 0).is_err());
+        *table.get_mut_or_insert(key.clone(), 0).unwrap() += 1;
+        table.end_bulk().unwrap();
+        assert_eq!(Some(&0), table.get_value((&staged.0, &staged.1)));
+        assert_eq!(Some(&3), table.get_value((&key.0, &key.1)));
+    }
+
+    // function to test a duplicate key arriving at a full hopscotch neighborhood
+    pub fn test_hopscotch_duplicate_full() {
+        let mut table = HashTable::new(
+            10,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::Hopscotch,
+            1,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        let name = Field::StringField(String::from("Mark"));
+        let course_taken = Field::IntField(6);
+        table.insert((name.clone(), course_taken.clone()), 1).unwrap();
+        table.insert((name.clone(), course_taken.clone()), 2).unwrap();
+
+        // the neighborhood of size 1 is full, but the key is already in it
+        assert_eq!(10, table.BUCKET_SIZE);
+        assert_eq!(1, table.taken_count.iter().sum::<usize>());
+        assert_eq!(Some(&mut 3), table.get_mut_value((&name, &course_taken)));
+    }
+
+    // function to test splitting a table into sub-tables by key hash
+    pub fn test_split() {
+        let mut table = HashTable::new(
+            8,
+            2,
+            HashFunction::MurmurHash3,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.75,
+        ).unwrap();
+        for i in 0..200 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i % 80)), 1).unwrap();
+        }
+        // a pinned key has to land in its split table too
+        table.insert_into_bucket(1, (Field::StringField(String::from("pinned")), Field::IntField(0)), 3).unwrap();
+        let original: Vec<(Key, usize)> = table.iter()
+            .map(|(key, value)| (key.clone(), *value))
+            .collect();
+        assert_eq!(81, original.len());
+
+        let parts = table.split(4).unwrap();
+        assert_eq!(4, parts.len());
+        let total: usize = parts.iter().map(|part| part.taken_count.iter().sum::<usize>()).sum();
+        assert_eq!(81, total);
+        for part in parts.iter() {
+            assert!(part.check_invariants().is_ok());
+        }
+        for (key, value) in original {
+            let found: Vec<usize> = parts.iter()
+                .filter_map(|part| part.get_value(&key).copied())
+                .collect();
+            assert_eq!(vec![value], found);
+        }
+        assert!(HashTable::with_defaults(4, 1).split(0).is_err());
+
+        // every sub-table spreads its keys over all of its buckets, in about its share of the slots
+        let mut table = HashTable::new(
+            64,
+            8,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        for i in 0..400 {
+            table.insert((Field::IntField(i), Field::IntField(0)), 1).unwrap();
+        }
+        let slots = table.BUCKET_SIZE * table.BUCKET_NUMBER;
+        for part in table.split(4).unwrap() {
+            assert!(part.taken_count.iter().all(|&count| count > 0), "{:?}", part.taken_count);
+            assert!(part.BUCKET_SIZE * part.BUCKET_NUMBER <= slots / 2);
+        }
+    }
+
+    // function to test a bulk load rehashes exactly once
+    pub fn test_bulk_load() {
+        let mut table = HashTable::new(
+            16,
+            4,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        table.begin_bulk();
+        for i in 0..100_000 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        // a repeated key is accumulated when the staged entries land
+        table.insert((Field::StringField(String::from("CS")), Field::IntField(7)), 1).unwrap();
+        assert_eq!(0, table.rehash_count);
+        assert_eq!(0, table.taken_count.iter().sum::<usize>());
+
+        table.end_bulk().unwrap();
+        assert_eq!(1, table.rehash_count);
+        assert_eq!(100_000, table.taken_count.iter().sum::<usize>());
+        assert!(table.check_invariants().is_ok());
+        let key = (Field::StringField(String::from("CS")), Field::IntField(7));
+        assert_eq!(Some(&mut 2), table.get_mut_value((&key.0, &key.1)));
+
+        // inserts after the bulk load go straight into the table again
+        table.insert((Field::StringField(String::from("EE")), Field::IntField(0)), 1).unwrap();
+        assert_eq!(100_001, table.taken_count.iter().sum::<usize>());
+
+        // a staged entry that fails to land stays staged along with the ones after it
+        let full = (Field::StringField(String::from("EE")), Field::IntField(1));
+        let after = (Field::StringField(String::from("EE")), Field::IntField(2));
+        table.insert(full.clone(), usize::MAX).unwrap();
+        table.begin_bulk();
+        table.insert(full.clone(), 1).unwrap();
+        table.insert(after.clone(), 1).unwrap();
+        assert!(matches!(table.end_bulk(), Err(CrustyError::ExecutionError(_))));
+        assert_eq!(Some(2), table.bulk.as_ref().map(Vec::len));
+        assert_eq!(2, table.staged_keys.len());
+        assert_eq!(None, table.get_value((&after.0, &after.1)));
+        table.remove((&full.0, &full.1));
+        table.end_bulk().unwrap();
+        assert_eq!(Some(&1), table.get_value((&full.0, &full.1)));
+        assert_eq!(Some(&1), table.get_value((&after.0, &after.1)));
+
+        // a table without buckets has nowhere to put the keys, which is an error rather than a panic
+        table.buckets.clear();
+        assert!(matches!(table.reserve(1), Err(CrustyError::ExecutionError(_))));
+        assert!(matches!(table.shrink_to_fit(), Err(CrustyError::ExecutionError(_))));
+        assert!(matches!(table.extend(RehashReason::Explicit), Err(CrustyError::ExecutionError(_))));
+        table.begin_bulk();
+        table.bulk.as_mut().unwrap().push((after.into(), 1));
+        assert!(matches!(table.end_bulk(), Err(CrustyError::ExecutionError(_))));
+        assert_eq!(Some(1), table.bulk.as_ref().map(Vec::len));
+    }
+
+    // function to test reporting an upcoming extend
+    pub fn test_should_extend() {
+        let mut table = HashTable::new(
+            10,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        assert!(!table.should_extend());
+
+        // the limit is 9 taken slots, so 8 leaves room for one more insert
+        for i in 0..7 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        assert!(!table.should_extend());
+        table.insert((Field::StringField(String::from("CS")), Field::IntField(7)), 1).unwrap();
+        assert!(table.should_extend());
+        assert_eq!(10, table.BUCKET_SIZE);
+    }
+
+    // function to test doubling the bucket number starting from a single bucket
+    pub fn test_extend_from_one_bucket() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut table = HashTable::new(
+                2,
+                1,
+                HashFunction::FarmHash,
+                scheme,
+                4,
+                ExtendOption::ExtendBucketNumber,
+                0.9,
+            ).unwrap();
+            for i in 0..500 {
+                table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+            }
+            // several doublings happened, with buckets grown just enough to hold two entries
+            assert!(table.BUCKET_NUMBER >= 128);
+            assert!(table.BUCKET_NUMBER.is_power_of_two());
+            let expected_size = if scheme == HashScheme::Hopscotch { 4 } else { 3 };
+            assert_eq!(expected_size, table.BUCKET_SIZE);
+            assert_eq!(500, table.taken_count.iter().sum::<usize>());
+            for i in 0..500 {
+                let key = (Field::StringField(String::from("CS")), Field::IntField(i));
+                assert_eq!(Some(&mut 1), table.get_mut_value((&key.0, &key.1)));
+            }
+        }
+    }
+
+    // function to test reading back the table configuration
+    pub fn test_config() {
+        let table = HashTable::new(
+            10,
+            2,
+            HashFunction::T1haHash,
+            HashScheme::Hopscotch,
+            8,
+            ExtendOption::ExtendBucketNumber,
+            0.75,
+        ).unwrap();
+        let (function, scheme, extend_op, load_factor, h) = table.config();
+        assert!(function == HashFunction::T1haHash);
+        assert!(scheme == HashScheme::Hopscotch);
+        assert!(extend_op == ExtendOption::ExtendBucketNumber);
+        assert_eq!(0.75, load_factor);
+        assert_eq!(8, h);
+
+        // the configuration survives a round trip through bytes
+        let restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+        assert!(restored.config() == table.config());
+    }
+
+    // function to test the adaptive extend picks bucket number for skewed data and bucket size otherwise
+    pub fn test_adaptive_extend() {
+        let new_table = || HashTable::new(
+            10,
+            4,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::Adaptive,
+            0.9,
+        ).unwrap();
+        let candidates: Vec<(Field, Field)> = (0..2000)
+            .map(|i| (Field::StringField(String::from("CS")), Field::IntField(i)))
+            .collect();
+
+        // skewed: every key lands in bucket 0
+        let mut skewed = new_table();
+        let keys: Vec<(Field, Field)> = candidates.iter()
+            .filter(|key| skewed.hash_bucket_index((&key.0, &key.1)) == 0)
+            .take(10)
+            .cloned()
+            .collect();
+        for key in keys {
+            skewed.insert(key, 1).unwrap();
+        }
+        assert_eq!(8, skewed.BUCKET_NUMBER);
+        assert_eq!(10, skewed.BUCKET_SIZE);
+
+        // even: keys are spread round robin over the buckets
+        let mut even = new_table();
+        let mut per_bucket: Vec<Vec<&(Field, Field)>> = vec![vec![]; 4];
+        for key in candidates.iter() {
+            per_bucket[even.hash_bucket_index((&key.0, &key.1))].push(key);
+        }
+        for i in 0..9 {
+            for bucket in per_bucket.iter() {
+                even.insert(bucket[i].clone(), 1).unwrap();
+            }
+        }
+        assert_eq!(10, even.BUCKET_SIZE);
+        even.insert(per_bucket[0][9].clone(), 1).unwrap();
+        assert_eq!(4, even.BUCKET_NUMBER);
+        assert_eq!(20, even.BUCKET_SIZE);
+        assert_eq!(37, even.taken_count.iter().sum::<usize>());
+    }
+
+    // function to test the jaccard similarity of two key sets
+    pub fn test_jaccard() {
+        let new_table = || HashTable::new(
+            10,
+            2,
+            HashFunction::MurmurHash3,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        let mut a = new_table();
+        let mut b = new_table();
+        assert_eq!(1.0, a.jaccard(&b));
+
+        // a holds 0..30 and b holds 20..40, so 10 shared keys out of 40
+        for i in 0..30 {
+            a.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        for i in 20..40 {
+            b.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 3).unwrap();
+        }
+        assert_eq!(0.25, a.jaccard(&b));
+        assert_eq!(0.25, b.jaccard(&a));
+        assert_eq!(1.0, a.jaccard(&a));
+        assert_eq!(0.0, a.jaccard(&new_table()));
+    }
+
+    // function to test probing with a slice of fields
+    pub fn test_get_by_slice() {
+        let mut table = HashTable::new(
+            10,
+            2,
+            HashFunction::FarmHash,
+            HashScheme::RobinHood,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        table.insert((Field::StringField(String::from("Mark")), Field::IntField(6)), 4).unwrap();
+
+        let row = vec![Field::StringField(String::from("Mark")), Field::IntField(6), Field::IntField(2021)];
+        assert_eq!(Some(4), table.get_by_slice(&row[..2]));
+        assert_eq!(None, table.get_by_slice(&row[1..]));
+        // a key of another arity is a different key
+        assert_eq!(None, table.get_by_slice(&row));
+        assert_eq!(None, table.get_by_slice(&row[..1]));
+    }
+
+    // function to test that bucket and slot indexes are independent, using a chi-squared test
+    pub fn test_slot_independence() {
+        let table = HashTable::new(
+            8,
+            4,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        let n = 4000;
+        let mut counts = vec![vec![0.0; 8]; 4];
+        for i in 0..n {
+            let key = (Field::StringField(String::from("CS")), Field::StringField(format!("name{}", i)));
+            counts[table.hash_bucket_index((&key.0, &key.1))][table.home_index((&key.0, &key.1))] += 1.0;
+        }
+        let bucket_totals: Vec<f64> = counts.iter().map(|row| row.iter().sum()).collect();
+        let slot_totals: Vec<f64> = (0..8).map(|j| counts.iter().map(|row| row[j]).sum()).collect();
+        let mut chi_squared = 0.0;
+        for b in 0..4 {
+            for j in 0..8 {
+                let expected = bucket_totals[b] * slot_totals[j] / n as f64;
+                chi_squared += (counts[b][j] - expected).powi(2) / expected;
+            }
+        }
+        // critical value for 21 degrees of freedom at p = 0.001
+        assert!(chi_squared < 46.8, "chi squared {}", chi_squared);
+    }
+
+    // function to test converting counts into a probability distribution
+    pub fn test_to_distribution() {
+        let mut table = HashTable::new(
+            10,
+            2,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        assert!(table.to_distribution().is_empty());
+
+        // counts 1, 2, ..., 10 over 10 keys, 55 in total
+        for i in 1..=10 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), i as usize).unwrap();
+        }
+        let distribution = table.to_distribution();
+        assert_eq!(10, distribution.len());
+        let sum: f64 = distribution.iter().map(|(_, p)| p).sum();
+        assert!((sum - 1.0).abs() < 1e-9);
+        for (key, p) in distribution {
+            let count = key[1].unwrap_int_field() as f64;
+            assert!((p - count / 55.0).abs() < 1e-9);
+        }
+    }
+
+    // function to test combining duplicate keys with a custom merge closure
+    pub fn test_insert_with() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut table = HashTable::new(
+                10,
+                2,
+                HashFunction::FarmHash,
+                scheme,
+                4,
+                ExtendOption::ExtendBucketSize,
+                0.9,
+            ).unwrap();
+            let max = |old: &mut usize, new: usize| *old = std::cmp::max(*old, new);
+            for (i, value) in [3, 9, 4, 7, 1].into_iter().enumerate() {
+                table.insert_with((Field::StringField(String::from("CS")), Field::IntField(0)), value, max).unwrap();
+                table.insert_with((Field::StringField(String::from("CS")), Field::IntField(i as i32 + 1)), value, max).unwrap();
+            }
+            let key = (Field::StringField(String::from("CS")), Field::IntField(0));
+            assert_eq!(Some(&mut 9), table.get_mut_value((&key.0, &key.1)));
+            assert_eq!(6, table.taken_count.iter().sum::<usize>());
+
+            // plain insert still sums
+            table.insert(key.clone(), 1).unwrap();
+            assert_eq!(Some(&mut 10), table.get_mut_value((&key.0, &key.1)));
+        }
+    }
+
+    // function to test the probe path of a linear probe collision
+    pub fn test_probe_path() {
+        let mut table = HashTable::new(
+            10,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        // two keys with the same home slot
+        let keys: Vec<(Field, Field)> = (0..100)
+            .map(|i| (Field::StringField(String::from("CS")), Field::IntField(i)))
+            .collect();
+        let first = keys[0].clone();
+        let home = table.home_index((&first.0, &first.1));
+        let second = keys.iter()
+            .skip(1)
+            .find(|key| table.home_index((&key.0, &key.1)) == home)
+            .unwrap()
+            .clone();
+        assert_eq!(vec![(0, home)], table.probe_path((&first.0, &first.1)));
+
+        table.insert(first.clone(), 1).unwrap();
+        table.insert(second.clone(), 1).unwrap();
+        let next = (home + 1) % 10;
+        assert_eq!(vec![(0, home)], table.probe_path((&first.0, &first.1)));
+        assert_eq!(vec![(0, home), (0, next)], table.probe_path((&second.0, &second.1)));
+        assert_eq!(Some(&mut 1), table.get_mut_value((&second.0, &second.1)));
+    }
+
+    // function to test lookups that report errors instead of panicking
+    pub fn test_safe_get() {
+        let mut table = HashTable::new(
+            10,
+            2,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        let key = (Field::StringField(String::from("Mark")), Field::IntField(6));
+        table.insert(key.clone(), 2).unwrap();
+        assert_eq!(Ok(Some(2)), table.safe_get((&key.0, &key.1)));
+        assert_eq!(Ok(None), table.safe_get((&key.0, &Field::IntField(7))));
+
+        // a key with more or fewer fields than the table's keys, or none at all
+        let oversized = [key.0.clone(), key.1.clone(), Field::StringField("x".repeat(200))];
+        assert!(matches!(table.safe_get(&oversized), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(table.safe_get(std::slice::from_ref(&key.0)), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(table.safe_get(&[] as &[Field]), Err(CrustyError::ValidationError(_))));
+        // inserts hold the table to the same number of fields
+        assert!(table.insert(oversized.to_vec(), 1).is_err());
+        assert_eq!(1, table.len());
+
+        // malformed tables
+        assert!(HashTable::default().safe_get((&key.0, &key.1)).is_err());
+        table.buckets.pop();
+        assert!(table.safe_get((&key.0, &key.1)).is_err());
+    }
+
+    // function to test the bucket occupancy score on uniform and pathological keys
+    pub fn test_distribution_quality() {
+        let new_table = || HashTable::new(
+            100,
+            8,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        let mut uniform = new_table();
+        assert_eq!(0.0, uniform.distribution_quality());
+        for i in 0..400 {
+            uniform.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        assert!(uniform.distribution_quality() < 3.0);
+
+        // with the default combine, equal fields hash to a multiple of 32 and all land in bucket 0
+        let mut pathological = new_table();
+        for i in 0..400 {
+            pathological.insert((Field::IntField(i), Field::IntField(i)), 1).unwrap();
+        }
+        assert!(pathological.distribution_quality() > 100.0);
+    }
+
+    // function to test iterating over the keys only
+    pub fn test_keys() {
+        let mut table = HashTable::new(
+            10,
+            2,
+            HashFunction::FarmHash,
+            HashScheme::RobinHood,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        let mut inserted = std::collections::HashSet::new();
+        for i in 0..50 {
+            let key = (Field::StringField(String::from("CS")), Field::IntField(i % 30));
+            table.insert(key.clone(), 1).unwrap();
+            inserted.insert(Key::from(key));
+        }
+        let keys: std::collections::HashSet<Key> = table.keys().cloned().collect();
+        assert_eq!(30, table.keys().count());
+        assert_eq!(inserted, keys);
+        assert_eq!(50, table.iter().map(|(_, value)| value).sum::<usize>());
+    }
+
+    // function to test every duplicate key policy
+    pub fn test_on_duplicate() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::Hopscotch] {
+            let new_table = |on_duplicate| {
+                let mut table = HashTable::new(
+                    10,
+                    2,
+                    HashFunction::FarmHash,
+                    scheme,
+                    4,
+                    ExtendOption::ExtendBucketSize,
+                    0.9,
+                ).unwrap();
+                table.set_on_duplicate(on_duplicate);
+                table
+            };
+            let key = (Field::StringField(String::from("Mark")), Field::IntField(6));
+
+            let mut accumulate = new_table(OnDuplicate::Accumulate);
+            accumulate.insert(key.clone(), 2).unwrap();
+            accumulate.insert(key.clone(), 5).unwrap();
+            assert_eq!(Some(&mut 7), accumulate.get_mut_value((&key.0, &key.1)));
+            // a sum past usize::MAX is an error and leaves the value alone
+            assert!(matches!(accumulate.insert(key.clone(), usize::MAX), Err(CrustyError::ExecutionError(_))));
+            assert_eq!(Some(&7), accumulate.get_value((&key.0, &key.1)));
+
+            let mut replace = new_table(OnDuplicate::Replace);
+            replace.insert(key.clone(), 2).unwrap();
+            replace.insert(key.clone(), 5).unwrap();
+            assert_eq!(Some(&mut 5), replace.get_mut_value((&key.0, &key.1)));
+
+            let mut keep_first = new_table(OnDuplicate::KeepFirst);
+            keep_first.insert(key.clone(), 2).unwrap();
+            keep_first.insert(key.clone(), 5).unwrap();
+            assert_eq!(Some(&mut 2), keep_first.get_mut_value((&key.0, &key.1)));
+
+            let mut error = new_table(OnDuplicate::Error);
+            assert_eq!(Ok(()), error.insert(key.clone(), 2));
+            assert!(matches!(error.insert(key.clone(), 5), Err(CrustyError::ExecutionError(_))));
+            // a key staged by a bulk load is a duplicate too
+            error.begin_bulk();
+            let staged = (Field::StringField(String::from("Mark")), Field::IntField(7));
+            assert_eq!(Ok(()), error.insert(staged.clone(), 1));
+            assert!(error.insert(staged.clone(), 1).is_err());
+            error.end_bulk().unwrap();
+            assert!(error.staged_keys.is_empty());
+            assert_eq!(Some(&1), error.get_value((&staged.0, &staged.1)));
+            assert_eq!(Some(&mut 2), error.get_mut_value((&key.0, &key.1)));
+            assert_eq!(2, error.taken_count.iter().sum::<usize>());
+        }
+
+        // staged keys are looked up instead of scanned, and a float key in the epsilon cell of a staged one is a duplicate
+        let mut error = HashTable::new(
+            16,
+            4,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        error.set_on_duplicate(OnDuplicate::Error);
+        error.set_float_epsilon(Some(0.01)).unwrap();
+        error.begin_bulk();
+        for i in 0..50_000 {
+            error.insert(Field::FloatField(i as f64), 1).unwrap();
+        }
+        assert!(matches!(error.insert(Field::FloatField(7.001), 1), Err(CrustyError::ExecutionError(_))));
+        // staged keys were hashed by their cell, so the epsilon can't change under them
+        assert!(matches!(error.set_float_epsilon(None), Err(CrustyError::ExecutionError(_))));
+        error.end_bulk().unwrap();
+        assert_eq!(50_000, error.len());
+    }
+
+    // function to test building a table from entries in one call
+    pub fn test_from_entries() {
+        let entries: Vec<(Key, usize)> = (0..5000)
+            .map(|i| ((Field::StringField(String::from("CS")), Field::IntField(i)).into(), i as usize))
+            .collect();
+        for op in [ExtendOption::ExtendBucketSize, ExtendOption::ExtendBucketNumber] {
+            let config = (HashFunction::FarmHash, HashScheme::LinearProbe, op, 0.9, 4);
+            let table = HashTable::from_entries(&entries, config).unwrap();
+            assert_eq!(0, table.rehash_count);
+            assert!(table.config() == config);
+            assert_eq!(5000, table.iter().count());
+            for (key, value) in entries.iter() {
+                assert_eq!(Some(*value), table.get_by_slice(key));
+            }
+            let mut round_trip = table.to_entries();
+            round_trip.sort();
+            assert_eq!(entries, round_trip);
+        }
+        // an entry insert rejects fails the whole build
+        let mixed: Vec<(Key, usize)> = vec![(Field::IntField(1).into(), 1), (vec![Field::IntField(1), Field::IntField(2)].into(), 2)];
+        assert!(HashTable::from_entries(&mixed, (HashFunction::FarmHash, HashScheme::LinearProbe, ExtendOption::ExtendBucketSize, 0.9, 4)).is_err());
+    }
+
+    // function to test that a bucket whose keys never split grows in size instead of number
+    pub fn test_colliding_bucket() {
+        let mut table = HashTable::new(
+            8,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketNumber,
+            0.9,
+        ).unwrap();
+        // equal fields combine to a multiple of 32, so they share a bucket for up to 32 buckets
+        for i in 0..200 {
+            table.insert((Field::IntField(i), Field::IntField(i)), 1).unwrap();
+        }
+        assert!(table.BUCKET_SIZE > 8);
+        assert_eq!(200, table.taken_count.iter().sum::<usize>());
+        for i in 0..200 {
+            assert_eq!(Some(1), table.get_by_slice(&[Field::IntField(i), Field::IntField(i)]));
+        }
+
+        // the minimum bucket size applies as soon as buckets are added
+        let mut table = HashTable::new(
+            4,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketNumber,
+            0.9,
+        ).unwrap();
+        table.set_min_effective_bucket_size(32);
+        for i in 0..10 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        assert_eq!(32, table.BUCKET_SIZE);
+        assert_eq!(10, table.taken_count.iter().sum::<usize>());
+    }
+
+    pub fn test_on_rehash() {
+        let mut table = HashTable::new(
+            10,
+            1,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = events.clone();
+        table.on_rehash(move |event| seen.lock().unwrap().push(event.clone()));
+
+        // the limits are 9 and then 18 taken slots, so 20 keys rehash twice
+        for i in 0..20 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        let events = events.lock().unwrap();
+        assert_eq!(2, events.len());
+        assert_eq!((10, 1), events[0].old_dimensions);
+        assert_eq!((20, 1), events[0].new_dimensions);
+        assert_eq!((20, 1), events[1].old_dimensions);
+        assert_eq!((40, 1), events[1].new_dimensions);
+        assert!(events.iter().all(|e| e.reason == RehashReason::LoadFactor));
+        assert_eq!(2, table.rehash_count);
+        for i in 0..20 {
+            assert_eq!(Some(&1), table.get_value((&Field::StringField(String::from("CS")), &Field::IntField(i))));
+        }
+    }
+
+    pub fn test_tuple_hash() {
+        let key = [Field::IntField(1), Field::StringField(String::from("CS")), Field::IntField(2)];
+        let same = key.clone();
+        assert_eq!(key.farm_hash(), same.farm_hash());
+        assert_eq!(key.metro_hash(), same.metro_hash());
+        // field boundaries and order are part of the hash
+        let shifted = [Field::IntField(1), Field::StringField(String::from("C")), Field::StringField(String::from("S"))];
+        assert_ne!(key.farm_hash(), shifted.farm_hash());
+        let swapped = [Field::IntField(2), Field::StringField(String::from("CS")), Field::IntField(1)];
+        assert_ne!(key.farm_hash(), swapped.farm_hash());
+
+        let table = HashTable::new(
+            10,
+            16,
+            HashFunction::T1haHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        ).unwrap();
+        assert_eq!(table.slice_hash(&key), key.t1ha_hash());
+        assert_ne!(table.slice_hash(&key), table.slice_hash(&key[..2]));
+        let pair = [Field::IntField(1), Field::IntField(2)];
+        assert_eq!(table.slice_hash(&pair), table.key_hash((&pair[0], &pair[1])));
+        // equal floats hash alike inside a composite key too
+        let zero = [Field::FloatField(0.0), Field::IntField(1), Field::IntField(2)];
+        let negative_zero = [Field::FloatField(-0.0), Field::IntField(1), Field::IntField(2)];
+        assert_eq!(table.slice_hash(&zero), table.slice_hash(&negative_zero));
+
+        // 16k composite keys spread evenly over 16 buckets
+        let mut counts = [0usize; 16];
+        for i in 0..16000 {
+            let key = [Field::IntField(i % 7), Field::StringField(i.to_string()), Field::IntField(i / 7)];
+            counts[table.slice_hash(&key) % 16] += 1;
+        }
+        assert!(counts.iter().all(|&c| (800..1200).contains(&c)), "{:?}", counts);
+    }
+
+    pub fn test_approx_distinct() {
+        let mut table = HashTable::with_capacity(
+            100000,
+            (HashFunction::FarmHash, HashScheme::LinearProbe, ExtendOption::ExtendBucketSize, 0.9, 4),
+        ).unwrap();
+        table.insert((Field::IntField(-1), Field::IntField(-1)), 1).unwrap();
+        table.track_distinct();
+        assert_eq!(1, table.approx_distinct());
+
+        for i in 1..100000 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        // duplicates don't move the estimate
+        for i in 1..1000 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        let estimate = table.approx_distinct() as f64;
+        assert!((estimate - 100000.0).abs() < 3000.0, "{}", estimate);
+    }
+
+    pub fn test_matches_std_hashmap() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+        use std::collections::HashMap;
+
+        let schemes = [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch, HashScheme::DoubleHash,
+                       HashScheme::Cuckoo];
+        let functions = [
+            HashFunction::FarmHash,
+            HashFunction::MurmurHash3,
+            HashFunction::T1haHash,
+            HashFunction::StdHash,
+            HashFunction::MetroHash,
+            HashFunction::SeededSip,
+        ];
+        for scheme in schemes {
+            for function in functions {
+                let mut rng = StdRng::seed_from_u64(1478);
+                let mut table = HashTable::new(8, 2, function, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+                let mut reference: HashMap<(Field, Field), usize> = HashMap::new();
+                for _ in 0..2000 {
+                    // a small key space so duplicates and misses are both common
+                    let key = if rng.gen_bool(0.5) {
+                        (Field::IntField(rng.gen_range(0..300)), Field::IntField(rng.gen_range(0..2)))
+                    } else {
+                        (Field::StringField(rng.gen_range(0..300).to_string()), Field::IntField(0))
+                    };
+                    // removes leave tombstones for the keys probed past them
+                    let op = rng.gen_range(0..10);
+                    if op < 5 {
+                        let value = rng.gen_range(1..10);
+                        table.insert(key.clone(), value).unwrap();
+                        *reference.entry(key.clone()).or_insert(0) += value;
+                    } else if op < 8 {
+                        assert_eq!(reference.remove(&key), table.remove((&key.0, &key.1)), "{:?}", key);
+                    }
+                    assert_eq!(reference.get(&key), table.get_value((&key.0, &key.1)), "{:?}", key);
+                    assert_eq!(reference.len(), table.iter().count());
+                }
+                for (key, value) in table.iter() {
+                    assert_eq!(reference.get(&key.clone().into_pair().unwrap()), Some(value));
+                }
+                assert!(table.check_invariants().is_ok(), "{:?} {:?}", scheme, function);
+            }
+        }
+    }
+
+    pub fn test_field_accessors() {
+        let f_int = Field::IntField(1);
+        let f_str = Field::StringField(String::from("Hello"));
+        assert_eq!(Some(1), f_int.as_int());
+        assert_eq!(None, f_int.as_str());
+        assert_eq!(Some("Hello"), f_str.as_str());
+        assert_eq!(None, f_str.as_int());
     }
 
-    // function to test basic functionality of user defined enum
-    pub fn test_my_enum() {
-        let s = HashFunction::FarmHash;
-        match s{
-            HashFunction::MurmurHash3 => { println!("Murmur3") },
-            HashFunction::T1haHash => { println!("T1") },
-            HashFunction::FarmHash => { println!("Farm") },
-            HashFunction::StdHash => { println!("Std") },
-        };
+    pub fn test_membership_only() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut table = HashTable::new(10, 2, HashFunction::FarmHash, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            table.set_membership_only(true);
+            // enough keys to rehash, every key inserted twice
+            for _ in 0..2 {
+                for i in 0..50 {
+                    table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 5).unwrap();
+                }
+            }
+            assert_eq!(50, table.iter().count());
+            for i in 0..50 {
+                assert!(table.contains_key((&Field::StringField(String::from("CS")), &Field::IntField(i))));
+            }
+            assert!(!table.contains_key((&Field::StringField(String::from("CS")), &Field::IntField(50))));
+            assert!(!table.contains_key((&Field::IntField(0), &Field::IntField(0))));
+            assert!(table.iter().all(|(_, value)| *value == 0));
+
+            // no value column, so the same keys in the same slots take less memory than counting them
+            let mut counted = HashTable::new(10, 2, HashFunction::FarmHash, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            for i in 0..50 {
+                counted.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 5).unwrap();
+            }
+            assert_eq!((counted.BUCKET_SIZE, counted.BUCKET_NUMBER), (table.BUCKET_SIZE, table.BUCKET_NUMBER));
+            assert!(table.values.is_empty());
+            assert_eq!(
+                counted.memory_bytes() - table.memory_bytes(),
+                table.BUCKET_SIZE * table.BUCKET_NUMBER * std::mem::size_of::<usize>());
+            assert!(table.check_invariants().is_ok());
+        }
+    }
+
+    pub fn test_extend_bucket_number_values() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut table = HashTable::new(8, 2, HashFunction::MurmurHash3, scheme, 4, ExtendOption::ExtendBucketNumber, 0.9).unwrap();
+            let mut expected = Vec::new();
+            for i in 0..200 {
+                let key = (Field::StringField(i.to_string()), Field::IntField(i));
+                table.insert(key.clone(), i as usize).unwrap();
+                // every third key is accumulated before later inserts rehash it
+                if i % 3 == 0 {
+                    table.insert(key.clone(), 1000).unwrap();
+                    expected.push((key, i as usize + 1000));
+                } else {
+                    expected.push((key, i as usize));
+                }
+            }
+            assert!(table.rehash_count > 0);
+            assert_eq!(expected.len(), table.iter().count());
+            for (key, value) in expected {
+                assert_eq!(Some(&value), table.get_value((&key.0, &key.1)), "{:?}", key);
+            }
+        }
+    }
+
+    pub fn test_insert_into_bucket() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            for op in [ExtendOption::ExtendBucketSize, ExtendOption::ExtendBucketNumber] {
+                let mut table = HashTable::new(8, 4, HashFunction::FarmHash, scheme, 4, op, 0.9).unwrap();
+                // hashed keys around the pinned ones, enough of both to rehash
+                for i in 0..40 {
+                    table.insert_into_bucket(0, (Field::StringField(String::from("tenant")), Field::IntField(i)), i as usize).unwrap();
+                    table.insert((Field::StringField(String::from("other")), Field::IntField(i)), 1).unwrap();
+                }
+                table.insert_into_bucket(0, (Field::StringField(String::from("tenant")), Field::IntField(0)), 5).unwrap();
+                assert!(table.rehash_count > 0);
+                for i in 0..40 {
+                    let key = (Field::StringField(String::from("tenant")), Field::IntField(i));
+                    let expected = if i == 0 { 5 } else { i as usize };
+                    assert_eq!(Some(&expected), table.get_from_bucket(0, (&key.0, &key.1)));
+                    assert!(table.buckets[0].iter().any(|node| node.taken && node.key == key));
+                    let other = (Field::StringField(String::from("other")), Field::IntField(i));
+                    assert_eq!(Some(&1), table.get_value((&other.0, &other.1)));
+                }
+                assert_eq!(80, table.iter().count());
+                let restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+                let key = (Field::StringField(String::from("tenant")), Field::IntField(3));
+                assert_eq!(Some(&3), restored.get_from_bucket(0, (&key.0, &key.1)));
+
+                // a bucket past the last one is rejected and leaves the table alone
+                let buckets = table.BUCKET_NUMBER;
+                assert!(table.insert_into_bucket(buckets, key, 1).is_err());
+                assert_eq!(80, table.iter().count());
+            }
+        }
+    }
+
+    pub fn test_freeze_to_bytes() {
+        let mut table = HashTable::new(10, 4, HashFunction::FarmHash, HashScheme::RobinHood, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        for i in 0..20 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        table.begin_bulk();
+        for i in 20..100 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        // serializing during a bulk load leaves the staged keys out
+        let naive = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+        assert_eq!(20, naive.iter().count());
+
+        let frozen = HashTable::from_bytes(&table.freeze_to_bytes().unwrap()).unwrap();
+        assert_eq!(100, frozen.iter().count());
+        for i in 0..100 {
+            assert_eq!(Some(1), frozen.get_by_slice(&[Field::StringField(String::from("CS")), Field::IntField(i)]));
+        }
+        // the frozen table probes exactly like the live one
+        let total = |t: &HashTable| t.keys().map(|k| t.probe_path(k).len()).sum::<usize>();
+        assert_eq!(total(&table), total(&frozen));
+    }
+
+    pub fn test_summary() {
+        let mut table = HashTable::new(10, 2, HashFunction::MurmurHash3, HashScheme::RobinHood, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        let empty = table.summary();
+        assert_eq!((0, 0.0, 0, 0.0), (empty.live, empty.load_factor, empty.max_probe, empty.mean_probe));
+
+        for i in 0..60 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        let summary = table.summary();
+        let (function, scheme, _, _, _) = table.config();
+        assert_eq!((table.BUCKET_SIZE, table.BUCKET_NUMBER), (summary.bucket_size, summary.bucket_number));
+        assert_eq!((function, scheme), (summary.function, summary.scheme));
+        assert_eq!(table.iter().count(), summary.live);
+        assert_eq!(60.0 / (summary.bucket_size * summary.bucket_number) as f64, summary.load_factor);
+        let probes: Vec<usize> = table.keys().map(|k| table.probe_path(k).len()).collect();
+        assert_eq!(*probes.iter().max().unwrap(), summary.max_probe);
+        assert!(summary.max_probe >= 1 && summary.mean_probe >= 1.0 && summary.mean_probe <= summary.max_probe as f64);
+        assert!(summary.rehash_count > 0);
+        assert_eq!(table.rehash_count, summary.rehash_count);
+        assert_eq!(table.memory_bytes(), summary.memory_bytes);
+        // the string keys are counted on top of the slots
+        assert!(summary.memory_bytes > summary.bucket_size * summary.bucket_number * std::mem::size_of::<HashNode>());
+    }
+
+    pub fn test_prehashed() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut hashed = HashTable::new(8, 2, HashFunction::T1haHash, scheme, 4, ExtendOption::ExtendBucketNumber, 0.9).unwrap();
+            let mut prehashed = HashTable::new(8, 2, HashFunction::T1haHash, scheme, 4, ExtendOption::ExtendBucketNumber, 0.9).unwrap();
+            // a partition with other dimensions shares the key hashes
+            let other = HashTable::new(16, 5, HashFunction::T1haHash, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            for i in 0..100 {
+                let key = (Field::StringField(String::from("CS")), Field::IntField(i % 60));
+                let hash = other.prehash((&key.0, &key.1));
+                hashed.insert(key.clone(), 1).unwrap();
+                prehashed.insert_prehashed(hash, key, 1).unwrap();
+            }
+            assert!(prehashed.rehash_count > 0);
+            for i in 0..70 {
+                let key = (Field::StringField(String::from("CS")), Field::IntField(i));
+                let hash = prehashed.prehash((&key.0, &key.1));
+                let expected = hashed.get_value((&key.0, &key.1)).copied();
+                assert_eq!(expected, prehashed.get_prehashed(hash, (&key.0, &key.1)));
+                assert_eq!(expected, prehashed.get_value((&key.0, &key.1)).copied());
+            }
+            let mut expected = hashed.to_entries();
+            let mut entries = prehashed.to_entries();
+            expected.sort();
+            entries.sort();
+            assert_eq!(expected, entries);
+        }
+    }
+
+    pub fn test_optimize() {
+        let mut table = HashTable::new(64, 4, HashFunction::FarmHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        // skewed: every key shares its first field
+        for i in 0..220 {
+            table.insert((Field::StringField(String::from("hot")), Field::IntField(i)), i as usize).unwrap();
+        }
+        let before = table.summary();
+        table.optimize();
+        let after = table.summary();
+        assert!(after.scheme != HashScheme::LinearProbe);
+        assert!(after.max_probe < before.max_probe);
+        assert!(after.mean_probe <= before.mean_probe);
+        assert_eq!(220, after.live);
+        for i in 0..220 {
+            assert_eq!(Some(&(i as usize)), table.get_value((&Field::StringField(String::from("hot")), &Field::IntField(i))));
+        }
+        // already the best scheme, so nothing is rebuilt
+        let rehashes = table.rehash_count;
+        table.optimize();
+        assert_eq!(rehashes, table.rehash_count);
     }
 
-    // function to test std hash function for Field
-    pub fn test_std_hash() {
-        let f_int = Field::IntField(1);
-        let f_str = Field::StringField(String::from("Hello"));
-        assert_eq!(1742378985846435984 as usize, f_int.std_hash());
-        assert_eq!(12991522711919756218 as usize, f_str.std_hash());
+    pub fn test_float_field() {
+        let f = Field::FloatField(1.5);
+        assert_eq!(1.5, f.unwrap_float_field());
+        assert_eq!(Some(1.5), f.as_float());
+        assert_eq!(None, Field::IntField(1).as_float());
+        assert_eq!("1.5", f.to_string());
+
+        // equal floats hash alike under every function, including both zeros and differently built NaNs
+        let pairs = [
+            (Field::FloatField(0.1 + 0.2), Field::FloatField(0.30000000000000004)),
+            (Field::FloatField(0.0), Field::FloatField(-0.0)),
+            (Field::FloatField(f64::NAN), Field::FloatField(-f64::NAN)),
+        ];
+        for (a, b) in &pairs {
+            assert_eq!(a, b);
+            assert_eq!(a.farm_hash(), b.farm_hash());
+            assert_eq!(a.murmur_hash3(), b.murmur_hash3());
+            assert_eq!(a.t1ha_hash(), b.t1ha_hash());
+            assert_eq!(a.std_hash(), b.std_hash());
+            assert_eq!(a.metro_hash(), b.metro_hash());
+        }
+        assert_ne!(Field::FloatField(1.0), Field::IntField(1));
+        assert!(Field::FloatField(-1.0) < Field::FloatField(-0.0));
+        assert_eq!(Ordering::Equal, Field::FloatField(-0.0).cmp(&Field::FloatField(0.0)));
+
+        let mut table = HashTable::new(10, 16, HashFunction::FarmHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        table.set_allow_nan(true);
+        let unit = Field::StringField(String::from("kg"));
+        table.insert((Field::FloatField(0.0), unit.clone()), 1).unwrap();
+        table.insert((Field::FloatField(-0.0), unit.clone()), 1).unwrap();
+        table.insert((Field::FloatField(f64::NAN), unit.clone()), 1).unwrap();
+        assert_eq!(2, table.iter().count());
+        assert_eq!(table.hash_bucket_index((&Field::FloatField(0.0), &unit)),
+                   table.hash_bucket_index((&Field::FloatField(-0.0), &unit)));
+        assert_eq!(Some(&2), table.get_value((&Field::FloatField(0.0), &unit)));
+        assert_eq!(Some(&1), table.get_value((&Field::FloatField(-f64::NAN), &unit)));
+
+        let restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+        assert_eq!(Some(2), restored.get_by_slice(&[Field::FloatField(-0.0), unit.clone()]));
     }
 
-    // function to test farm hash function for Field
-    pub fn test_farm_hash() {
-        let f_int = Field::IntField(1);
-        let f_str = Field::StringField(String::from("Hello"));
-        let f_str2 = Field::StringField(String::from("There"));
-        let sum = f_int.farm_hash() + f_str2.farm_hash();
-        assert_eq!(538479481099171624 as usize, f_int.farm_hash());
-        assert_eq!(15404698994557526151 as usize, f_str.farm_hash());
+    // function to test NaN keys are rejected by default and match each other once allowed
+    pub fn test_nan_keys() {
+        let unit = Field::StringField(String::from("kg"));
+        let nan = (Field::FloatField(f64::NAN), unit.clone());
+        let mut table = HashTable::new(10, 4, HashFunction::FarmHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        assert!(matches!(table.insert(nan.clone(), 1), Err(CrustyError::ValidationError(_))));
+        assert!(table.insert_with(nan.clone(), 1, |old, new| *old += new).is_err());
+        assert!(table.insert_into_bucket(0, nan.clone(), 1).is_err());
+        assert!(table.safe_get((&nan.0, &nan.1)).is_err());
+        assert!(table.is_empty());
+
+        table.set_allow_nan(true);
+        // NaNs with a different sign and payload are the same key
+        let other_nan = Field::FloatField(f64::from_bits(0xfff8_0000_0000_0001));
+        table.insert(nan.clone(), 1).unwrap();
+        table.insert((other_nan.clone(), unit.clone()), 2).unwrap();
+        assert_eq!(1, table.len());
+        assert_eq!(Some(&3), table.get_value((&Field::FloatField(-f64::NAN), &unit)));
+        assert_eq!(Ok(Some(3)), table.safe_get((&other_nan, &unit)));
+        let (key, _) = table.iter().next().unwrap();
+        assert_eq!(f64::NAN.to_bits(), key[0].unwrap_float_field().to_bits());
     }
 
-    // function to test murmur3 hash function for Field
-    pub fn test_murmur3_hash() {
-        let f_int = Field::IntField(1);
-        let f_str = Field::StringField(String::from("Hello"));
-        assert_eq!(854115492 as usize, f_int.murmur_hash3());
-        assert_eq!(316307400 as usize, f_str.murmur_hash3());
+    pub fn test_string_to_bytes() {
+        for len in [5, 128, 4000] {
+            let field = Field::StringField("k".repeat(len));
+            let bytes = field.to_bytes();
+            assert_eq!(8 + len, bytes.len());
+            assert_eq!(len as u64, u64::from_le_bytes(bytes[..8].try_into().unwrap()));
+
+            let mut buf = Vec::new();
+            write_field(&mut buf, &field);
+            let mut reader = ByteReader::new(&buf);
+            assert_eq!(field, reader.read_field().unwrap());
+            assert!(reader.is_empty());
+
+            let mut table = HashTable::new(10, 2, HashFunction::FarmHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            table.insert((field.clone(), Field::IntField(1)), 3).unwrap();
+            let restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+            assert_eq!(Some(3), restored.get_by_slice(&[field, Field::IntField(1)]));
+        }
     }
 
-    // function to test t1ha function for Field
-    pub fn test_t1ha_hash() {
-        let f_int = Field::IntField(1);
-        let f_str = Field::StringField(String::from("Hello"));
-        assert_eq!(4348539232621042483 as usize, f_int.t1ha_hash());
-        assert_eq!(3284986864571460951 as usize, f_str.t1ha_hash());
+    // function to test lookups follow the scheme's probe sequence, ending where probe_path ends
+    pub fn test_lookup_follows_probe() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch, HashScheme::DoubleHash,
+                       HashScheme::Cuckoo] {
+            let mut table = HashTable::new(8, 4, HashFunction::FarmHash, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            for i in 0..200 {
+                table.insert((Field::IntField(i), Field::IntField(i % 3)), i as usize).unwrap();
+            }
+            for i in 0..200 {
+                let key = (Field::IntField(i), Field::IntField(i % 3));
+                let slot = table.find_slot((&key.0, &key.1)).unwrap();
+                assert_eq!(Some(&slot), table.probe_path((&key.0, &key.1)).last(), "{:?}", scheme);
+                assert_eq!(Some(&(i as usize)), table.get_value((&key.0, &key.1)));
+            }
+            assert!(table.check_invariants().is_ok());
+        }
+
+        // a hopscotch key is only found through the hop info of its home slot
+        let mut table = HashTable::new(8, 1, HashFunction::FarmHash, HashScheme::Hopscotch, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        let key = (Field::StringField(String::from("Adam")), Field::IntField(1));
+        table.insert(key.clone(), 1).unwrap();
+        let home = table.home_index((&key.0, &key.1));
+        assert_eq!(Some(&1), table.get_value((&key.0, &key.1)));
+        table.hop_info[0][home] = 0;
+        assert_eq!(None, table.get_value((&key.0, &key.1)));
+        assert!(table.check_invariants().is_err());
     }
 
-    // function to test initialization and modification of HashNode
-    pub fn test_hash_node() {
-        // init a node object with default
-        let mut node = HashNode::default();
-        assert_eq!((Field::IntField(0), Field::IntField(0)), node.key);
-        assert_eq!(0, node.value);
-        assert_eq!(false, node.taken);
-        assert_eq!(usize::MAX, node.dis);
+    // function to test a removed key's slot is reused while keys probed past it stay reachable
+    pub fn test_remove_tombstone() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood] {
+            let mut table = HashTable::new(16, 1, HashFunction::FarmHash, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            // four keys sharing a home slot, so each probes past the ones before it
+            let key_of = |i: i32| (Field::StringField(String::from("k")), Field::IntField(i));
+            let home = table.home_index((&key_of(0).0, &key_of(0).1));
+            let keys: Vec<(Field, Field)> = (0..).map(key_of)
+                .filter(|key| table.home_index((&key.0, &key.1)) == home)
+                .take(4).collect();
+            let (a, b, c, d) = (&keys[0], &keys[1], &keys[2], &keys[3]);
+            table.insert(a.clone(), 1).unwrap();
+            table.insert(b.clone(), 2).unwrap();
+            table.insert(c.clone(), 3).unwrap();
+            let b_slot = table.find_slot((&b.0, &b.1)).unwrap();
+
+            assert_eq!(Some(2), table.remove((&b.0, &b.1)));
+            assert_eq!(None, table.remove((&b.0, &b.1)));
+            assert_eq!(None, table.get_value((&b.0, &b.1)));
+            assert_eq!(2, table.taken_count[0]);
+            if scheme == HashScheme::RobinHood {
+                // robin hood leaves no tombstone, C shifts back into the freed slot instead
+                assert_eq!(Some(b_slot), table.find_slot((&c.0, &c.1)));
+                assert!(!table.buckets[0].iter().any(|node| node.deleted));
+            }
+            // C sits past the tombstone, inserting it again merges instead of adding a copy
+            table.insert(c.clone(), 10).unwrap();
+            assert_eq!(Some(&13), table.get_value((&c.0, &c.1)));
+            assert_eq!(2, table.taken_count[0]);
+
+            table.insert(d.clone(), 4).unwrap();
+            if scheme == HashScheme::LinearProbe {
+                assert_eq!(Some(b_slot), table.find_slot((&d.0, &d.1)));
+            }
+            assert_eq!(3, table.taken_count[0]);
+            assert_eq!(Some(&1), table.get_value((&a.0, &a.1)));
+            assert_eq!(Some(&13), table.get_value((&c.0, &c.1)));
+            assert!(table.check_invariants().is_ok());
+
+            // tombstones survive serialization, and freezing drops them
+            table.remove((&a.0, &a.1));
+            let mut restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+            restored.insert(c.clone(), 1).unwrap();
+            assert_eq!(Some(&14), restored.get_value((&c.0, &c.1)));
+            HashTable::from_bytes(&table.freeze_to_bytes().unwrap()).unwrap();
+            assert!(!table.buckets[0].iter().any(|node| node.deleted));
+            assert_eq!(2, table.iter().count());
+        }
 
-        let name = Field::StringField(String::from("Mark"));
-        let course_taken = Field::IntField(6);
-        let hash_key = (name, course_taken);
+        // hopscotch drops removed keys from their neighborhood
+        let mut table = HashTable::new(8, 4, HashFunction::FarmHash, HashScheme::Hopscotch, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        for i in 0..100 {
+            table.insert((Field::IntField(i), Field::IntField(i)), i as usize).unwrap();
+        }
+        for i in (0..100).step_by(2) {
+            assert_eq!(Some(i as usize), table.remove((&Field::IntField(i), &Field::IntField(i))));
+        }
+        assert!(table.check_invariants().is_ok());
+        for i in 0..100 {
+            table.insert((Field::IntField(i), Field::IntField(i)), 1).unwrap();
+        }
+        for i in 0..100 {
+            let expected = if i % 2 == 0 { 1 } else { i as usize + 1 };
+            assert_eq!(Some(&expected), table.get_value((&Field::IntField(i), &Field::IntField(i))));
+        }
+        assert_eq!(100, table.iter().count());
+    }
 
-        // modify the node object
-        node = HashNode {key: hash_key, value: 1, taken: true, dis: 0};
-        assert_eq!((Field::StringField(String::from("Mark")), Field::IntField(6)), node.key);
-        assert_eq!(1, node.value);
-        assert_eq!(true, node.taken);
-        assert_eq!(0, node.dis);
+    // function to test the full key hash picks the bucket, so keys spread over many buckets
+    pub fn test_bucket_spread() {
+        for function in [HashFunction::FarmHash, HashFunction::MurmurHash3, HashFunction::T1haHash,
+                         HashFunction::StdHash, HashFunction::MetroHash, HashFunction::SeededSip] {
+            let mut table = HashTable::new(64, 256, function, HashScheme::LinearProbe, 4,
+                                           ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            for i in 0..10000 {
+                table.insert((Field::StringField(format!("name{}", i)), Field::IntField(i)), 1).unwrap();
+            }
+            assert_eq!(256, table.BUCKET_NUMBER);
+            let mean = 10000 / 256;
+            let fullest = table.taken_count.iter().copied().max().unwrap();
+            assert!(fullest <= 3 * mean, "{:?} put {} keys in one bucket", function, fullest);
+            assert!(table.taken_count.iter().all(|&taken| taken > 0));
+        }
     }
 
-    // function to test initialization of HashTable
-    pub fn test_table_new() {
-        let table = HashTable::new(
-            10,
-            2,
-            HashFunction::StdHash,
-            HashScheme::LinearProbe,
-            4,
-            ExtendOption::ExtendBucketSize,
-            0.9,
-        );
-        assert_eq!(2, table.BUCKET_NUMBER);
-        assert_eq!(10, table.BUCKET_SIZE);
-        assert_eq!(vec![0; 2],table.taken_count);
-        assert_eq!(2, table.buckets.len());
-        assert_eq!(10, table.buckets[0].len());
-        assert_eq!(false, table.buckets[0][0].taken);
-        assert_eq!((Field::IntField(0), Field::IntField(0)), table.buckets[0][0].key);
-        assert_eq!(0, table.buckets[0][0].value);
-        assert_eq!(4, table.H);
+    // function to test lookups only need a shared borrow of the table
+    pub fn test_shared_lookups() {
+        let mut table = HashTable::new(4, 2, HashFunction::FarmHash, HashScheme::Hopscotch, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        for i in 0..20 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), i as usize).unwrap();
+        }
+        let first = &table;
+        let second = &table;
+        let key = (Field::StringField(String::from("CS")), Field::IntField(3));
+        let value = first.get_value((&key.0, &key.1));
+        assert!(second.contains_key((&key.0, &key.1)));
+        assert_eq!(Some(&3), value);
+        assert_eq!(second.find_slot((&key.0, &key.1)), first.find_slot((&key.0, &key.1)));
+        assert_eq!(None, second.get_value((&key.0, &Field::IntField(20))));
     }
 
-    // function to test get_bucket_index
-    pub fn test_get_bucket_index() {
-        let table = HashTable::new(
-            10,
-            2,
-            HashFunction::MurmurHash3,
-            HashScheme::LinearProbe,
-            4,
-            ExtendOption::ExtendBucketSize,
-            0.9,
-        );
+    // function to test iterating yields every live entry once, for every scheme
+    pub fn test_iter_entries() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut table = HashTable::new(4, 2, HashFunction::FarmHash, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            for i in 0..5 {
+                table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), i as usize).unwrap();
+            }
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(5)), 1).unwrap();
+            table.remove((&Field::StringField(String::from("CS")), &Field::IntField(5)));
+            let mut entries: Vec<(Key, usize)> = table.iter().map(|(key, value)| (key.clone(), *value)).collect();
+            entries.sort();
+            let expected: Vec<(Key, usize)> = (0..5)
+                .map(|i| ((Field::StringField(String::from("CS")), Field::IntField(i)).into(), i as usize))
+                .collect();
+            assert_eq!(expected, entries);
+
+            for (_, value) in table.iter_mut() {
+                *value *= 10;
+            }
+            assert_eq!(5, table.iter_mut().count());
+            assert_eq!(Some(&40), table.get_value((&Field::StringField(String::from("CS")), &Field::IntField(4))));
+        }
+    }
 
-        let name = Field::StringField(String::from("Mark"));
-        let course_taken = Field::IntField(6);
-        let hash_key = (&name, &course_taken);
-        assert_eq!(table.get_bucket_index(hash_key).unwrap(), 1);
+    // function to test the key count through duplicates, a rehash and removals
+    pub fn test_len() {
+        let mut table = HashTable::new(8, 1, HashFunction::FarmHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        assert!(table.is_empty());
+        for i in 0..6 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i % 3)), 1).unwrap();
+        }
+        assert_eq!(3, table.len());
+        assert_eq!(0, table.rehash_count);
+        for i in 3..8 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        assert!(table.rehash_count > 0);
+        assert_eq!(8, table.len());
+        table.remove((&Field::StringField(String::from("CS")), &Field::IntField(0)));
+        assert_eq!(7, table.len());
+        assert!(!table.is_empty());
     }
 
-    // function to test linear_probe
-    pub fn test_linear_probe() {
-        let mut table = HashTable::new(
-            10,
-            1,
-            HashFunction::StdHash,
-            HashScheme::LinearProbe,
-            4,
-            ExtendOption::ExtendBucketSize,
-            0.9,
-        );
-        table.buckets[0][0].taken = true;
+    // function to test clearing empties the table in place and it can be filled again
+    pub fn test_clear() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut table = HashTable::new(8, 4, HashFunction::FarmHash, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            table.keep_insertion_order();
+            for i in 0..100 {
+                table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+            }
+            let dimensions = (table.BUCKET_SIZE, table.BUCKET_NUMBER);
+            table.clear();
+            assert_eq!(0, table.len());
+            assert!(table.is_empty());
+            assert_eq!(dimensions, (table.BUCKET_SIZE, table.BUCKET_NUMBER));
+            assert_eq!(table.BUCKET_NUMBER, table.buckets.len());
+            assert!(table.buckets.iter().all(|bucket| bucket.len() == table.BUCKET_SIZE));
+            assert!(table.hop_info.iter().flatten().all(|&hop| hop == 0));
+            assert!(table.check_invariants().is_ok());
+            assert_eq!(None, table.get_value((&Field::StringField(String::from("CS")), &Field::IntField(3))));
+            assert_eq!(0, table.iter_ordered().count());
+
+            for i in 0..100 {
+                table.insert((Field::StringField(String::from("EE")), Field::IntField(i)), 2).unwrap();
+            }
+            assert_eq!(100, table.len());
+            assert_eq!(dimensions, (table.BUCKET_SIZE, table.BUCKET_NUMBER));
+            assert_eq!(Some(&2), table.get_value((&Field::StringField(String::from("EE")), &Field::IntField(3))));
+            assert_eq!(100, table.iter_ordered().count());
+        }
+    }
 
-        let name = Field::StringField(String::from("Mark"));
-        let course_taken = Field::IntField(6);
-        assert_eq!(
-            table.linear_probe((&name, &course_taken), 0, 0).unwrap(),
-            1);
+    // function to test keys made of a single field
+    pub fn test_single_field_key() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut table = HashTable::new(8, 4, HashFunction::FarmHash, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            for i in 0..100 {
+                table.insert(Field::IntField(i), 1).unwrap();
+            }
+            table.insert(Field::IntField(3), 2).unwrap();
+            assert_eq!(100, table.len());
+            assert_eq!(Some(&3), table.get_value(&[Field::IntField(3)]));
+            assert_eq!(Some(&1), table.get_value(&Key::from(Field::IntField(99))));
+            assert_eq!(None, table.get_value(&[Field::IntField(100)]));
+            assert_eq!(None, table.get_value((&Field::IntField(3), &Field::IntField(0))));
+            assert!(table.keys().all(|key| key.len() == 1));
+            assert_eq!(Some(1), table.remove(&[Field::IntField(99)]));
+            assert!(table.check_invariants().is_ok());
+        }
+    }
 
-        table.buckets[0][1].key = (name, course_taken);
-        table.buckets[0][1].taken = true;
-        let name = Field::StringField(String::from("Mark"));
-        let course_taken = Field::IntField(6);
-        assert_eq!(
-            table.linear_probe((&name, &course_taken), 0, 0).unwrap(),
-            1);
+    // function to test keys made of three fields
+    pub fn test_three_field_key() {
+        let key_of = |i: i32| Key::new(vec![
+            Field::StringField(String::from("CS")), Field::IntField(i % 10), Field::IntField(i / 10),
+        ]);
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut table = HashTable::new(8, 4, HashFunction::FarmHash, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            for i in 0..200 {
+                table.insert(key_of(i), i as usize).unwrap();
+            }
+            assert_eq!(200, table.len());
+            let lookup = [Field::StringField(String::from("CS")), Field::IntField(7), Field::IntField(4)];
+            assert_eq!(Some(&47), table.get_value(&lookup));
+            assert_eq!(Some(47), table.get_by_slice(&lookup));
+            // the two-field prefix is a different key
+            assert_eq!(None, table.get_value(&lookup[..2]));
+            assert_ne!(table.key_hash(&lookup), table.key_hash(&lookup[..2]));
+            assert!(table.check_invariants().is_ok());
+
+            let restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+            assert_eq!(Some(&47), restored.get_value(&key_of(47)));
+            let mut entries = table.to_entries();
+            let mut restored_entries = restored.to_entries();
+            entries.sort();
+            restored_entries.sort();
+            assert_eq!(entries, restored_entries);
+        }
+    }
 
-        let name2 = Field::StringField(String::from("Jack"));
-        let course_taken2 = Field::IntField(3);
-        table.buckets[0][1].key = (name2, course_taken2);
-        table.buckets[0][1].taken = true;
-        assert_eq!(
-            table.linear_probe((&name, &course_taken), 0, 0).unwrap(),
-            2);
+    // function to test that the SeededSip seed decides where keys land and survives rehashing and serialization
+    pub fn test_seeded_sip() {
+        let new_table = |k0, k1| HashTable::with_seed(8, 64, HashFunction::SeededSip, HashScheme::LinearProbe, 4,
+                                                      ExtendOption::ExtendBucketSize, 0.9, k0, k1).unwrap();
+        let keys: Vec<(Field, Field)> = (0..20)
+            .map(|i| (Field::StringField(String::from("CS")), Field::IntField(i)))
+            .collect();
+        let buckets_of = |table: &HashTable| -> Vec<usize> {
+            keys.iter().map(|key| table.hash_bucket_index((&key.0, &key.1))).collect()
+        };
+        let table = new_table(1, 2);
+        assert_eq!(buckets_of(&table), buckets_of(&new_table(1, 2)));
+        assert_ne!(buckets_of(&table), buckets_of(&new_table(3, 4)));
+        assert_ne!(table.hash_bucket_index((&keys[0].0, &keys[0].1)),
+                   new_table(3, 4).hash_bucket_index((&keys[0].0, &keys[0].1)));
+
+        let mut table = new_table(1, 2);
+        for i in 0..500 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        assert!(table.rehash_count > 0);
+        assert_eq!((1, 2), table.seed);
+        assert!(table.check_invariants().is_ok());
+        let restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+        assert_eq!((1, 2), restored.seed);
+        assert_eq!(Some(&1), restored.get_value((&Field::StringField(String::from("CS")), &Field::IntField(321))));
+        assert!(restored.check_invariants().is_ok());
     }
 
-    // function to test get_index
-    pub fn test_get_indexes() {
-        let mut table = HashTable::new(
-            10,
-            1,
-            HashFunction::FarmHash,
-            HashScheme::LinearProbe,
-            4,
-            ExtendOption::ExtendBucketSize,
-            0.9,
-        );
-        let name = Field::StringField(String::from("Mark"));
-        let course_taken = Field::IntField(6);
+    // function to test that keys colliding on the home slot follow their own double hashing steps
+    pub fn test_double_hash() {
+        let mut table = HashTable::new(16, 1, HashFunction::FarmHash, HashScheme::DoubleHash, 4,
+                                       ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        let key_of = |i: i32| (Field::StringField(String::from("CS")), Field::IntField(i));
+        // three keys sharing a home slot, the last two with different steps
+        let home = table.home_index((&key_of(0).0, &key_of(0).1));
+        let colliding: Vec<i32> = (0..).filter(|i| table.home_index((&key_of(*i).0, &key_of(*i).1)) == home)
+            .take(3).collect();
+        let step = |table: &HashTable, i: i32| table.step_of(table.key_hash((&key_of(i).0, &key_of(i).1)));
+        assert!((0..2000).all(|i| gcd(step(&table, i), 16) == 1));
+        let (first, second) = (colliding[1], colliding[2]);
+        let (first_slot, second_slot) = ((home + step(&table, first)) % 16, (home + step(&table, second)) % 16);
+        assert_ne!(first_slot, second_slot);
+
+        table.insert(key_of(colliding[0]), 1).unwrap();
+        table.insert(key_of(first), 1).unwrap();
+        table.insert(key_of(second), 1).unwrap();
+        // both probe past the shared home slot, each to its own next slot
+        assert_eq!(vec![(0, home), (0, first_slot)], table.probe_path((&key_of(first).0, &key_of(first).1)));
+        assert_eq!(vec![(0, home), (0, second_slot)], table.probe_path((&key_of(second).0, &key_of(second).1)));
+        assert_eq!(key_of(first), table.buckets[0][first_slot].key);
+        assert_eq!(key_of(second), table.buckets[0][second_slot].key);
+
+        // the table stays consistent through extends, removes and serialization
+        for i in 0..1000 {
+            table.insert(key_of(i), 1).unwrap();
+        }
+        for i in (0..1000).step_by(3) {
+            assert!(table.remove((&key_of(i).0, &key_of(i).1)).is_some());
+        }
+        assert!(table.check_invariants().is_ok());
+        let restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+        assert_eq!(HashScheme::DoubleHash, restored.scheme);
+        for i in 0..1000 {
+            let expected = if i % 3 == 0 { None } else if colliding.contains(&i) { Some(&2) } else { Some(&1) };
+            assert_eq!(expected, restored.get_value((&key_of(i).0, &key_of(i).1)));
+        }
+    }
 
-        // table.buckets[0][9].taken = true;
-        // table.buckets[0][0].taken = true;
+    // function to test that a cuckoo insert evicts an occupant to its other slot and lookups check only two slots
+    pub fn test_cuckoo() {
+        let mut table = HashTable::new(8, 1, HashFunction::FarmHash, HashScheme::Cuckoo, 4,
+                                       ExtendOption::ExtendBucketSize, 1.0).unwrap();
+        let key_of = |i: i32| (Field::StringField(String::from("CS")), Field::IntField(i));
+        let slots = |table: &HashTable, i: i32| table.cuckoo_slots(table.key_hash((&key_of(i).0, &key_of(i).1)));
+        // x finds both its slots taken, a is in x's first slot and has a free other slot
+        let x = 0;
+        let (p, q) = slots(&table, x);
+        let a = (1..).find(|&i| slots(&table, i).0 == p && slots(&table, i).1 != q).unwrap();
+        let b = (1..).find(|&i| i != a && slots(&table, i).0 == q && slots(&table, i).1 != slots(&table, a).1).unwrap();
+        table.insert(key_of(a), 1).unwrap();
+        table.insert(key_of(b), 2).unwrap();
+        table.insert(key_of(x), 3).unwrap();
+        assert_eq!(0, table.rehash_count);
+        assert_eq!(key_of(x), table.buckets[0][p].key);
+        assert_eq!(key_of(a), table.buckets[0][slots(&table, a).1].key);
+        assert_eq!(key_of(b), table.buckets[0][q].key);
+        for (i, value) in [(a, 1), (b, 2), (x, 3)] {
+            assert_eq!(Some(&value), table.get_value((&key_of(i).0, &key_of(i).1)));
+            assert!(table.probe_path((&key_of(i).0, &key_of(i).1)).len() <= 2);
+        }
+        assert!(table.check_invariants().is_ok());
 
-        let indexes = table.get_indexes((&name, &course_taken));
-        assert_eq!(0, indexes.unwrap().0);
-        assert_eq!(9, indexes.unwrap().1);
-        assert_eq!(0, indexes.unwrap().2);
+        // long chains through a filling table keep every key in one of its slots
+        for i in 0..2000 {
+            table.insert(key_of(i), 1).unwrap();
+        }
+        assert!(table.check_invariants().is_ok());
+        for i in 0..2000 {
+            let expected = if i == a { 2 } else if i == b { 3 } else if i == x { 4 } else { 1 };
+            assert_eq!(Some(&expected), table.get_value((&key_of(i).0, &key_of(i).1)));
+        }
+        assert!(table.keys().all(|key| table.probe_path(key).len() <= 2));
     }
 
-    // function to test get_mut_value
-    pub fn test_get_mut_value() {
-        let mut table = HashTable::new(
-            10,
-            1,
-            HashFunction::FarmHash,
-            HashScheme::LinearProbe,
-            4,
-            ExtendOption::ExtendBucketSize,
-            0.9,
-        );
+    // function to test that three keys sharing both cuckoo slots make the table grow instead of kicking forever
+    pub fn test_cuckoo_cycle() {
+        let mut table = HashTable::new(4, 1, HashFunction::FarmHash, HashScheme::Cuckoo, 4,
+                                       ExtendOption::ExtendBucketSize, 1.0).unwrap();
+        let key_of = |i: i32| (Field::StringField(String::from("CS")), Field::IntField(i));
+        let pair = |table: &HashTable, i: i32| {
+            let (first, second) = table.cuckoo_slots(table.key_hash((&key_of(i).0, &key_of(i).1)));
+            (first.min(second), first.max(second))
+        };
+        let target = pair(&table, 0);
+        let keys: Vec<i32> = (0..).filter(|&i| pair(&table, i) == target).take(3).collect();
+        for &i in keys.iter() {
+            table.insert(key_of(i), 1).unwrap();
+        }
+        assert!(table.rehash_count > 0);
+        assert!(table.BUCKET_SIZE > 4);
+        for &i in keys.iter() {
+            assert_eq!(Some(&1), table.get_value((&key_of(i).0, &key_of(i).1)));
+        }
+        assert!(table.check_invariants().is_ok());
+    }
 
-        let name = Field::StringField(String::from("Mark"));
-        let course_taken = Field::IntField(6);
-        let indexes = table.get_indexes((&name, &course_taken)).unwrap();
-        table.buckets[indexes.0][indexes.1].key = (name, course_taken);
-        table.buckets[indexes.0][indexes.1].value = 1;
+    // function to test the probe counts of colliding keys in a single bucket
+    pub fn test_probe_stats() {
+        let key_of = |i: i32| (Field::StringField(String::from("CS")), Field::IntField(i));
+        let mut table = HashTable::new(10, 1, HashFunction::FarmHash, HashScheme::LinearProbe, 4,
+                                       ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        let home = table.home_index((&key_of(0).0, &key_of(0).1));
+        let colliding: Vec<i32> = (0..).filter(|&i| table.home_index((&key_of(i).0, &key_of(i).1)) == home)
+            .take(5).collect();
+        for &i in colliding.iter() {
+            table.insert(key_of(i), 1).unwrap();
+        }
+        // the first key finds its home empty, the n-th visits n slots
+        assert_eq!(ProbeStats { total_probes: 2 + 3 + 4 + 5, max_probe: 5, lookups: 4 }, table.probe_stats());
+
+        table.reset_stats();
+        assert_eq!(ProbeStats::default(), table.probe_stats());
+        // a duplicate stops at the slot holding the key
+        table.insert(key_of(colliding[2]), 1).unwrap();
+        assert_eq!(ProbeStats { total_probes: 3, max_probe: 3, lookups: 1 }, table.probe_stats());
+
+        // counts survive a rehash
+        for i in 0..100 {
+            table.insert(key_of(i), 1).unwrap();
+        }
+        assert!(table.rehash_count > 0);
+        let stats = table.probe_stats();
+        assert!(stats.lookups > 1);
+        assert!(stats.total_probes >= stats.lookups);
+    }
 
-        let v = table.get_mut_value(
-            (&Field::StringField(String::from("Mark")), &Field::IntField(6))).unwrap();
-        let expected_v = 1 as usize;
-        assert_eq!(&expected_v, v);
+    // function to test that inserting after reserve doesn't rehash
+    pub fn test_reserve() {
+        // hopscotch and cuckoo can still run out of displacements, so only the probing schemes
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::DoubleHash] {
+            for op in [ExtendOption::ExtendBucketSize, ExtendOption::ExtendBucketNumber] {
+                let mut table = HashTable::new(8, 4, HashFunction::FarmHash, scheme, 4, op, 0.9).unwrap();
+                table.insert((Field::StringField(String::from("EE")), Field::IntField(0)), 1).unwrap();
+                table.reserve(10000).unwrap();
+                assert_eq!(1, table.rehash_count);
+                for i in 0..10000 {
+                    table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+                }
+                assert_eq!(1, table.rehash_count, "{:?}", scheme);
+                assert_eq!(10001, table.len());
+                // room is already there
+                let dimensions = (table.BUCKET_SIZE, table.BUCKET_NUMBER);
+                table.reserve(0).unwrap();
+                assert_eq!(dimensions, (table.BUCKET_SIZE, table.BUCKET_NUMBER));
+                assert!(table.check_invariants().is_ok());
+            }
+        }
     }
 
-    // function to test get_value
-    pub fn test_get_value() {
-        let mut table = HashTable::new(
-            10,
-            1,
-            HashFunction::FarmHash,
-            HashScheme::LinearProbe,
-            4,
-            ExtendOption::ExtendBucketSize,
-            0.9,
-        );
+    // function to test shrinking a grown table after most of its keys are removed
+    pub fn test_shrink_to_fit() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch, HashScheme::DoubleHash] {
+            for op in [ExtendOption::ExtendBucketSize, ExtendOption::ExtendBucketNumber] {
+                let mut table = HashTable::new(8, 4, HashFunction::FarmHash, scheme, 4, op, 0.9).unwrap();
+                for i in 0..5000 {
+                    table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), i as usize).unwrap();
+                }
+                for i in 50..5000 {
+                    table.remove((&Field::StringField(String::from("CS")), &Field::IntField(i)));
+                }
+                let slots = table.BUCKET_SIZE * table.BUCKET_NUMBER;
+                table.shrink_to_fit().unwrap();
+                assert!(table.BUCKET_SIZE * table.BUCKET_NUMBER < slots, "{:?}", scheme);
+                assert_eq!(50, table.len());
+                for i in 0..50 {
+                    assert_eq!(Some(&(i as usize)), table.get_value((&Field::StringField(String::from("CS")), &Field::IntField(i))));
+                }
+                assert!(table.check_invariants().is_ok());
+                // already as small as it gets
+                let dimensions = (table.BUCKET_SIZE, table.BUCKET_NUMBER);
+                let rehashes = table.rehash_count();
+                table.shrink_to_fit().unwrap();
+                assert_eq!(dimensions, (table.BUCKET_SIZE, table.BUCKET_NUMBER));
+                assert_eq!(rehashes, table.rehash_count());
+            }
+        }
+    }
 
-        let name = Field::StringField(String::from("Mark"));
-        let course_taken = Field::IntField(6);
-        let indexes = table.get_indexes((&name, &course_taken)).unwrap();
-        table.buckets[indexes.0][indexes.1].key = (name, course_taken);
-        table.buckets[indexes.0][indexes.1].value = 1;
+    // function to test that insert_many leaves the same entries as inserting one by one
+    pub fn test_insert_many() {
+        // every key shows up three times, so values accumulate
+        let items: Vec<((Field, Field), usize)> = (0..3000)
+            .map(|i| ((Field::StringField(String::from("CS")), Field::IntField(i % 1000)), i as usize))
+            .collect();
+        for op in [ExtendOption::ExtendBucketSize, ExtendOption::ExtendBucketNumber] {
+            let mut looped = HashTable::new(8, 4, HashFunction::FarmHash, HashScheme::RobinHood, 4, op, 0.9).unwrap();
+            for (key, value) in items.clone() {
+                looped.insert(key, value).unwrap();
+            }
+            let mut batched = HashTable::new(8, 4, HashFunction::FarmHash, HashScheme::RobinHood, 4, op, 0.9).unwrap();
+            batched.insert_many(items.clone()).unwrap();
+            // sized once up front
+            assert_eq!(1, batched.rehash_count());
+            let mut expected = looped.to_entries();
+            expected.sort();
+            let mut entries = batched.to_entries();
+            entries.sort();
+            assert_eq!(1000, entries.len());
+            assert_eq!(expected, entries);
+        }
+    }
 
-        let v = table.get_mut_value(
-            (&Field::StringField(String::from("Mark")), &Field::IntField(6))).unwrap();
-        let expected_v = 1 as usize;
-        assert_eq!(&expected_v, v);
+    // function to test that an insert only checks the load of the bucket its key goes to
+    pub fn test_insert_checks_target_bucket() {
+        let mut table = HashTable::new(10, 4, HashFunction::FarmHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        // bucket 0 is at its limit of 9 taken slots
+        for i in 0..9 {
+            table.insert_into_bucket(0, (Field::StringField(String::from("tenant")), Field::IntField(i)), 1).unwrap();
+        }
+        let key = |i: i32| (Field::StringField(String::from("CS")), Field::IntField(i));
+        let bucket = |table: &HashTable, i: i32| table.slice_hash(&[key(i).0, key(i).1]) % table.BUCKET_NUMBER;
+        let others: Vec<i32> = (0..).filter(|&i| bucket(&table, i) != 0).take(5).collect();
+        for &i in others.iter() {
+            table.insert(key(i), 1).unwrap();
+        }
+        assert_eq!(0, table.rehash_count());
+        // a key going to the full bucket extends
+        let full = (0..).find(|&i| bucket(&table, i) == 0).unwrap();
+        table.insert(key(full), 1).unwrap();
+        assert_eq!(1, table.rehash_count());
+        assert_eq!(15, table.len());
     }
 
-    // function to test insert
-    pub fn test_insert() {
-        let mut table = HashTable::new(
-            10,
-            2,
-            HashFunction::T1haHash,
-            HashScheme::LinearProbe,
-            4,
-            ExtendOption::ExtendBucketSize,
-            0.9,
-        );
+    // function to test that the debug output shows the configuration but not the empty slots
+    pub fn test_debug() {
+        let mut table = HashTable::new(100, 5000, HashFunction::MurmurHash3, HashScheme::RobinHood, 4, ExtendOption::ExtendBucketSize, 0.75).unwrap();
+        let empty = format!("{:?}", table);
+        assert!(empty.contains("MurmurHash3"));
+        assert!(empty.contains("load_factor: 0.75"));
+        assert!(empty.contains("combine: MulAdd"));
+        assert!(empty.contains("on_duplicate: Accumulate"));
+        assert!(empty.contains("entries: {}"));
+        for i in 0..20 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        let debug = format!("{:?}", table);
+        assert!(debug.contains("len: 20"));
+        assert!(debug.contains(".."));
+        assert!(debug.len() < 1000);
+    }
 
-        let name1 = Field::StringField(String::from("Mark"));
-        let course_taken1 = Field::IntField(6);
-        let indexes1 = table.get_indexes((&name1, &course_taken1)).unwrap();
+    // function to test the debug output of the configuration enums
+    pub fn test_config_debug() {
+        assert_eq!("FarmHash", format!("{:?}", HashFunction::FarmHash));
+        assert_eq!("MurmurHash3", format!("{:?}", HashFunction::MurmurHash3));
+        assert_eq!("T1haHash", format!("{:?}", HashFunction::T1haHash));
+        assert_eq!("StdHash", format!("{:?}", HashFunction::StdHash));
+        assert_eq!("MetroHash", format!("{:?}", HashFunction::MetroHash));
+        assert_eq!("SeededSip", format!("{:?}", HashFunction::SeededSip));
+        assert_eq!("WyHash", format!("{:?}", HashFunction::WyHash));
+        assert_eq!("LinearProbe", format!("{:?}", HashScheme::LinearProbe));
+        assert_eq!("RobinHood", format!("{:?}", HashScheme::RobinHood));
+        assert_eq!("Hopscotch", format!("{:?}", HashScheme::Hopscotch));
+        assert_eq!("DoubleHash", format!("{:?}", HashScheme::DoubleHash));
+        assert_eq!("Cuckoo", format!("{:?}", HashScheme::Cuckoo));
+        assert_eq!("ExtendBucketSize", format!("{:?}", ExtendOption::ExtendBucketSize));
+        assert_eq!("ExtendBucketNumber", format!("{:?}", ExtendOption::ExtendBucketNumber));
+        assert_eq!("Adaptive", format!("{:?}", ExtendOption::Adaptive));
+        assert_eq!("XorShift", format!("{:?}", CombineStrategy::XorShift));
+        assert_eq!("KeepFirst", format!("{:?}", OnDuplicate::KeepFirst));
+    }
 
-        table.insert((name1, course_taken1), 1);
-        assert_eq!(Field::StringField(String::from("Mark")), table.buckets[indexes1.0][indexes1.1].key.0);
-        assert_eq!(Field::IntField(6), table.buckets[indexes1.0][indexes1.1].key.1);
-        assert_eq!(1, table.buckets[indexes1.0][indexes1.1].value);
-        assert_eq!(true, table.buckets[indexes1.0][indexes1.1].taken);
-        assert_eq!(1, table.taken_count[indexes1.0]);
+    // function to test saving a table to a file and loading it back
+    pub fn test_save_load() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::Hopscotch, HashScheme::Cuckoo] {
+            let mut table = HashTable::new(8, 4, HashFunction::FarmHash, scheme, 4, ExtendOption::ExtendBucketNumber, 0.9).unwrap();
+            for i in 0..500 {
+                table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), i as usize).unwrap();
+            }
+            let path = std::env::temp_dir().join(format!("rust_hash_save_{}_{:?}", std::process::id(), scheme));
+            table.save(&path).unwrap();
+            let loaded = HashTable::load(&path).unwrap();
+            let _ = std::fs::remove_file(&path);
+            assert!(loaded.config() == table.config());
+            assert_eq!(table.taken_count, loaded.taken_count);
+            assert_eq!(table.hop_info, loaded.hop_info);
+            for i in 0..500 {
+                assert_eq!(Some(&(i as usize)), loaded.get_value((&Field::StringField(String::from("CS")), &Field::IntField(i))));
+            }
+        }
+        assert!(HashTable::load(std::env::temp_dir().join("rust_hash_missing_table")).is_err());
+        // a file that isn't a saved table and a table that can't be serialized are errors
+        let path = std::env::temp_dir().join(format!("rust_hash_save_{}_garbage", std::process::id()));
+        std::fs::write(&path, b"RHT1").unwrap();
+        assert!(HashTable::load(&path).is_err());
+        let custom = HashTable::with_custom_hash(8, 4, |_| 0, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9);
+        assert!(custom.save(&path).is_err());
+        let _ = std::fs::remove_file(&path);
+
+        // the serde impls go through the same bytes
+        let mut table = HashTable::new(8, 4, HashFunction::SeededSip, HashScheme::RobinHood, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        for i in 0..50 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), i as usize).unwrap();
+        }
+        let bytes = table.to_bytes().unwrap();
+        let deserializer = serde::de::value::BytesDeserializer::<serde::de::value::Error>::new(&bytes);
+        let restored = HashTable::deserialize(deserializer).unwrap();
+        assert_eq!(Some(&7), restored.get_value((&Field::StringField(String::from("CS")), &Field::IntField(7))));
+        let deserializer = serde::de::value::BytesDeserializer::<serde::de::value::Error>::new(b"RHT1");
+        assert!(HashTable::deserialize(deserializer).is_err());
+    }
 
-        let name1_2 = Field::StringField(String::from("Mark"));
-        let course_taken1_2 = Field::IntField(6);
-        table.insert((name1_2, course_taken1_2), 1);
-        assert_eq!(Field::StringField(String::from("Mark")), table.buckets[indexes1.0][indexes1.1].key.0);
-        assert_eq!(Field::IntField(6), table.buckets[indexes1.0][indexes1.1].key.1);
-        assert_eq!(2, table.buckets[indexes1.0][indexes1.1].value);
-        assert_eq!(true, table.buckets[indexes1.0][indexes1.1].taken);
-        assert_eq!(1, table.taken_count[indexes1.0]);
+    // function to test counting word frequencies through the entry API against inserting one by one
+    pub fn test_entry() {
+        let words = ["the", "cat", "sat", "on", "the", "mat", "the", "end", "cat"];
+        // the value is written through the slot insert reports, which has to follow the key through extends
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch, HashScheme::Cuckoo, HashScheme::DoubleHash] {
+            let mut counted = HashTable::new(2, 1, HashFunction::FarmHash, scheme, 2, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            let mut inserted = HashTable::new(2, 1, HashFunction::FarmHash, scheme, 2, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            for (i, word) in words.iter().cycle().take(900).enumerate() {
+                let key = (Field::StringField(word.to_string()), Field::IntField(i as i32 % 50));
+                *counted.entry(key.clone()).or_insert(0).unwrap() += 1;
+                inserted.insert(key, 1).unwrap();
+            }
+            assert!(counted.rehash_count() > 0);
+            let mut expected = inserted.to_entries();
+            expected.sort();
+            let mut entries = counted.to_entries();
+            entries.sort();
+            assert_eq!(expected, entries);
+        }
+
+        let mut table = HashTable::new(8, 2, HashFunction::FarmHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        let key = (Field::StringField(String::from("CS")), Field::IntField(1));
+        assert!(matches!(table.entry(key.clone()), Entry::Vacant(_)));
+        // and_modify only touches a key that is there
+        assert_eq!(5, *table.entry(key.clone()).and_modify(|v| *v += 1).or_insert(5).unwrap());
+        assert_eq!(7, *table.entry(key.clone()).and_modify(|v| *v += 2).or_insert(5).unwrap());
+        assert_eq!(&Key::from(key.clone()), table.entry(key.clone()).key());
+        assert_eq!(Some(&7), table.get_value((&key.0, &key.1)));
+
+        // a membership-only table has no value to hand out, rather than keeping 0 in place of the default
+        table.set_membership_only(true);
+        let missing = (Field::StringField(String::from("EE")), Field::IntField(2));
+        assert!(matches!(table.entry(missing.clone()).or_insert(5), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(table.get_mut_or_insert(missing.clone(), 5), Err(CrustyError::ValidationError(_))));
+        assert!(!table.contains_key((&missing.0, &missing.1)));
     }
 
-    // function to test robin_hood
-    pub fn test_robin_hood() {
-        let mut table = HashTable::new(
-            10,
-            1,
-            HashFunction::StdHash,
-            HashScheme::RobinHood,
-            4,
-            ExtendOption::ExtendBucketSize,
-            0.9,
-        );
+    // function to test draining the entries out of a table
+    pub fn test_drain() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::Hopscotch] {
+            let mut table = HashTable::new(8, 2, HashFunction::FarmHash, scheme, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            for i in 0..4 {
+                table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), i as usize).unwrap();
+            }
+            let mut drained: Vec<(Key, usize)> = table.drain().collect();
+            drained.sort();
+            let expected: Vec<(Key, usize)> = (0..4)
+                .map(|i| ((Field::StringField(String::from("CS")), Field::IntField(i)).into(), i as usize))
+                .collect();
+            assert_eq!(expected, drained);
+            assert_eq!(0, table.len());
+            assert!(table.hop_info.iter().flatten().all(|&hop| hop == 0));
+            assert!(table.check_invariants().is_ok());
+
+            // stopping early still empties the table, which stays usable
+            for i in 0..4 {
+                table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+            }
+            table.remove((&Field::StringField(String::from("CS")), &Field::IntField(3)));
+            assert_eq!(1, table.drain().take(1).count());
+            assert_eq!(0, table.len());
+            assert!(table.buckets.iter().flatten().all(|node| !node.taken && !node.deleted));
+            assert!(table.check_invariants().is_ok());
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(0)), 1).unwrap();
+            assert_eq!(Some(&1), table.get_value((&Field::StringField(String::from("CS")), &Field::IntField(0))));
+        }
+    }
 
-        // HN1 -> 0
-        let name = Field::StringField(String::from("Adam"));
-        let course_taken = Field::IntField(6);
-        let node = HashNode {key: (name, course_taken), value: 1, taken: true, dis: 0};
-        table.buckets[0][0] = node;
+    // function to test converting a table to a std HashMap and back
+    pub fn test_hashmap_round_trip() {
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch, HashScheme::Cuckoo] {
+            let config = (HashFunction::FarmHash, scheme, ExtendOption::ExtendBucketNumber, 0.9, 4);
+            let mut table = HashTable::new(8, 2, config.0, config.1, config.4, config.2, config.3).unwrap();
+            for i in 0..1000 {
+                table.insert((Field::StringField(String::from("CS")), Field::IntField(i % 300)), i as usize).unwrap();
+            }
+            let mut expected = table.to_entries();
+            expected.sort();
+
+            let map: HashMap<Key, usize> = table.into();
+            assert_eq!(300, map.len());
+            for (key, value) in expected.iter() {
+                assert_eq!(Some(value), map.get(key));
+            }
+            let restored = HashTable::from_hashmap(map, config).unwrap();
+            assert!(restored.config() == config);
+            let mut entries = restored.to_entries();
+            entries.sort();
+            assert_eq!(expected, entries);
+        }
+
+        // pairs convert to keys
+        let mut pairs = HashMap::new();
+        pairs.insert((Field::StringField(String::from("EE")), Field::IntField(1)), 3);
+        let table = HashTable::from_hashmap(pairs, (HashFunction::StdHash, HashScheme::LinearProbe, ExtendOption::ExtendBucketSize, 0.9, 4)).unwrap();
+        assert_eq!(Some(&3), table.get_value((&Field::StringField(String::from("EE")), &Field::IntField(1))));
+    }
 
-        // HN2 -> 0 -> 1
-        let name = Field::StringField(String::from("Ben"));
-        let course_taken = Field::IntField(12);
-        assert_eq!(
-            table.robin_hood((&name, &course_taken), 0, 0).unwrap(),
-            (1 as usize, 1 as usize));
-        let node = HashNode {key: (name, course_taken), value: 1, taken: true, dis: 1};
-        table.buckets[0][1] = node;
+    // function to test binary keys with an interior NUL and bytes that aren't utf8
+    pub fn test_bytes_field() {
+        let blob = Field::BytesField(vec![0xde, 0x00, 0xad, 0xff]);
+        assert_eq!(&[0xde, 0x00, 0xad, 0xff], blob.unwrap_bytes_field());
+        assert_eq!("de00adff", blob.to_string());
+        let mut expected = 4usize.to_le_bytes().to_vec();
+        expected.extend([0xde, 0x00, 0xad, 0xff]);
+        assert_eq!(expected, blob.to_bytes());
+        // a shorter blob differs from its prefix up to the NUL
+        let prefix = Field::BytesField(vec![0xde]);
+        assert_ne!(blob, prefix);
+        assert_ne!(blob.farm_hash(), prefix.farm_hash());
+        assert_ne!(blob.murmur_hash3(), prefix.murmur_hash3());
+        assert_ne!(blob.t1ha_hash(), prefix.t1ha_hash());
+        assert_ne!(blob.std_hash(), prefix.std_hash());
+        assert_ne!(blob.metro_hash(), prefix.metro_hash());
+        assert_ne!(Field::BytesField(b"CS".to_vec()), Field::StringField(String::from("CS")));
+
+        for function in [HashFunction::FarmHash, HashFunction::MurmurHash3, HashFunction::T1haHash, HashFunction::StdHash,
+                         HashFunction::MetroHash, HashFunction::SeededSip] {
+            let mut table = HashTable::new(10, 4, function, HashScheme::RobinHood, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+            table.insert((blob.clone(), Field::IntField(1)), 1).unwrap();
+            table.insert((prefix.clone(), Field::IntField(1)), 2).unwrap();
+            assert_eq!(Some(&1), table.get_value((&Field::BytesField(vec![0xde, 0x00, 0xad, 0xff]), &Field::IntField(1))));
+            assert_eq!(Some(&2), table.get_value((&prefix, &Field::IntField(1))));
+            assert_eq!(None, table.get_value((&Field::BytesField(vec![0xde, 0x00]), &Field::IntField(1))));
+            let restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+            assert_eq!(Some(1), restored.get_by_slice(&[blob.clone(), Field::IntField(1)]));
+        }
+    }
 
-        // HN3 -> 1 -> 2
-        let name = Field::StringField(String::from("Chris"));
-        let course_taken = Field::IntField(1);
-        assert_eq!(
-            table.robin_hood((&name, &course_taken), 0, 1).unwrap(),
-            (2 as usize, 1 as usize));
-        let node = HashNode {key: (name, course_taken), value: 1, taken: true, dis: 1};
-        table.buckets[0][2] = node;
+    // function to test integers past the range of IntField
+    pub fn test_long_field() {
+        let big = i32::MAX as i64 + 1;
+        let long = Field::LongField(big);
+        assert_eq!(big, long.unwrap_long_field());
+        assert_eq!("2147483648", long.to_string());
+        assert_eq!(big.to_le_bytes().to_vec(), long.to_bytes());
+        assert_eq!(farmhash::hash64(&big.to_be_bytes()) as usize, long.farm_hash());
+        // a long doesn't wrap onto the int with the same low bits
+        assert_ne!(long, Field::IntField(i32::MIN));
+        assert_ne!(Field::LongField(7), Field::IntField(7));
+        assert_ne!(long.farm_hash(), Field::LongField(0).farm_hash());
+        assert!(Field::LongField(-big) < Field::LongField(big));
+
+        let mut table = HashTable::new(10, 4, HashFunction::MetroHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        for i in 0..100 {
+            table.insert((Field::LongField(big + i * big), Field::IntField(0)), i as usize).unwrap();
+        }
+        assert_eq!(Some(&3), table.get_value((&Field::LongField(4 * big), &Field::IntField(0))));
+        let restored = HashTable::from_bytes(&table.to_bytes().unwrap()).unwrap();
+        assert_eq!(Some(99), restored.get_by_slice(&[Field::LongField(100 * big), Field::IntField(0)]));
+        let bytes = encode_fields(std::slice::from_ref(&long));
+        let mut reader = ByteReader::new(&bytes);
+        assert_eq!(long, reader.read_field().unwrap());
+    }
 
-        // HN4 -> 0 -> 2
-        let name = Field::StringField(String::from("David"));
-        let course_taken = Field::IntField(3);
-        assert_eq!(
-            table.robin_hood((&name, &course_taken), 0, 0).unwrap(),
-            (2 as usize, 2 as usize));
-        let node = HashNode {key: (name, course_taken), value: 1, taken: true, dis: 2};
-        table.buckets[0][2] = node;
+    // function to test that a custom hash decides placement, through extends, the same way every time
+    pub fn test_custom_hash() {
+        // identity on ints, so a pair (a, b) hashes to a * 31 + b
+        let identity = |field: &Field| match field {
+            Field::IntField(i) => *i as u64,
+            _ => 0,
+        };
+        let build = || {
+            let mut table = HashTable::with_custom_hash(4, 8, identity, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketNumber, 0.9);
+            for i in 0..200 {
+                table.insert((Field::IntField(0), Field::IntField(i)), i as usize).unwrap();
+            }
+            table
+        };
+        let table = build();
+        assert_eq!(HashFunction::Custom, table.config().0);
+        assert!(table.rehash_count() > 0);
+        for i in 0..200 {
+            let key = (Field::IntField(0), Field::IntField(i));
+            assert_eq!(i as usize, table.slice_hash(&[key.0.clone(), key.1.clone()]));
+            assert_eq!(i as usize % table.BUCKET_NUMBER, table.hash_bucket_index((&key.0, &key.1)));
+            assert_eq!(Some(&(i as usize)), table.get_value((&key.0, &key.1)));
+        }
+        let again = build();
+        assert_eq!(table.to_entries(), again.to_entries());
+        assert!(matches!(table.to_bytes(), Err(CrustyError::ValidationError(_))));
+
+        // tables split off the table keep hashing with the closure
+        let config = table.config();
+        let entries = table.to_entries();
+        for part in table.split(2).unwrap() {
+            assert_eq!(HashFunction::Custom, part.config().0);
+            for (key, value) in part.iter() {
+                assert_eq!(*value, part.slice_hash(key));
+            }
+        }
 
-        // HN3 -> 1 -> 3
-        let name = Field::StringField(String::from("Chris"));
-        let course_taken = Field::IntField(1);
-        assert_eq!(
-            table.robin_hood((&name, &course_taken), 0, 1).unwrap(),
-            (3 as usize, 2 as usize));
-        let node = HashNode {key: (name, course_taken), value: 1, taken: true, dis: 2};
-        table.buckets[0][3] = node;
+        // the config alone doesn't carry the closure, so building from it is refused
+        assert!(matches!(HashTable::new(10, 4, HashFunction::Custom, HashScheme::LinearProbe, 4,
+                                        ExtendOption::ExtendBucketSize, 0.9), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(HashTable::with_seed(10, 4, HashFunction::Custom, HashScheme::LinearProbe, 4,
+                                              ExtendOption::ExtendBucketSize, 0.9, 1, 2), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(HashTable::with_capacity(100, config), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(HashTable::from_entries(&entries, config), Err(CrustyError::ValidationError(_))));
+        let map: HashMap<Key, usize> = entries.into_iter().collect();
+        assert!(matches!(HashTable::from_hashmap(map, config), Err(CrustyError::ValidationError(_))));
+    }
+
+    // function to test that the rehash counter reads one per resize
+    pub fn test_rehash_count() {
+        let mut table = HashTable::new(10, 1, HashFunction::FarmHash, HashScheme::LinearProbe, 4, ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        for i in 0..9 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        assert_eq!(0, table.rehash_count());
+        // the limits are 9 and then 18 taken slots, so 20 keys rehash twice
+        for i in 9..20 {
+            table.insert((Field::StringField(String::from("CS")), Field::IntField(i)), 1).unwrap();
+        }
+        assert_eq!(2, table.rehash_count());
+        // lookups and updates don't resize
+        table.insert((Field::StringField(String::from("CS")), Field::IntField(0)), 1).unwrap();
+        assert!(table.contains_key((&Field::StringField(String::from("CS")), &Field::IntField(19))));
+        assert_eq!(2, table.rehash_count());
     }
 
     mod hash {
@@ -1243,6 +6358,16 @@ mod test_hash {
             test_t1ha_hash();
         }
 
+        #[test]
+        fn t_metro_hash() {
+            test_metro_hash();
+        }
+
+        #[test]
+        fn t_wy_hash() {
+            test_wy_hash();
+        }
+
         #[test]
         fn t_hash_node() {
             test_hash_node();
@@ -1253,10 +6378,369 @@ mod test_hash {
             test_table_new();
         }
 
+        #[test]
+        fn t_with_defaults() {
+            test_with_defaults();
+        }
+
         #[test]
         fn t_get_bucket_index() {
             test_get_bucket_index();
         }
 
+        #[test]
+        fn t_table_to_bytes() {
+            test_table_to_bytes();
+        }
+
+        #[test]
+        fn t_insertion_order() {
+            test_insertion_order();
+        }
+
+        #[test]
+        fn t_combine_strategy() {
+            test_combine_strategy();
+        }
+
+        #[test]
+        fn t_longest_chain() {
+            test_longest_chain();
+        }
+
+        #[test]
+        fn t_reduce() {
+            test_reduce();
+        }
+
+        #[test]
+        fn t_into_field() {
+            test_into_field();
+        }
+
+        #[test]
+        fn t_corrupted_bytes() {
+            test_corrupted_bytes();
+        }
+
+        #[test]
+        fn t_bytes_settings() {
+            test_bytes_settings();
+        }
+
+        #[test]
+        fn t_get_mut_or_insert() {
+            test_get_mut_or_insert();
+        }
+
+        #[test]
+        fn t_hopscotch_duplicate_full() {
+            test_hopscotch_duplicate_full();
+        }
+
+        #[test]
+        fn t_split() {
+            test_split();
+        }
+
+        #[test]
+        fn t_bulk_load() {
+            test_bulk_load();
+        }
+
+        #[test]
+        fn t_should_extend() {
+            test_should_extend();
+        }
+
+        #[test]
+        fn t_extend_from_one_bucket() {
+            test_extend_from_one_bucket();
+        }
+
+        #[test]
+        fn t_config() {
+            test_config();
+        }
+
+        #[test]
+        fn t_adaptive_extend() {
+            test_adaptive_extend();
+        }
+
+        #[test]
+        fn t_jaccard() {
+            test_jaccard();
+        }
+
+        #[test]
+        fn t_get_by_slice() {
+            test_get_by_slice();
+        }
+
+        #[test]
+        fn t_slot_independence() {
+            test_slot_independence();
+        }
+
+        #[test]
+        fn t_to_distribution() {
+            test_to_distribution();
+        }
+
+        #[test]
+        fn t_insert_with() {
+            test_insert_with();
+        }
+
+        #[test]
+        fn t_probe_path() {
+            test_probe_path();
+        }
+
+        #[test]
+        fn t_safe_get() {
+            test_safe_get();
+        }
+
+        #[test]
+        fn t_distribution_quality() {
+            test_distribution_quality();
+        }
+
+        #[test]
+        fn t_keys() {
+            test_keys();
+        }
+
+        #[test]
+        fn t_on_duplicate() {
+            test_on_duplicate();
+        }
+
+        #[test]
+        fn t_from_entries() {
+            test_from_entries();
+        }
+
+        #[test]
+        fn t_colliding_bucket() {
+            test_colliding_bucket();
+        }
+
+        #[test]
+        fn t_on_rehash() {
+            test_on_rehash();
+        }
+
+        #[test]
+        fn t_tuple_hash() {
+            test_tuple_hash();
+        }
+
+        #[test]
+        fn t_approx_distinct() {
+            test_approx_distinct();
+        }
+
+        #[test]
+        fn t_matches_std_hashmap() {
+            test_matches_std_hashmap();
+        }
+
+        #[test]
+        fn t_field_accessors() {
+            test_field_accessors();
+        }
+
+        #[test]
+        fn t_membership_only() {
+            test_membership_only();
+        }
+
+        #[test]
+        fn t_extend_bucket_number_values() {
+            test_extend_bucket_number_values();
+        }
+
+        #[test]
+        fn t_insert_into_bucket() {
+            test_insert_into_bucket();
+        }
+
+        #[test]
+        fn t_freeze_to_bytes() {
+            test_freeze_to_bytes();
+        }
+
+        #[test]
+        fn t_summary() {
+            test_summary();
+        }
+
+        #[test]
+        fn t_prehashed() {
+            test_prehashed();
+        }
+
+        #[test]
+        fn t_optimize() {
+            test_optimize();
+        }
+
+        #[test]
+        fn t_float_field() {
+            test_float_field();
+        }
+
+        #[test]
+        fn t_string_to_bytes() {
+            test_string_to_bytes();
+        }
+
+        #[test]
+        fn t_remove_tombstone() {
+            test_remove_tombstone();
+        }
+
+        #[test]
+        fn t_nan_keys() {
+            test_nan_keys();
+        }
+
+        #[test]
+        fn t_lookup_follows_probe() {
+            test_lookup_follows_probe();
+        }
+
+        #[test]
+        fn t_bucket_spread() {
+            test_bucket_spread();
+        }
+
+        #[test]
+        fn t_shared_lookups() {
+            test_shared_lookups();
+        }
+
+        #[test]
+        fn t_iter_entries() {
+            test_iter_entries();
+        }
+
+        #[test]
+        fn t_len() {
+            test_len();
+        }
+
+        #[test]
+        fn t_clear() {
+            test_clear();
+        }
+
+        #[test]
+        fn t_single_field_key() {
+            test_single_field_key();
+        }
+
+        #[test]
+        fn t_three_field_key() {
+            test_three_field_key();
+        }
+
+        #[test]
+        fn t_seeded_sip() {
+            test_seeded_sip();
+        }
+
+        #[test]
+        fn t_double_hash() {
+            test_double_hash();
+        }
+
+        #[test]
+        fn t_cuckoo() {
+            test_cuckoo();
+        }
+
+        #[test]
+        fn t_cuckoo_cycle() {
+            test_cuckoo_cycle();
+        }
+
+        #[test]
+        fn t_probe_stats() {
+            test_probe_stats();
+        }
+
+        #[test]
+        fn t_reserve() {
+            test_reserve();
+        }
+
+        #[test]
+        fn t_rehash_count() {
+            test_rehash_count();
+        }
+
+        #[test]
+        fn t_shrink_to_fit() {
+            test_shrink_to_fit();
+        }
+
+        #[test]
+        fn t_insert_many() {
+            test_insert_many();
+        }
+
+        #[test]
+        fn t_insert_checks_target_bucket() {
+            test_insert_checks_target_bucket();
+        }
+
+        #[test]
+        fn t_debug() {
+            test_debug();
+        }
+
+        #[test]
+        fn t_config_debug() {
+            test_config_debug();
+        }
+
+        #[test]
+        fn t_save_load() {
+            test_save_load();
+        }
+
+        #[test]
+        fn t_entry() {
+            test_entry();
+        }
+
+        #[test]
+        fn t_drain() {
+            test_drain();
+        }
+
+        #[test]
+        fn t_hashmap_round_trip() {
+            test_hashmap_round_trip();
+        }
+
+        #[test]
+        fn t_bytes_field() {
+            test_bytes_field();
+        }
+
+        #[test]
+        fn t_long_field() {
+            test_long_field();
+        }
+
+        #[test]
+        fn t_custom_hash() {
+            test_custom_hash();
+        }
     }
 }
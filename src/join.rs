@@ -1,7 +1,18 @@
-use crate::common::{CrustyError, OpIterator, PredicateOp};
-use crate::hash::{Field, HashTable, HashNode, HashFunction, HashScheme, ExtendOption};
+use crate::common::{CrustyError, JoinedTuple, OpIterator, OuterJoinedTuple, PredicateOp, Schema, Tuple};
+use crate::hash::{Field, HashTable, HashFunction, HashScheme, ExtendOption, ByteReader, write_field};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// number of on-disk partitions the spilled build rows are split into
+const SPILL_PARTITIONS: usize = 4;
+// distinguishes the spill files of joins running in the same process
+static SPILL_ID: AtomicUsize = AtomicUsize::new(0);
 
 // Compares the fields of two tuples using a predicate.
+#[derive(Debug, Clone, Copy)]
 pub struct JoinPredicate {
     left_index: usize,
     right_index: usize,
@@ -18,18 +29,61 @@ impl JoinPredicate {
     }
 }
 
+// function to split a tuple into the field at index and the other field
+fn split_at_column(tuple: &(Field, Field), index: usize) -> (&Field, &Field) {
+    if index == 0 { (&tuple.0, &tuple.1) } else { (&tuple.1, &tuple.0) }
+}
+
+// function to merge a left and a right tuple that share their key column into (key, other left field, other right field)
+fn merge<'a>(left: &'a (Field, Field), right: &'a (Field, Field), predicate: &JoinPredicate) -> (&'a Field, &'a Field, &'a Field) {
+    let (_, left) = split_at_column(left, predicate.left_index);
+    let (key, right) = split_at_column(right, predicate.right_index);
+    (key, left, right)
+}
+
+// function to add a left position to the group of its key, opening a group for a key the table hasn't seen
+fn push_to_group(table: &mut HashTable, groups: &mut Vec<Vec<usize>>, key: &Field, position: usize) -> Result<(), CrustyError> {
+    match table.get_value(std::slice::from_ref(key)) {
+        Some(&group) => groups[group].push(position),
+        None => {
+            table.insert(key.clone(), groups.len())?;
+            groups.push(vec![position]);
+        }
+    }
+    Ok(())
+}
+
 /// Hash equi-join implementation.
+///
+/// The join builds a hash table over the key column of the left child and probes it with the key column
+/// of the right child, see set_predicate. Every match is merged into a JoinedTuple.
 pub struct HashEqJoin {
     open: bool,
     left_child: Vec<(Field,Field)>,
     right_child: Vec<(Field,Field)>,
     join_hash_table: HashTable,
-    current_node: Option<HashNode>,
-    current_bucket: Option<Vec<HashNode>>,
+    exclude_self: bool,
+    built: bool,
+    schema: Option<Schema>,
+    probe_index: usize,
+    spill_threshold_bytes: Option<usize>,
+    // directory the spill files are written to, the system temp directory when None
+    spill_dir: Option<PathBuf>,
+    spilled_rows: usize,
+    expected_results: Option<usize>,
+    predicate: JoinPredicate,
+    // positions of the in-memory left tuples, one group per key in the join table
+    left_groups: Vec<Vec<usize>>,
+    // positions of the spilled left tuples every right tuple matched, empty when nothing spilled
+    spill_matches: Vec<Vec<usize>>,
+    // merged tuples of the last probed right tuple that next hasn't returned yet
+    pending: VecDeque<JoinedTuple>,
 }
 
 impl HashEqJoin {
-    #[allow(dead_code)]
+    // initialize a join over the two children, configuring its hash table like HashTable::new
+    // a join with HashFunction::Custom has no closure to hash with, so building it returns an error
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         l_child: Vec<(Field,Field)>,
         r_child: Vec<(Field,Field)>,
@@ -45,73 +99,496 @@ impl HashEqJoin {
             open: false,
             left_child: l_child,
             right_child: r_child,
-            join_hash_table: HashTable::new(bucket_size, bucket_number, func, sche, h, op, load_f),
-            current_node: None,
-            current_bucket: None,
+            join_hash_table: HashTable::new_unchecked(bucket_size, bucket_number, func, sche, h, op, load_f),
+            exclude_self: false,
+            built: false,
+            schema: None,
+            probe_index: 0,
+            spill_threshold_bytes: None,
+            spill_dir: None,
+            spilled_rows: 0,
+            expected_results: None,
+            predicate: JoinPredicate::new(PredicateOp::Equals, 0, 0),
+            left_groups: Vec::new(),
+            spill_matches: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    // method to choose the columns the join keys on, column 0 of both sides by default
+    // returns an error for a predicate other than Equals or a column past the second, drops a built table
+    pub fn set_predicate(&mut self, predicate: JoinPredicate) -> Result<(), CrustyError> {
+        if !matches!(predicate.op, PredicateOp::Equals) {
+            return Err(CrustyError::ValidationError(String::from("A hash join needs an equality predicate")));
+        }
+        if predicate.left_index > 1 || predicate.right_index > 1 {
+            return Err(CrustyError::ValidationError(String::from("Join column out of tuple bounds")));
+        }
+        self.predicate = predicate;
+        self.join_hash_table = self.empty_table()?;
+        self.built = false;
+        Ok(())
+    }
+
+    // method to skip matches where the right tuple sits at the same position as an identical left tuple
+    pub fn set_exclude_self(&mut self, exclude: bool) {
+        self.exclude_self = exclude;
+    }
+
+    // method to hint how many tuples join returns, so the result is allocated once
+    pub fn set_expected_results(&mut self, expected: usize) {
+        self.expected_results = Some(expected);
+    }
+
+    // method to name the columns of the join output
+    pub fn set_schema(&mut self, schema: Schema) {
+        self.schema = Some(schema);
+    }
+
+    pub fn schema(&self) -> Option<&Schema> {
+        self.schema.as_ref()
+    }
+
+    // method to join and label every output field with its column name
+    pub fn labeled_join(&mut self) -> Result<Vec<Vec<(String, Field)>>, CrustyError> {
+        let schema = self.schema.clone()
+            .ok_or_else(|| CrustyError::ValidationError(String::from("Join has no schema")))?;
+        self.join()?.iter().map(|tuple| schema.label(tuple)).collect()
+    }
+
+    // method to cap the in-memory build side, left tuples past the threshold are spilled to disk
+    pub fn set_spill_threshold_bytes(&mut self, threshold: usize) {
+        self.spill_threshold_bytes = Some(threshold);
+    }
+
+    // method to write the spill files to dir instead of the system temp directory
+    pub fn set_spill_dir(&mut self, dir: PathBuf) {
+        self.spill_dir = Some(dir);
+    }
+
+    // method to match float fields that round to the same multiple of epsilon, see HashTable::set_float_epsilon
+    // returns an error for an epsilon that isn't positive and finite, or once the join table has been built
+    pub fn set_float_epsilon(&mut self, epsilon: Option<f64>) -> Result<(), CrustyError> {
+        self.join_hash_table.set_float_epsilon(epsilon)
+    }
+
+    // method to get how many left tuples the last build spilled to disk
+    pub fn spilled_rows(&self) -> usize {
+        self.spilled_rows
+    }
+
+    // method to build the hash table from the key column of the left child, only done once
+    // returns an error if a spill file can't be written or read back, the next call then builds from scratch
+    fn build(&mut self) -> Result<(), CrustyError> {
+        if self.built {
+            return Ok(());
+        }
+        self.join_hash_table = self.empty_table()?;
+        self.left_groups.clear();
+        self.spill_matches.clear();
+        let mut partitions: Vec<Option<(PathBuf, BufWriter<File>)>> = (0..SPILL_PARTITIONS).map(|_| None).collect();
+        let res = self.build_partitions(&mut partitions);
+        // the spill files are only needed during the build, whether it succeeded or not
+        for (path, writer) in partitions.into_iter().flatten() {
+            drop(writer);
+            let _ = std::fs::remove_file(path);
         }
+        res?;
+        self.built = true;
+        Ok(())
     }
 
-    pub fn join(&mut self) -> Vec<(Field, Field)> {
-        let mut res = Vec::default();
-        for tuple in self.left_child.clone() {
-            self.join_hash_table.insert(tuple, 1);
+    // method to place the left tuples that fit the threshold in the join table, spill the rest into the
+    // partition files and grace join those against the right tuples that hash into them
+    fn build_partitions(&mut self, partitions: &mut [Option<(PathBuf, BufWriter<File>)>]) -> Result<(), CrustyError> {
+        let mut in_memory = 0;
+        self.spilled_rows = 0;
+        for (i, tuple) in self.left_child.iter().enumerate() {
+            let (key, _) = split_at_column(tuple, self.predicate.left_index);
+            let size = tuple.0.to_bytes().len() + tuple.1.to_bytes().len();
+            if self.spill_threshold_bytes.is_some_and(|threshold| in_memory + size > threshold) {
+                let partition = self.join_hash_table.key_hash(std::slice::from_ref(key)) % SPILL_PARTITIONS;
+                let spill = match partitions[partition].take() {
+                    Some(spill) => spill,
+                    None => {
+                        let dir = self.spill_dir.clone().unwrap_or_else(std::env::temp_dir);
+                        let path = dir.join(format!(
+                            "rust_hash_spill_{}_{}_{}",
+                            std::process::id(),
+                            SPILL_ID.fetch_add(1, Ordering::Relaxed),
+                            partition,
+                        ));
+                        let file = File::create(&path)?;
+                        (path, BufWriter::new(file))
+                    }
+                };
+                let (_, writer) = partitions[partition].insert(spill);
+                // a spilled row is its key and its position in the left child
+                let mut buf = Vec::new();
+                write_field(&mut buf, key);
+                write_field(&mut buf, &Field::LongField(i as i64));
+                writer.write_all(&buf)?;
+                self.spilled_rows += 1;
+            } else {
+                in_memory += size;
+                push_to_group(&mut self.join_hash_table, &mut self.left_groups, key, i)?;
+            }
         }
-        for tuple in self.right_child.clone() {
-            if self.join_hash_table.get_value((&tuple.0, &tuple.1)) == Some(&(1 as usize)) {
-                res.push(tuple);
+
+        // grace join the spilled partitions against the right tuples that hash into them
+        self.spill_matches = vec![Vec::new(); if self.spilled_rows > 0 { self.right_child.len() } else { 0 }];
+        for (partition, spilled) in partitions.iter_mut().enumerate() {
+            if let Some((path, writer)) = spilled {
+                writer.flush()?;
+                let bytes = std::fs::read(path)?;
+                let (table, groups) = self.load_partition(&bytes)?;
+                for (i, tuple) in self.right_child.iter().enumerate() {
+                    let (key, _) = split_at_column(tuple, self.predicate.right_index);
+                    if self.join_hash_table.key_hash(std::slice::from_ref(key)) % SPILL_PARTITIONS == partition {
+                        if let Some(&group) = table.get_value(std::slice::from_ref(key)) {
+                            self.spill_matches[i].extend(&groups[group]);
+                        }
+                    }
+                }
             }
         }
-        res
+        Ok(())
+    }
+
+    // method to create an empty hash table configured like the join's
+    // returns an error for HashFunction::Custom, see HashTable::new
+    fn empty_table(&self) -> Result<HashTable, CrustyError> {
+        let (func, sche, op, load_f, h) = self.join_hash_table.config();
+        let mut table = HashTable::new(
+            self.join_hash_table.BUCKET_SIZE,
+            self.join_hash_table.BUCKET_NUMBER,
+            func,
+            sche,
+            h,
+            op,
+            load_f,
+        )?;
+        table.float_epsilon = self.join_hash_table.float_epsilon;
+        Ok(table)
+    }
+
+    // method to create an empty hash table configured like the join's, sized to hold capacity keys
+    #[cfg(feature = "parallel")]
+    fn sized_table(&self, capacity: usize) -> Result<HashTable, CrustyError> {
+        let mut table = HashTable::with_capacity(capacity, self.join_hash_table.config())?;
+        table.float_epsilon = self.join_hash_table.float_epsilon;
+        Ok(table)
+    }
+
+    // method to read a spilled partition back into a hash table configured like the join's, with the
+    // left positions of every key
+    fn load_partition(&self, bytes: &[u8]) -> Result<(HashTable, Vec<Vec<usize>>), CrustyError> {
+        let mut table = self.empty_table()?;
+        let mut groups = Vec::new();
+        let mut reader = ByteReader::new(bytes);
+        while !reader.is_empty() {
+            let key = reader.read_field()?;
+            let position = match reader.read_field()? {
+                Field::LongField(position) => position as usize,
+                field => return Err(CrustyError::ValidationError(format!("Spilled row has position {:?}", field))),
+            };
+            push_to_group(&mut table, &mut groups, &key, position)?;
+        }
+        Ok((table, groups))
+    }
+
+    // method to get the positions of the left tuples the right tuple at position i joins with, in left child order
+    fn matching_left(&self, i: usize) -> Vec<usize> {
+        let (key, _) = split_at_column(&self.right_child[i], self.predicate.right_index);
+        let mut left = match self.join_hash_table.get_value(std::slice::from_ref(key)) {
+            Some(&group) => self.left_groups[group].clone(),
+            None => Vec::new(),
+        };
+        if let Some(spilled) = self.spill_matches.get(i).filter(|spilled| !spilled.is_empty()) {
+            left.extend(spilled);
+            left.sort_unstable();
+        }
+        // the left tuple at the same position doesn't count as a match
+        if self.exclude_self {
+            left.retain(|&j| j != i || self.left_child[j] != self.right_child[i]);
+        }
+        left
+    }
+
+    // method to equi-join on the predicate's columns, building from the left child and probing with the right
+    // every match is merged into (key, other left field, other right field), in right child order
+    // returns an error if the join table can't be built, see build
+    pub fn join(&mut self) -> Result<Vec<JoinedTuple>, CrustyError> {
+        let mut res = Vec::with_capacity(self.expected_results.unwrap_or(self.right_child.len()));
+        self.join_for_each(|(key, left, right)| res.push((key.clone(), left.clone(), right.clone())))?;
+        Ok(res)
+    }
+
+    // method to join with the build and the probe split across the rayon thread pool
+    // the left side is hashed once and partitioned by key hash into one sub-table per thread, sized to its
+    // partition, instead of the join's table and is never spilled, the matches come out in the same order as join
+    // returns an error if a left key can't be placed in its sub-table, or for HashFunction::Custom
+    #[cfg(feature = "parallel")]
+    pub fn par_join(&mut self) -> Result<Vec<JoinedTuple>, CrustyError> {
+        use rayon::prelude::*;
+
+        let threads = rayon::current_num_threads();
+        let (predicate, exclude_self) = (self.predicate, self.exclude_self);
+        let router = self.empty_table()?;
+        let partition_of = |key: &Field| router.key_hash(std::slice::from_ref(key)) % threads;
+        let left_partitions: Vec<usize> = self.left_child.par_iter()
+            .map(|tuple| partition_of(split_at_column(tuple, predicate.left_index).0))
+            .collect();
+        let mut positions = vec![Vec::new(); threads];
+        for (i, &partition) in left_partitions.iter().enumerate() {
+            positions[partition].push(i);
+        }
+        let partitions = positions.into_par_iter()
+            .map(|positions| {
+                let mut table = self.sized_table(positions.len())?;
+                let mut groups = Vec::new();
+                for i in positions {
+                    let (key, _) = split_at_column(&self.left_child[i], predicate.left_index);
+                    push_to_group(&mut table, &mut groups, key, i)?;
+                }
+                Ok((table, groups))
+            })
+            .collect::<Result<Vec<(HashTable, Vec<Vec<usize>>)>, CrustyError>>()?;
+
+        let left_child = &self.left_child;
+        Ok(self.right_child.par_iter()
+            .enumerate()
+            .flat_map_iter(|(i, right)| {
+                let (key, _) = split_at_column(right, predicate.right_index);
+                let (table, groups) = &partitions[partition_of(key)];
+                let group = table.get_value(std::slice::from_ref(key)).map_or(&[][..], |&group| &groups[group]);
+                group.iter()
+                    // the left tuple at the same position doesn't count as a match
+                    .filter(move |&&l| !(exclude_self && l == i && left_child[l] == *right))
+                    .map(move |&l| {
+                        let (key, left, right) = merge(&left_child[l], right, &predicate);
+                        (key.clone(), left.clone(), right.clone())
+                    })
+            })
+            .collect())
+    }
+
+    // method to join and sort the result
+    pub fn join_sorted(&mut self) -> Result<Vec<JoinedTuple>, CrustyError> {
+        let mut res = self.join()?;
+        res.sort();
+        Ok(res)
+    }
+
+    // method to hand every merged tuple to f as it is found, without collecting them
+    pub fn join_for_each(&mut self, mut f: impl FnMut((&Field, &Field, &Field))) -> Result<(), CrustyError> {
+        self.build()?;
+        for i in 0..self.right_child.len() {
+            for j in self.matching_left(i) {
+                f(merge(&self.left_child[j], &self.right_child[i], &self.predicate));
+            }
+        }
+        Ok(())
+    }
+
+    // method to count the merged tuples without collecting them
+    pub fn join_count(&mut self) -> Result<usize, CrustyError> {
+        self.build()?;
+        Ok((0..self.right_child.len()).map(|i| self.matching_left(i).len()).sum())
+    }
+
+    // method to join without cloning, returning references to the fields of every merged tuple
+    pub fn join_refs(&mut self) -> Result<Vec<(&Field, &Field, &Field)>, CrustyError> {
+        self.build()?;
+        let mut res = Vec::new();
+        for i in 0..self.right_child.len() {
+            for j in self.matching_left(i) {
+                res.push(merge(&self.left_child[j], &self.right_child[i], &self.predicate));
+            }
+        }
+        Ok(res)
+    }
+
+    // method to join and pair every left tuple with each right tuple it matched, instead of merging them
+    pub fn join_pairs(&mut self) -> Result<Vec<(Tuple, Tuple)>, CrustyError> {
+        self.build()?;
+        let mut res = Vec::new();
+        for i in 0..self.right_child.len() {
+            for j in self.matching_left(i) {
+                res.push((self.left_child[j].clone(), self.right_child[i].clone()));
+            }
+        }
+        Ok(res)
+    }
+
+    // method to index tuples by their field at index, the table maps a key field to the group holding
+    // the other field of every tuple with it
+    // returns an error for a key the table rejects, such as a NaN float
+    fn build_groups(&self, tuples: &[(Field, Field)], index: usize) -> Result<(HashTable, Vec<Vec<Field>>), CrustyError> {
+        let mut table = self.empty_table()?;
+        let mut groups: Vec<Vec<Field>> = Vec::new();
+        for tuple in tuples.iter() {
+            let (key, value) = split_at_column(tuple, index);
+            match table.get_value(std::slice::from_ref(key)) {
+                Some(&group) => groups[group].push(value.clone()),
+                None => {
+                    table.insert(key.clone(), groups.len())?;
+                    groups.push(vec![value.clone()]);
+                }
+            }
+        }
+        Ok((table, groups))
+    }
+
+    // method to index the right tuples by the predicate's right column, for the joins that probe with the left side
+    fn build_right_groups(&self) -> Result<(HashTable, Vec<Vec<Field>>), CrustyError> {
+        self.build_groups(&self.right_child, self.predicate.right_index)
+    }
+
+    // method to left outer join on the predicate's columns, pairing every left tuple with the other field of each
+    // right tuple that shares its key, or with None when no right tuple does
+    // returns an error if a right key can't be placed in the table, see build_groups
+    pub fn left_join(&mut self) -> Result<Vec<(Field, Field, Option<Field>)>, CrustyError> {
+        let (table, groups) = self.build_right_groups()?;
+        let mut res = Vec::with_capacity(self.left_child.len());
+        for tuple in self.left_child.iter() {
+            let (key, value) = split_at_column(tuple, self.predicate.left_index);
+            match table.get_value(std::slice::from_ref(key)) {
+                Some(&group) => res.extend(groups[group].iter()
+                    .map(|right| (key.clone(), value.clone(), Some(right.clone())))),
+                None => res.push((key.clone(), value.clone(), None)),
+            }
+        }
+        Ok(res)
+    }
+
+    // method to right outer join on the predicate's columns, pairing every right tuple with the other field of each
+    // left tuple that shares its key, or with None when no left tuple does
+    // returns an error if a left key can't be placed in the table, see build_groups
+    pub fn right_join(&mut self) -> Result<Vec<(Field, Option<Field>, Field)>, CrustyError> {
+        let (table, groups) = self.build_groups(&self.left_child, self.predicate.left_index)?;
+        let mut res = Vec::with_capacity(self.right_child.len());
+        for tuple in self.right_child.iter() {
+            let (key, value) = split_at_column(tuple, self.predicate.right_index);
+            match table.get_value(std::slice::from_ref(key)) {
+                Some(&group) => res.extend(groups[group].iter()
+                    .map(|left| (key.clone(), Some(left.clone()), value.clone()))),
+                None => res.push((key.clone(), None, value.clone())),
+            }
+        }
+        Ok(res)
+    }
+
+    // method to full outer join on the predicate's columns, the rows of left_join followed by every right tuple
+    // no left tuple shares its key with, paired with None
+    // returns an error if a right key can't be placed in the table, see build_groups
+    pub fn full_join(&mut self) -> Result<Vec<OuterJoinedTuple>, CrustyError> {
+        let (table, groups) = self.build_right_groups()?;
+        // right groups some left tuple already emitted
+        let mut matched = vec![false; groups.len()];
+        let mut res = Vec::with_capacity(self.left_child.len() + self.right_child.len());
+        for tuple in self.left_child.iter() {
+            let (key, value) = split_at_column(tuple, self.predicate.left_index);
+            match table.get_value(std::slice::from_ref(key)) {
+                Some(&group) => {
+                    matched[group] = true;
+                    res.extend(groups[group].iter()
+                        .map(|right| (key.clone(), Some(value.clone()), Some(right.clone()))));
+                },
+                None => res.push((key.clone(), Some(value.clone()), None)),
+            }
+        }
+        for tuple in self.right_child.iter() {
+            let (key, value) = split_at_column(tuple, self.predicate.right_index);
+            let &group = table.get_value(std::slice::from_ref(key))
+                .ok_or_else(|| CrustyError::ExecutionError(format!("Right key {} has no group", key)))?;
+            if !matched[group] {
+                res.push((key.clone(), None, Some(value.clone())));
+            }
+        }
+        Ok(res)
+    }
+
+    // method to keep the left tuples whose key appears in some right tuple, each at most once
+    // returns an error if a right key can't be placed in the table, see build_groups
+    pub fn semi_join(&mut self) -> Result<Vec<(Field, Field)>, CrustyError> {
+        let (table, _) = self.build_right_groups()?;
+        Ok(self.left_child.iter()
+            .filter(|tuple| table.contains_key(std::slice::from_ref(split_at_column(tuple, self.predicate.left_index).0)))
+            .cloned()
+            .collect())
+    }
+
+    // method to keep the left tuples whose key appears in no right tuple
+    // returns an error if a right key can't be placed in the table, see build_groups
+    pub fn anti_join(&mut self) -> Result<Vec<(Field, Field)>, CrustyError> {
+        let (table, _) = self.build_right_groups()?;
+        Ok(self.left_child.iter()
+            .filter(|tuple| !table.contains_key(std::slice::from_ref(split_at_column(tuple, self.predicate.left_index).0)))
+            .cloned()
+            .collect())
+    }
+
+    // method to stop the join after its first n output rows, without probing the rest of the right side
+    pub fn limit(self, n: usize) -> Limit<Self> {
+        Limit::new(self, n)
+    }
+
+    // method to run the join and scan its result as an operator, so joins can be chained
+    pub fn join_as_iterator(&mut self) -> Result<impl OpIterator<Item = JoinedTuple>, CrustyError> {
+        Ok(TupleScan::new(self.join()?))
     }
 }
 
-impl OpIterator for HashEqJoin {
+/// Scan over a materialized vector of tuples, e.g. the result of a join.
+pub struct TupleScan<T = Tuple> {
+    open: bool,
+    tuples: Vec<T>,
+    index: usize,
+    schema: Option<Schema>,
+}
+
+impl<T> TupleScan<T> {
+    pub fn new(tuples: Vec<T>) -> Self {
+        Self {
+            open: false,
+            tuples,
+            index: 0,
+            schema: None,
+        }
+    }
+
+    // method to name the columns of the scanned tuples
+    pub fn set_schema(&mut self, schema: Schema) {
+        self.schema = Some(schema);
+    }
+
+    pub fn schema(&self) -> Option<&Schema> {
+        self.schema.as_ref()
+    }
+}
+
+impl<T: Clone> OpIterator for TupleScan<T> {
+    type Item = T;
+
     fn open(&mut self) -> Result<(), CrustyError> {
-        // self.left_child.open()?;
-        // let field_num = self.predicate.left_index();
-        // while let Some(t) = self.left_child.next()? {
-        //     let field = t.get_field(field_num).unwrap();
-        //     let entry = self.join_map.entry(field.clone()).or_insert_with(Vec::new);
-        //     entry.push(t);
-        // }
-        // self.left_child.close()?;
-        // self.right_child.open()?;
-        // self.current_tuple = self.right_child.next()?;
-        // self.open = true;
+        self.index = 0;
+        self.open = true;
         Ok(())
     }
 
-    fn next(&mut self) -> Result<Option<HashNode>, CrustyError> {
-//         if !self.open {
-//             panic!("Operator has not been opened")
-//         }
-//
-        let mut res = None;
-//         while let Some(t2) = &self.current_tuple {
-//             let field = t2.get_field(self.predicate.right_index()).unwrap();
-//             if let Some(tuples) = self.join_map.get(field) {
-//                 if self.current_bucket.is_none() {
-//                     let mut ti =
-//                         TupleIterator::new(tuples.to_vec(), self.left_child.get_schema().clone());
-//                     ti.open()?;
-//                     self.current_bucket = Some(ti);
-//                 }
-//                 if let Some(t1) = self.current_bucket.as_mut().unwrap().next()? {
-//                     res = Some(t1.merge(t2));
-//                     break;
-//                 }
-//                 self.current_bucket = None;
-//             }
-//             self.current_tuple = self.right_child.next()?;
-//         }
+    fn next(&mut self) -> Result<Option<T>, CrustyError> {
+        if !self.open {
+            panic!("Operator has not been opened")
+        }
+        let res = self.tuples.get(self.index).cloned();
+        if res.is_some() {
+            self.index += 1;
+        }
         Ok(res)
     }
 
     fn close(&mut self) -> Result<(), CrustyError> {
-        self.right_child = Vec::default();
-        self.join_hash_table = HashTable::default();
-        self.current_node = None;
-        self.current_bucket = None;
         self.open = false;
         Ok(())
     }
@@ -120,16 +597,135 @@ impl OpIterator for HashEqJoin {
         if !self.open {
             panic!("Operator has not been opened")
         }
-        self.close()?;
-        self.open()
+        self.index = 0;
+        Ok(())
+    }
+}
+
+/// Opens the operator, pulls every tuple out of it and closes it again.
+pub fn collect_tuples<O: OpIterator>(op: &mut O) -> Result<Vec<O::Item>, CrustyError> {
+    let mut res = Vec::new();
+    op.open()?;
+    while let Some(tuple) = op.next()? {
+        res.push(tuple);
+    }
+    op.close()?;
+    Ok(res)
+}
+
+impl OpIterator for HashEqJoin {
+    type Item = JoinedTuple;
+
+    fn open(&mut self) -> Result<(), CrustyError> {
+        self.build()?;
+        self.probe_index = 0;
+        self.pending.clear();
+        self.open = true;
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<Option<JoinedTuple>, CrustyError> {
+        if !self.open {
+            panic!("Operator has not been opened")
+        }
+        // probe lazily, only as far as the next right tuple with a match
+        while self.pending.is_empty() && self.probe_index < self.right_child.len() {
+            let i = self.probe_index;
+            self.probe_index += 1;
+            for j in self.matching_left(i) {
+                let (key, left, right) = merge(&self.left_child[j], &self.right_child[i], &self.predicate);
+                self.pending.push_back((key.clone(), left.clone(), right.clone()));
+            }
+        }
+        Ok(self.pending.pop_front())
+    }
+
+    fn close(&mut self) -> Result<(), CrustyError> {
+        // release the built table but keep its configuration, so opening again rebuilds it from the inputs
+        self.join_hash_table = self.empty_table()?;
+        self.left_groups.clear();
+        self.spill_matches.clear();
+        self.pending.clear();
+        self.built = false;
+        self.open = false;
+        Ok(())
+    }
+
+    fn rewind(&mut self) -> Result<(), CrustyError> {
+        if !self.open {
+            panic!("Operator has not been opened")
+        }
+        // the hash table is already built, only the probe starts over
+        self.probe_index = 0;
+        self.pending.clear();
+        Ok(())
     }
 
 }
 
+/// Passes through the first n tuples of its child, then stops pulling from it.
+pub struct Limit<C: OpIterator> {
+    open: bool,
+    child: C,
+    limit: usize,
+    produced: usize,
+}
+
+impl<C: OpIterator> Limit<C> {
+    pub fn new(child: C, limit: usize) -> Self {
+        Self {
+            open: false,
+            child,
+            limit,
+            produced: 0,
+        }
+    }
+}
+
+impl<C: OpIterator> OpIterator for Limit<C> {
+    type Item = C::Item;
+
+    fn open(&mut self) -> Result<(), CrustyError> {
+        self.child.open()?;
+        self.produced = 0;
+        self.open = true;
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<Option<C::Item>, CrustyError> {
+        if !self.open {
+            panic!("Operator has not been opened")
+        }
+        if self.produced >= self.limit {
+            return Ok(None);
+        }
+        let res = self.child.next()?;
+        if res.is_some() {
+            self.produced += 1;
+        }
+        Ok(res)
+    }
+
+    fn close(&mut self) -> Result<(), CrustyError> {
+        self.open = false;
+        self.child.close()
+    }
+
+    fn rewind(&mut self) -> Result<(), CrustyError> {
+        if !self.open {
+            panic!("Operator has not been opened")
+        }
+        self.produced = 0;
+        self.child.rewind()
+    }
+}
+
 #[cfg(test)]
 mod test_join {
     use rand::distributions::Alphanumeric;
     use rand::Rng;
+    use crate::common::Row;
+    use crate::common::test_common::assert_opiterator_contract;
     use super::*;
 
     /// Creates a Vec of (StringField, StringField) given a Vec of (&str, &str) 's
@@ -152,9 +748,9 @@ mod test_join {
                 .take(7)
                 .map(char::from)
                 .collect();
-            // use fixed "CS" as "department"
-            let fields = (Field::StringField(String::from("CS")),
-                          Field::StringField(s));
+            // use fixed "CS" as "department", after the name since joins key on the first field
+            let fields = (Field::StringField(s),
+                          Field::StringField(String::from("CS")));
             tuples.push(fields);
         }
         tuples
@@ -166,8 +762,8 @@ mod test_join {
             vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "David")]);
         let r_child = create_vec_tuple(
             vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Eva"), ("CS", "Fordham")]);
-        let b_number = 2 as usize;
-        let b_size = 10 as usize;
+        let b_number = 2_usize;
+        let b_size = 10_usize;
         let h_e_join = HashEqJoin::new(
             l_child,
             r_child,
@@ -179,7 +775,7 @@ mod test_join {
             ExtendOption::ExtendBucketSize,
             0.9,
         );
-        assert_eq!(h_e_join.open, false);
+        assert!(!h_e_join.open);
         assert_eq!(h_e_join.left_child.len(), 4);
         assert_eq!(h_e_join.right_child.len(), 4);
     }
@@ -190,8 +786,8 @@ mod test_join {
             vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "David")]);
         let r_child = create_vec_tuple(
             vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "Eva"), ("CS", "Fordham")]);
-        let b_number = 2 as usize;
-        let b_size = 10 as usize;
+        let b_number = 2_usize;
+        let b_size = 10_usize;
         let mut h_e_join = HashEqJoin::new(
             l_child,
             r_child,
@@ -203,14 +799,16 @@ mod test_join {
             ExtendOption::ExtendBucketSize,
             0.9,
         );
-        let res_farm = h_e_join.join();
+        // key on the name, every department matches every other
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 1)).unwrap();
+        let res_farm = h_e_join.join().unwrap();
 
         let dep = Field::StringField(String::from("CS"));
 
         assert_eq!(res_farm.len(), 3);
-        assert_eq!(res_farm[0], (dep.clone(), Field::StringField(String::from("Adam"))));
-        assert_eq!(res_farm[1], (dep.clone(), Field::StringField(String::from("Ben"))));
-        assert_eq!(res_farm[2], (dep.clone(), Field::StringField(String::from("Chris"))));
+        assert_eq!(res_farm[0], (Field::StringField(String::from("Adam")), dep.clone(), dep.clone()));
+        assert_eq!(res_farm[1], (Field::StringField(String::from("Ben")), dep.clone(), dep.clone()));
+        assert_eq!(res_farm[2], (Field::StringField(String::from("Chris")), dep.clone(), dep.clone()));
 
     }
 
@@ -220,8 +818,8 @@ mod test_join {
             vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "David")]);
         let r_child = create_vec_tuple(
             vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "Eva"), ("CS", "Fordham")]);
-        let b_number = 2 as usize;
-        let b_size = 10 as usize;
+        let b_number = 2_usize;
+        let b_size = 10_usize;
         let mut h_e_join = HashEqJoin::new(
             l_child,
             r_child,
@@ -234,14 +832,16 @@ mod test_join {
             0.9,
         );
 
-        let res_murmur = h_e_join.join();
+        // key on the name, every department matches every other
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 1)).unwrap();
+        let res_murmur = h_e_join.join().unwrap();
 
         let dep = Field::StringField(String::from("CS"));
 
         assert_eq!(res_murmur.len(), 3);
-        assert_eq!(res_murmur[0], (dep.clone(), Field::StringField(String::from("Adam"))));
-        assert_eq!(res_murmur[1], (dep.clone(), Field::StringField(String::from("Ben"))));
-        assert_eq!(res_murmur[2], (dep.clone(), Field::StringField(String::from("Chris"))));
+        assert_eq!(res_murmur[0], (Field::StringField(String::from("Adam")), dep.clone(), dep.clone()));
+        assert_eq!(res_murmur[1], (Field::StringField(String::from("Ben")), dep.clone(), dep.clone()));
+        assert_eq!(res_murmur[2], (Field::StringField(String::from("Chris")), dep.clone(), dep.clone()));
     }
 
     // function to test join a HashEqJoin using std::hash
@@ -250,8 +850,8 @@ mod test_join {
             vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "David")]);
         let r_child = create_vec_tuple(
             vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "Eva"), ("CS", "Fordham")]);
-        let b_number = 2 as usize;
-        let b_size = 10 as usize;
+        let b_number = 2_usize;
+        let b_size = 10_usize;
         let mut h_e_join = HashEqJoin::new(
             l_child,
             r_child,
@@ -264,14 +864,16 @@ mod test_join {
             0.9,
         );
 
-        let res_std = h_e_join.join();
+        // key on the name, every department matches every other
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 1)).unwrap();
+        let res_std = h_e_join.join().unwrap();
 
         let dep = Field::StringField(String::from("CS"));
 
         assert_eq!(res_std.len(), 3);
-        assert_eq!(res_std[0], (dep.clone(), Field::StringField(String::from("Adam"))));
-        assert_eq!(res_std[1], (dep.clone(), Field::StringField(String::from("Ben"))));
-        assert_eq!(res_std[2], (dep.clone(), Field::StringField(String::from("Chris"))));
+        assert_eq!(res_std[0], (Field::StringField(String::from("Adam")), dep.clone(), dep.clone()));
+        assert_eq!(res_std[1], (Field::StringField(String::from("Ben")), dep.clone(), dep.clone()));
+        assert_eq!(res_std[2], (Field::StringField(String::from("Chris")), dep.clone(), dep.clone()));
 
     }
 
@@ -281,8 +883,8 @@ mod test_join {
             vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "David")]);
         let r_child = create_vec_tuple(
             vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "Eva"), ("CS", "Fordham")]);
-        let b_number = 2 as usize;
-        let b_size = 10 as usize;
+        let b_number = 2_usize;
+        let b_size = 10_usize;
         let mut h_e_join = HashEqJoin::new(
             l_child,
             r_child,
@@ -295,14 +897,16 @@ mod test_join {
             0.9,
         );
 
-        let res_t1ha = h_e_join.join();
+        // key on the name, every department matches every other
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 1)).unwrap();
+        let res_t1ha = h_e_join.join().unwrap();
 
         let dep = Field::StringField(String::from("CS"));
 
         assert_eq!(res_t1ha.len(), 3);
-        assert_eq!(res_t1ha[0], (dep.clone(), Field::StringField(String::from("Adam"))));
-        assert_eq!(res_t1ha[1], (dep.clone(), Field::StringField(String::from("Ben"))));
-        assert_eq!(res_t1ha[2], (dep.clone(), Field::StringField(String::from("Chris"))));
+        assert_eq!(res_t1ha[0], (Field::StringField(String::from("Adam")), dep.clone(), dep.clone()));
+        assert_eq!(res_t1ha[1], (Field::StringField(String::from("Ben")), dep.clone(), dep.clone()));
+        assert_eq!(res_t1ha[2], (Field::StringField(String::from("Chris")), dep.clone(), dep.clone()));
     }
 
     // function to test join a HashEqJoin using hopscotch
@@ -320,12 +924,777 @@ mod test_join {
             ExtendOption::ExtendBucketSize,
             1.0,
         );
-        hopscotch_farm_join.join();
+        hopscotch_farm_join.join().unwrap();
+    }
+
+    // function to test chaining two joins through join_as_iterator
+    fn test_join_as_iterator() {
+        let a = create_vec_tuple(
+            vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "David")]);
+        let b = create_vec_tuple(
+            vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "Eva")]);
+        let c = create_vec_tuple(
+            vec![("CS", "Ben"), ("CS", "Chris"), ("CS", "Fordham")]);
+        let mut first = HashEqJoin::new(
+            a,
+            b,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        first.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 1)).unwrap();
+        // project the (name, department, department) rows of the first join back to (name, department)
+        let left: Vec<(Field, Field)> = collect_tuples(&mut first.join_as_iterator().unwrap()).unwrap()
+            .into_iter()
+            .map(|(name, dep, _)| (name, dep))
+            .collect();
+        let mut second = HashEqJoin::new(
+            left,
+            c,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        second.set_predicate(JoinPredicate::new(PredicateOp::Equals, 0, 1)).unwrap();
+        let mut scan = second.join_as_iterator().unwrap();
+        scan.open().unwrap();
+        let dep = Field::StringField(String::from("CS"));
+        assert_eq!(scan.next().unwrap(), Some((Field::StringField(String::from("Ben")), dep.clone(), dep.clone())));
+        assert_eq!(scan.next().unwrap(), Some((Field::StringField(String::from("Chris")), dep.clone(), dep.clone())));
+        assert!(scan.next().unwrap().is_none());
+    }
+
+    // function to test excluding self matches when both sides are the same data
+    fn test_exclude_self() {
+        let data = create_vec_tuple(vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Adam"), ("CS", "Chris")]);
+        let mut h_e_join = HashEqJoin::new(
+            data.clone(),
+            data.clone(),
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        h_e_join.set_exclude_self(true);
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 1)).unwrap();
+        let res = h_e_join.join().unwrap();
+
+        // only "Adam" has another identical row to match against
+        let row = |name: &str| (Field::StringField(String::from(name)),
+                                Field::StringField(String::from("CS")),
+                                Field::StringField(String::from("CS")));
+        assert_eq!(vec![row("Adam"), row("Adam")], res);
+
+        let mut self_join = HashEqJoin::new(
+            data.clone(),
+            data.clone(),
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        self_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 1)).unwrap();
+        assert_eq!(vec![row("Adam"), row("Adam"), row("Ben"), row("Adam"), row("Adam"), row("Chris")], self_join.join().unwrap());
+    }
+
+    // function to test that join_count agrees with join
+    fn test_join_count() {
+        let inputs = vec![
+            (create_vec_tuple(vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "David")]),
+             create_vec_tuple(vec![("CS", "Adam"), ("CS", "Ben"), ("CS", "Chris"), ("CS", "Eva")])),
+            (create_vec_tuple(vec![("CS", "Adam")]),
+             create_vec_tuple(vec![("CS", "Ben"), ("EE", "Adam")])),
+            (create_vec_tuple1(500), create_vec_tuple1(500)),
+        ];
+        for (l_child, r_child) in inputs {
+            let mut common = l_child.clone();
+            common.extend(r_child.iter().step_by(2).cloned());
+            let mut h_e_join = HashEqJoin::new(
+                common,
+                r_child,
+                10,
+                100,
+                HashFunction::MurmurHash3,
+                HashScheme::RobinHood,
+                4,
+                ExtendOption::ExtendBucketSize,
+                0.9,
+            );
+            let count = h_e_join.join_count().unwrap();
+            assert_eq!(h_e_join.join().unwrap().len(), count);
+        }
+    }
+
+    // function to test reading join output through a schema
+    fn test_labeled_join() {
+        let l_child = create_vec_tuple(vec![("CS", "Adam"), ("CS", "Ben"), ("EE", "Chris")]);
+        let r_child = create_vec_tuple(vec![("CS", "Ben"), ("EE", "Chris"), ("CS", "David")]);
+        let mut h_e_join = HashEqJoin::new(
+            l_child,
+            r_child,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        assert!(h_e_join.labeled_join().is_err());
+
+        h_e_join.set_schema(Schema::new(vec![String::from("department"), String::from("name"), String::from("peer")]));
+        let rows = h_e_join.labeled_join().unwrap();
+        assert_eq!(5, rows.len());
+        assert_eq!(rows[0], vec![
+            (String::from("department"), Field::StringField(String::from("CS"))),
+            (String::from("name"), Field::StringField(String::from("Adam"))),
+            (String::from("peer"), Field::StringField(String::from("Ben"))),
+        ]);
+        assert_eq!(rows[2][1], (String::from("name"), Field::StringField(String::from("Chris"))));
+
+        h_e_join.set_schema(Schema::new(vec![String::from("department"), String::from("name")]));
+        assert!(h_e_join.labeled_join().is_err());
+        h_e_join.set_schema(Schema::new(vec![String::from("name")]));
+        assert!(h_e_join.labeled_join().is_err());
+    }
+
+    // function to test that join_refs borrows the same tuples join clones
+    fn test_join_refs() {
+        let l_child = create_vec_tuple(vec![("CS", "Adam"), ("CS", "Ben"), ("EE", "Chris")]);
+        let r_child = create_vec_tuple(vec![("CS", "Ben"), ("EE", "Chris"), ("CS", "David"), ("CS", "Ben")]);
+        let mut h_e_join = HashEqJoin::new(
+            l_child,
+            r_child,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        let cloned = h_e_join.join().unwrap();
+        let refs = h_e_join.join_refs().unwrap();
+        assert_eq!(7, refs.len());
+        assert_eq!(cloned.iter().map(|(key, left, right)| (key, left, right)).collect::<Vec<_>>(), refs);
+    }
+
+    // function to test reading mixed-type join output through the typed accessors
+    fn test_typed_row() {
+        let l_child = vec![
+            (Field::StringField(String::from("CS")), Field::IntField(101)),
+            (Field::StringField(String::from("EE")), Field::IntField(201)),
+        ];
+        let r_child = vec![
+            (Field::StringField(String::from("CS")), Field::IntField(101)),
+            (Field::StringField(String::from("CS")), Field::IntField(102)),
+        ];
+        let mut h_e_join = HashEqJoin::new(
+            l_child,
+            r_child,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        let res = h_e_join.join().unwrap();
+        assert_eq!(2, res.len());
+        let row = &res[0];
+        assert_eq!(Some("CS"), row.get_str(0));
+        assert_eq!(Some(101), row.get_int(1));
+        assert_eq!(Some(101), row.get_int(2));
+        assert_eq!(None, row.get_int(0));
+        assert_eq!(None, row.get_str(1));
+        assert_eq!(None, row.get_int(3));
+        assert_eq!(Some(102), res[1].get_int(2));
+
+        h_e_join.set_schema(Schema::new(vec![String::from("department"), String::from("course"), String::from("other")]));
+        let labeled = h_e_join.labeled_join().unwrap();
+        assert_eq!(Some("CS"), labeled[0].get_str(0));
+        assert_eq!(Some(101), labeled[0].get_int(1));
+        assert_eq!(Some(102), labeled[1].get_int(2));
+    }
+
+    // function to test that a limited join stops probing once it has enough rows
+    fn test_limit() {
+        let l_child: Vec<(Field, Field)> = (0..1000)
+            .map(|i| (Field::StringField(String::from("CS")), Field::IntField(i)))
+            .collect();
+        // every other right tuple has a match
+        let r_child: Vec<(Field, Field)> = (0..10000)
+            .map(|i| (Field::StringField(String::from("CS")), Field::IntField(if i % 2 == 0 { i / 2 } else { -i })))
+            .collect();
+        let mut h_e_join = HashEqJoin::new(
+            l_child,
+            r_child,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 1)).unwrap();
+        let mut limited = h_e_join.limit(10);
+        limited.open().unwrap();
+        let mut res = Vec::new();
+        while let Some(tuple) = limited.next().unwrap() {
+            res.push(tuple);
+        }
+        assert_eq!(10, res.len());
+        let dep = Field::StringField(String::from("CS"));
+        assert_eq!((Field::IntField(9), dep.clone(), dep.clone()), res[9]);
+        // the 10th match is the 19th right tuple, nothing after it was probed
+        assert_eq!(19, limited.child.probe_index);
+
+        limited.rewind().unwrap();
+        assert_eq!(Some((Field::IntField(0), dep.clone(), dep)), limited.next().unwrap());
+        limited.close().unwrap();
+    }
+
+    // function to test that spilling the build side gives the same result as an in-memory join
+    fn test_spill() {
+        let mut common = create_vec_tuple1(50);
+        let mut l_child = create_vec_tuple1(300);
+        let mut r_child = create_vec_tuple1(300);
+        l_child.extend(common.iter().cloned());
+        // a key that is both in memory and spilled is still counted once per left tuple
+        l_child.insert(0, common[0].clone());
+        r_child.append(&mut common);
+        let new_join = || HashEqJoin::new(
+            l_child.clone(),
+            r_child.clone(),
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        let mut in_memory = new_join();
+        let expected = in_memory.join().unwrap();
+        assert_eq!(0, in_memory.spilled_rows());
+
+        let mut spilling = new_join();
+        spilling.set_spill_threshold_bytes(200);
+        assert_eq!(expected, spilling.join().unwrap());
+        assert!(spilling.spilled_rows() > 300);
+        assert_eq!(expected.len(), spilling.join_count().unwrap());
+
+        // a spill file that can't be created fails open and join, and a later open builds from scratch
+        let dir = std::env::temp_dir().join(format!("rust_hash_test_spill_{}", std::process::id()));
+        let mut spilling = new_join();
+        spilling.set_spill_threshold_bytes(200);
+        spilling.set_spill_dir(dir.join("missing"));
+        assert!(spilling.open().is_err());
+        assert!(spilling.join().is_err());
+        assert!(spilling.join_count().is_err());
+        std::fs::create_dir_all(&dir).unwrap();
+        spilling.set_spill_dir(dir.clone());
+        assert_eq!(expected, collect_tuples(&mut spilling).unwrap());
+        // the spill files are removed once the build is done
+        assert_eq!(0, std::fs::read_dir(&dir).unwrap().count());
+        std::fs::remove_dir(&dir).unwrap();
+    }
+
+    // function to test that the operators follow the shared OpIterator contract
+    fn test_opiterator_contract() {
+        let l_child = create_vec_tuple(vec![("CS", "Adam"), ("CS", "Ben"), ("EE", "Chris")]);
+        let r_child = create_vec_tuple(vec![("CS", "Ben"), ("EE", "Chris"), ("CS", "David")]);
+        let new_join = || HashEqJoin::new(
+            l_child.clone(),
+            r_child.clone(),
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        assert_opiterator_contract(&mut TupleScan::new(r_child.clone()));
+        assert_opiterator_contract(&mut TupleScan::<Tuple>::new(Vec::new()));
+        assert_opiterator_contract(&mut new_join());
+        assert_opiterator_contract(&mut new_join().limit(1));
+    }
+
+    // function to test that pulling the join through next yields what join returns, again after a rewind
+    fn test_next_matches_join() {
+        let mut common = create_vec_tuple1(200);
+        let mut l_child = create_vec_tuple1(1000);
+        l_child.extend(common.iter().cloned());
+        let mut r_child = create_vec_tuple1(1000);
+        r_child.append(&mut common);
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let new_join = || HashEqJoin::new(
+                l_child.clone(),
+                r_child.clone(),
+                10,
+                20,
+                HashFunction::FarmHash,
+                scheme,
+                4,
+                ExtendOption::ExtendBucketSize,
+                0.9,
+            );
+            let expected = new_join().join().unwrap();
+            let mut streamed = new_join();
+            streamed.open().unwrap();
+            let mut pulled = Vec::new();
+            while let Some(tuple) = streamed.next().unwrap() {
+                pulled.push(tuple);
+            }
+            assert_eq!(expected, pulled);
+            streamed.rewind().unwrap();
+            let first = streamed.next().unwrap();
+            assert_eq!(expected.first().cloned(), first);
+            streamed.close().unwrap();
+        }
+    }
+
+    // function to test that sorted join output is in key order
+    fn test_join_sorted() {
+        let mut common = create_vec_tuple1(100);
+        let mut l_child = create_vec_tuple(vec![("EE", "Chris"), ("CS", "Ben")]);
+        let mut r_child = create_vec_tuple(vec![("EE", "Chris"), ("CS", "Ben"), ("CS", "David")]);
+        l_child.extend(common.iter().cloned());
+        r_child.append(&mut common);
+        let mut h_e_join = HashEqJoin::new(
+            l_child,
+            r_child,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        let res = h_e_join.join_sorted().unwrap();
+        // EE once, the CS Ben and David right tuples both match the CS Ben left tuple, the 100 shared names once each
+        assert_eq!(103, res.len());
+        assert!(res.windows(2).all(|pair| pair[0] <= pair[1]));
+        let field = |s: &str| Field::StringField(String::from(s));
+        assert!(res.contains(&(field("EE"), field("Chris"), field("Chris"))));
+        assert!(res.contains(&(field("CS"), field("Ben"), field("David"))));
+    }
+
+    // function to test that join_for_each visits the same tuples join returns
+    fn test_join_for_each() {
+        let mut common = create_vec_tuple1(50);
+        let mut l_child = create_vec_tuple1(100);
+        let mut r_child = create_vec_tuple1(100);
+        l_child.extend(common.iter().cloned());
+        r_child.append(&mut common);
+        let mut h_e_join = HashEqJoin::new(
+            l_child,
+            r_child,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        let expected = h_e_join.join().unwrap();
+        let mut calls = 0;
+        let mut visited = Vec::new();
+        h_e_join.join_for_each(|(key, left, right)| {
+            calls += 1;
+            visited.push((key.clone(), left.clone(), right.clone()));
+        }).unwrap();
+        assert_eq!(expected.len(), calls);
+        assert_eq!(expected, visited);
+    }
+
+    // function to test that the result hint sizes the join output up front
+    fn test_expected_results() {
+        let common = create_vec_tuple1(3000);
+        let mut l_child = create_vec_tuple1(500);
+        let mut r_child = create_vec_tuple1(1000);
+        l_child.extend(common.iter().cloned());
+        r_child.extend(common.iter().cloned());
+        let new_join = || HashEqJoin::new(
+            l_child.clone(),
+            r_child.clone(),
+            16,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        let expected = new_join().join().unwrap();
+        assert_eq!(3000, expected.len());
+
+        let mut h_e_join = new_join();
+        h_e_join.set_expected_results(3000);
+        let res = h_e_join.join().unwrap();
+        assert_eq!(expected, res);
+        // a single allocation of exactly the hint, never grown
+        assert_eq!(3000, res.capacity());
+    }
+
+    // function to test that every join pair shares its key on both sides
+    fn test_join_pairs() {
+        let mut common = create_vec_tuple1(50);
+        let mut l_child = create_vec_tuple1(100);
+        let mut r_child = create_vec_tuple1(100);
+        l_child.extend(common.iter().cloned());
+        r_child.append(&mut common);
+        for spill in [None, Some(200)] {
+            let mut h_e_join = HashEqJoin::new(
+                l_child.clone(),
+                r_child.clone(),
+                2,
+                10,
+                HashFunction::FarmHash,
+                HashScheme::LinearProbe,
+                4,
+                ExtendOption::ExtendBucketSize,
+                0.9,
+            );
+            if let Some(threshold) = spill {
+                h_e_join.set_spill_threshold_bytes(threshold);
+            }
+            let pairs = h_e_join.join_pairs().unwrap();
+            assert_eq!(spill.is_some(), h_e_join.spilled_rows() > 0);
+            let merged: Vec<JoinedTuple> = pairs.iter()
+                .map(|(left, right)| (right.0.clone(), left.1.clone(), right.1.clone()))
+                .collect();
+            assert_eq!(h_e_join.join().unwrap(), merged);
+            assert!(pairs.iter().all(|(left, right)| left == right));
+            assert!(pairs.iter().all(|(left, _)| l_child.contains(left)));
+        }
+    }
+
+    // function to test that a left join keeps unmatched left tuples and repeats left tuples with several matches
+    fn test_left_join() {
+        let l_child = create_vec_tuple(
+            vec![("CS", "Adam"), ("EE", "Ben"), ("ME", "Chris"), ("CS", "David")]);
+        let r_child = create_vec_tuple(
+            vec![("CS", "Algorithms"), ("EE", "Circuits"), ("CS", "Databases"), ("MATH", "Algebra")]);
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut h_e_join = HashEqJoin::new(
+                l_child.clone(),
+                r_child.clone(),
+                2,
+                10,
+                HashFunction::FarmHash,
+                scheme,
+                4,
+                ExtendOption::ExtendBucketSize,
+                0.9,
+            );
+            let field = |s: &str| Field::StringField(String::from(s));
+            let expected = vec![
+                (field("CS"), field("Adam"), Some(field("Algorithms"))),
+                (field("CS"), field("Adam"), Some(field("Databases"))),
+                (field("EE"), field("Ben"), Some(field("Circuits"))),
+                (field("ME"), field("Chris"), None),
+                (field("CS"), field("David"), Some(field("Algorithms"))),
+                (field("CS"), field("David"), Some(field("Databases"))),
+            ];
+            assert_eq!(expected, h_e_join.left_join().unwrap());
+        }
+    }
+
+    // function to test that the threaded join matches the same tuples as join
+    #[cfg(feature = "parallel")]
+    fn test_par_join() {
+        let mut l_child = create_vec_tuple1(3000);
+        let common = create_vec_tuple1(500);
+        l_child.extend(common.iter().cloned());
+        // the shared tuples sit at other positions than on the left
+        let mut r_child = common.clone();
+        r_child.extend(create_vec_tuple1(3000));
+        // the same position on both sides, for exclude_self
+        l_child.push((Field::StringField(String::from("CS")), Field::StringField(String::from("self"))));
+        r_child.push((Field::StringField(String::from("CS")), Field::StringField(String::from("self"))));
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            for exclude in [false, true] {
+                let mut h_e_join = HashEqJoin::new(
+                    l_child.clone(),
+                    r_child.clone(),
+                    10,
+                    20,
+                    HashFunction::FarmHash,
+                    scheme,
+                    4,
+                    ExtendOption::ExtendBucketSize,
+                    0.9,
+                );
+                h_e_join.set_exclude_self(exclude);
+                let mut parallel = h_e_join.par_join().unwrap();
+                let mut serial = h_e_join.join().unwrap();
+                assert!(serial.len() >= 500);
+                parallel.sort();
+                serial.sort();
+                assert_eq!(serial, parallel);
+            }
+        }
+    }
+
+    // function to test right and full outer joins on rows unique to each side plus shared rows
+    fn test_right_full_join() {
+        let l_child = create_vec_tuple(
+            vec![("CS", "Adam"), ("EE", "Ben"), ("ME", "Chris"), ("CS", "David")]);
+        let r_child = create_vec_tuple(
+            vec![("CS", "Algorithms"), ("EE", "Circuits"), ("CS", "Databases"), ("MATH", "Algebra"), ("MATH", "Topology")]);
+        let field = |s: &str| Field::StringField(String::from(s));
+        for scheme in [HashScheme::LinearProbe, HashScheme::RobinHood, HashScheme::Hopscotch] {
+            let mut h_e_join = HashEqJoin::new(
+                l_child.clone(),
+                r_child.clone(),
+                2,
+                10,
+                HashFunction::FarmHash,
+                scheme,
+                4,
+                ExtendOption::ExtendBucketSize,
+                0.9,
+            );
+            let expected = vec![
+                (field("CS"), Some(field("Adam")), field("Algorithms")),
+                (field("CS"), Some(field("David")), field("Algorithms")),
+                (field("EE"), Some(field("Ben")), field("Circuits")),
+                (field("CS"), Some(field("Adam")), field("Databases")),
+                (field("CS"), Some(field("David")), field("Databases")),
+                (field("MATH"), None, field("Algebra")),
+                (field("MATH"), None, field("Topology")),
+            ];
+            assert_eq!(expected, h_e_join.right_join().unwrap());
+
+            // matched rows come out once, from the left side's pass
+            let expected = vec![
+                (field("CS"), Some(field("Adam")), Some(field("Algorithms"))),
+                (field("CS"), Some(field("Adam")), Some(field("Databases"))),
+                (field("EE"), Some(field("Ben")), Some(field("Circuits"))),
+                (field("ME"), Some(field("Chris")), None),
+                (field("CS"), Some(field("David")), Some(field("Algorithms"))),
+                (field("CS"), Some(field("David")), Some(field("Databases"))),
+                (field("MATH"), None, Some(field("Algebra"))),
+                (field("MATH"), None, Some(field("Topology"))),
+            ];
+            assert_eq!(expected, h_e_join.full_join().unwrap());
+        }
+    }
+
+    // function to test that semi and anti joins split the left tuples by whether they have a right match
+    fn test_semi_anti_join() {
+        let l_child = create_vec_tuple(
+            vec![("CS", "Adam"), ("EE", "Ben"), ("ME", "Chris"), ("CS", "David"), ("BIO", "Eva")]);
+        // CS appears three times on the right but each CS left tuple is kept once
+        let r_child = create_vec_tuple(
+            vec![("CS", "Algorithms"), ("EE", "Circuits"), ("CS", "Databases"), ("CS", "Compilers"), ("MATH", "Algebra")]);
+        let mut h_e_join = HashEqJoin::new(
+            l_child.clone(),
+            r_child,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::RobinHood,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        let semi = h_e_join.semi_join().unwrap();
+        let anti = h_e_join.anti_join().unwrap();
+        assert_eq!(create_vec_tuple(vec![("CS", "Adam"), ("EE", "Ben"), ("CS", "David")]), semi);
+        assert_eq!(create_vec_tuple(vec![("ME", "Chris"), ("BIO", "Eva")]), anti);
+        assert_eq!(l_child.len(), semi.len() + anti.len());
+        assert!(semi.iter().all(|tuple| !anti.contains(tuple)));
+    }
+
+    // function to test that the outer, semi and anti joins key on the predicate's columns, not the first field
+    fn test_outer_joins_on_predicate() {
+        let field = |s: &str| Field::StringField(String::from(s));
+        let l_child = create_vec_tuple(vec![("CS", "Adam"), ("EE", "Ben"), ("ME", "Chris")]);
+        let r_child = create_vec_tuple(
+            vec![("Algorithms", "Adam"), ("Circuits", "Ben"), ("Databases", "Adam"), ("Algebra", "Dan")]);
+        let mut h_e_join = HashEqJoin::new(
+            l_child,
+            r_child,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 1)).unwrap();
+        let expected = vec![
+            (field("Adam"), field("CS"), Some(field("Algorithms"))),
+            (field("Adam"), field("CS"), Some(field("Databases"))),
+            (field("Ben"), field("EE"), Some(field("Circuits"))),
+            (field("Chris"), field("ME"), None),
+        ];
+        assert_eq!(expected, h_e_join.left_join().unwrap());
+        let expected = vec![
+            (field("Adam"), Some(field("CS")), field("Algorithms")),
+            (field("Ben"), Some(field("EE")), field("Circuits")),
+            (field("Adam"), Some(field("CS")), field("Databases")),
+            (field("Dan"), None, field("Algebra")),
+        ];
+        assert_eq!(expected, h_e_join.right_join().unwrap());
+        let expected = vec![
+            (field("Adam"), Some(field("CS")), Some(field("Algorithms"))),
+            (field("Adam"), Some(field("CS")), Some(field("Databases"))),
+            (field("Ben"), Some(field("EE")), Some(field("Circuits"))),
+            (field("Chris"), Some(field("ME")), None),
+            (field("Dan"), None, Some(field("Algebra"))),
+        ];
+        assert_eq!(expected, h_e_join.full_join().unwrap());
+        assert_eq!(create_vec_tuple(vec![("CS", "Adam"), ("EE", "Ben")]), h_e_join.semi_join().unwrap());
+        assert_eq!(create_vec_tuple(vec![("ME", "Chris")]), h_e_join.anti_join().unwrap());
+    }
+
+    // function to test that the outer, semi and anti joins return the table's error for a NaN key instead of panicking
+    fn test_nan_key_outer_joins() {
+        let nan = (Field::FloatField(f64::NAN), Field::StringField(String::from("NaN")));
+        let one = (Field::FloatField(1.0), Field::StringField(String::from("one")));
+        let mut h_e_join = HashEqJoin::new(
+            vec![one.clone(), nan.clone()],
+            vec![nan, one],
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        assert!(matches!(h_e_join.left_join(), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(h_e_join.right_join(), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(h_e_join.full_join(), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(h_e_join.semi_join(), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(h_e_join.anti_join(), Err(CrustyError::ValidationError(_))));
+    }
+
+    // function to test that a join configured with HashFunction::Custom, which has no closure, returns errors
+    fn test_custom_function_join() {
+        let mut h_e_join = HashEqJoin::new(
+            create_vec_tuple(vec![("CS", "Adam")]),
+            create_vec_tuple(vec![("CS", "Ben")]),
+            2,
+            10,
+            HashFunction::Custom,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        assert!(matches!(h_e_join.join(), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(h_e_join.join_count(), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(h_e_join.left_join(), Err(CrustyError::ValidationError(_))));
+        assert!(matches!(h_e_join.open(), Err(CrustyError::ValidationError(_))));
+    }
+
+    // function to test joining on a single column, keyed on column 0 and on column 1
+    fn test_join_on() {
+        let field = |s: &str| Field::StringField(String::from(s));
+        let l_child = create_vec_tuple(vec![("CS", "Adam"), ("EE", "Ben"), ("CS", "Chris")]);
+        let r_child = create_vec_tuple(vec![("CS", "Adam2"), ("ME", "Dan"), ("Ben", "EE")]);
+        let mut h_e_join = HashEqJoin::new(
+            l_child,
+            r_child,
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        // no tuple appears on both sides, but the departments match on column 0 by default
+        let by_department = vec![
+            (field("CS"), field("Adam"), field("Adam2")),
+            (field("CS"), field("Chris"), field("Adam2")),
+        ];
+        assert_eq!(by_department, h_e_join.join().unwrap());
+        assert_eq!(2, h_e_join.join_count().unwrap());
+
+        // the name column on both sides
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 1)).unwrap();
+        assert!(h_e_join.join().unwrap().is_empty());
+
+        // the left name column against the right first column
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 1, 0)).unwrap();
+        assert_eq!(vec![(field("Ben"), field("EE"), field("EE"))], h_e_join.join().unwrap());
+
+        // the left department column against the right second column
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 0, 1)).unwrap();
+        assert_eq!(vec![(field("EE"), field("Ben"), field("Ben"))], h_e_join.join().unwrap());
+
+        // a rejected predicate keeps the last one
+        assert!(h_e_join.set_predicate(JoinPredicate::new(PredicateOp::LessThan, 0, 0)).is_err());
+        assert!(h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 2, 0)).is_err());
+        assert_eq!(vec![(field("EE"), field("Ben"), field("Ben"))], h_e_join.join().unwrap());
+
+        // the operator streams the same merged tuples
+        h_e_join.set_predicate(JoinPredicate::new(PredicateOp::Equals, 0, 0)).unwrap();
+        assert_eq!(by_department, collect_tuples(&mut h_e_join).unwrap());
+    }
+
+    // function to test that float fields within the epsilon grid join, and only once it is set
+    fn test_float_epsilon_join() {
+        let unit = Field::StringField(String::from("kg"));
+        let l_child = vec![(Field::FloatField(1.0), unit.clone()), (Field::FloatField(2.0), unit.clone())];
+        let r_child = vec![(Field::FloatField(1.0000001), unit.clone()), (Field::FloatField(1.5), unit.clone())];
+        let new_join = || HashEqJoin::new(
+            l_child.clone(),
+            r_child.clone(),
+            2,
+            10,
+            HashFunction::FarmHash,
+            HashScheme::LinearProbe,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.9,
+        );
+        assert!(new_join().join().unwrap().is_empty());
+
+        let mut h_e_join = new_join();
+        h_e_join.set_float_epsilon(Some(1e-3)).unwrap();
+        assert_eq!(vec![(Field::FloatField(1.0000001), unit.clone(), unit.clone())], h_e_join.join().unwrap());
+        assert_eq!(1, h_e_join.join_count().unwrap());
+        // the table holds the left side now
+        assert!(h_e_join.set_float_epsilon(None).is_err());
+
+        assert!(new_join().set_float_epsilon(Some(0.0)).is_err());
+        assert!(new_join().set_float_epsilon(Some(f64::NAN)).is_err());
     }
 
     mod join {
         use super::*;
 
+        #[test]
+        fn t_float_epsilon_join() {
+            test_float_epsilon_join();
+        }
+
         #[test]
         fn t_hop() {
             test_hopscotch();
@@ -355,5 +1724,116 @@ mod test_join {
         fn t_join_t1ha() {
             test_join_t1ha();
         }
+
+        #[test]
+        fn t_join_as_iterator() {
+            test_join_as_iterator();
+        }
+
+        #[test]
+        fn t_exclude_self() {
+            test_exclude_self();
+        }
+
+        #[test]
+        fn t_join_count() {
+            test_join_count();
+        }
+
+        #[test]
+        fn t_labeled_join() {
+            test_labeled_join();
+        }
+
+        #[test]
+        fn t_join_refs() {
+            test_join_refs();
+        }
+
+        #[test]
+        fn t_typed_row() {
+            test_typed_row();
+        }
+
+        #[test]
+        fn t_limit() {
+            test_limit();
+        }
+
+        #[test]
+        fn t_spill() {
+            test_spill();
+        }
+
+        #[test]
+        fn t_opiterator_contract() {
+            test_opiterator_contract();
+        }
+
+        #[test]
+        fn t_join_sorted() {
+            test_join_sorted();
+        }
+
+        #[test]
+        fn t_join_for_each() {
+            test_join_for_each();
+        }
+
+        #[test]
+        fn t_expected_results() {
+            test_expected_results();
+        }
+
+        #[test]
+        fn t_join_pairs() {
+            test_join_pairs();
+        }
+
+        #[test]
+        fn t_left_join() {
+            test_left_join();
+        }
+
+        #[test]
+        fn t_semi_anti_join() {
+            test_semi_anti_join();
+        }
+
+        #[test]
+        fn t_outer_joins_on_predicate() {
+            test_outer_joins_on_predicate();
+        }
+
+        #[test]
+        fn t_nan_key_outer_joins() {
+            test_nan_key_outer_joins();
+        }
+
+        #[test]
+        fn t_custom_function_join() {
+            test_custom_function_join();
+        }
+
+        #[test]
+        fn t_join_on() {
+            test_join_on();
+        }
+
+        #[test]
+        fn t_right_full_join() {
+            test_right_full_join();
+        }
+
+        #[test]
+        #[cfg(feature = "parallel")]
+        fn t_par_join() {
+            test_par_join();
+        }
+
+        #[test]
+        fn t_next_matches_join() {
+            test_next_matches_join();
+        }
     }
-}
\ No newline at end of file
+}
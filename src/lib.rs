@@ -1,4 +1,8 @@
 pub mod join;
+pub mod aggregation;
 pub mod hash;
 pub mod common;
+pub mod concurrent;
+pub mod signed;
+pub mod sip;
 
@@ -3,7 +3,6 @@ use hash::join::*;
 use hash::hash::*;
 use rand::{distributions::Alphanumeric, Rng}; // 0.8.5
 use std::fs::File;
-use std::io::LineWriter;
 use std::io::prelude::*;
 
 // function to creat number of tuples for benchmark
@@ -16,20 +15,37 @@ pub fn create_vec_tuple(tuple_number: usize, key_length: usize) -> Vec<(Field, F
             .take(key_length)
             .map(char::from)
             .collect();
-        // use fixed "CS" as "department"
-        let fields = (Field::StringField(String::from("CS")),
-                      Field::StringField(s));
+        // use fixed "CS" as "department", after the name since joins key on the first field
+        let fields = (Field::StringField(s),
+                      Field::StringField(String::from("CS")));
         tuples.push(fields);
     }
     tuples
 }
 
+// function to creat number of (IntField, StringField) tuples for benchmark
+pub fn create_mixed_vec_tuple(tuple_number: usize, key_length: usize) -> Vec<(Field, Field)> {
+    let mut rng = rand::thread_rng();
+    let mut tuples = Vec::new();
+    for _ in 0..tuple_number {
+        // create a random int as "id" attribute and a random string as "name" attribute
+        let id: i32 = rng.gen();
+        let s: String = (&mut rng)
+            .sample_iter(&Alphanumeric)
+            .take(key_length)
+            .map(char::from)
+            .collect();
+        tuples.push((Field::IntField(id), Field::StringField(s)));
+    }
+    tuples
+}
+
 // helper method to benchmark 5k tuples
 fn c_5k(mut file: &File) {
-    file.write_all("5k:\n".as_ref());
-    let mut common = create_vec_tuple((2500 as f64 * 0.1) as usize, 7);
-    let mut left_child = create_vec_tuple((2500 as f64 * 0.9) as usize, 7);
-    let mut right_child = create_vec_tuple((2500 as f64 * 0.9) as usize, 7);
+    file.write_all("5k:\n".as_ref()).unwrap();
+    let mut common = create_vec_tuple((2500.0 * 0.1) as usize, 7);
+    let mut left_child = create_vec_tuple((2500.0 * 0.9) as usize, 7);
+    let mut right_child = create_vec_tuple((2500.0 * 0.9) as usize, 7);
     left_child.append(&mut common);
     right_child.append(&mut common);
     // // Linear Probe
@@ -113,11 +129,11 @@ fn c_5k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.75,
     );
-    file.write_all("Hopscotch + Farm Hash:\n".as_ref());
+    file.write_all("Hopscotch + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    hopscotch_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    hopscotch_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut hopscotch_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -130,11 +146,11 @@ fn c_5k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.75,
     );
-    file.write_all("Hopscotch + Murmur Hash 3:\n".as_ref());
+    file.write_all("Hopscotch + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    hopscotch_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    hopscotch_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut hopscotch_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -147,11 +163,11 @@ fn c_5k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.75,
     );
-    file.write_all("Hopscotch + std Hash:\n".as_ref());
+    file.write_all("Hopscotch + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    hopscotch_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    hopscotch_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut hopscotch_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -164,14 +180,14 @@ fn c_5k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.75,
     );
-    file.write_all("Hopscotch + T1ha Hash:\n".as_ref());
+    file.write_all("Hopscotch + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    hopscotch_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    hopscotch_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Robin hood
-    // let mut RobinHood_farm_join = HashEqJoin::new(
+    // let mut robin_hood_farm_join = HashEqJoin::new(
     //     left_child.clone(),
     //     right_child.clone(),
     //     50,
@@ -184,11 +200,11 @@ fn c_5k(mut file: &File) {
     // );
     // file.write_all("RobinHood + Farm Hash:\n".as_ref());
     // let now = Instant::now();
-    // RobinHood_farm_join.join();
+    // robin_hood_farm_join.join();
     // file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
     // file.write_all("\n".as_ref());
     //
-    // let mut RobinHood_murmur_join = HashEqJoin::new(
+    // let mut robin_hood_murmur_join = HashEqJoin::new(
     //     left_child.clone(),
     //     right_child.clone(),
     //     50,
@@ -201,11 +217,11 @@ fn c_5k(mut file: &File) {
     // );
     // file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
     // let now = Instant::now();
-    // RobinHood_murmur_join.join();
+    // robin_hood_murmur_join.join();
     // file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
     // file.write_all("\n".as_ref());
     //
-    // let mut RobinHood_std_join = HashEqJoin::new(
+    // let mut robin_hood_std_join = HashEqJoin::new(
     //     left_child.clone(),
     //     right_child.clone(),
     //     50,
@@ -218,11 +234,11 @@ fn c_5k(mut file: &File) {
     // );
     // file.write_all("RobinHood + std Hash:\n".as_ref());
     // let now = Instant::now();
-    // RobinHood_std_join.join();
+    // robin_hood_std_join.join();
     // file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
     // file.write_all("\n".as_ref());
     //
-    // let mut RobinHood_t1ha_join = HashEqJoin::new(
+    // let mut robin_hood_t1ha_join = HashEqJoin::new(
     //     left_child.clone(),
     //     right_child.clone(),
     //     50,
@@ -235,13 +251,13 @@ fn c_5k(mut file: &File) {
     // );
     // file.write_all("RobinHood + T1ha Hash:\n".as_ref());
     // let now = Instant::now();
-    // RobinHood_t1ha_join.join();
+    // robin_hood_t1ha_join.join();
     // file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
     // file.write_all("\n".as_ref());
 }
 // helper method to benchmark 100k tuples
 fn c_100k(mut file: &File) {
-    file.write_all("100k:\n".as_ref());
+    file.write_all("100k:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 7);
     let right_child = create_vec_tuple(50000, 7);
     // Linear Probe
@@ -256,11 +272,11 @@ fn c_100k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -273,11 +289,11 @@ fn c_100k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -290,11 +306,11 @@ fn c_100k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -307,11 +323,11 @@ fn c_100k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -383,7 +399,7 @@ fn c_100k(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -394,13 +410,13 @@ fn c_100k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -411,13 +427,13 @@ fn c_100k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -428,13 +444,13 @@ fn c_100k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -445,15 +461,15 @@ fn c_100k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // helper method to benchmark 500k tuples
 fn c_500k(mut file: &File) {
-    file.write_all("500k:\n".as_ref());
+    file.write_all("500k:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(250000, 7);
     let right_child = create_vec_tuple(250000, 7);
     // Linear Probe
@@ -468,11 +484,11 @@ fn c_500k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -485,11 +501,11 @@ fn c_500k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -502,11 +518,11 @@ fn c_500k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -519,11 +535,11 @@ fn c_500k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -595,7 +611,7 @@ fn c_500k(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         5000,
@@ -606,13 +622,13 @@ fn c_500k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         5000,
@@ -623,13 +639,13 @@ fn c_500k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         5000,
@@ -640,13 +656,13 @@ fn c_500k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         5000,
@@ -657,15 +673,15 @@ fn c_500k(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // method to benchmark different cardinality with 12 permutations
 fn cardinality(mut file: &File) {
-    file.write_all("Micro-benchmark with different cardinality\n".as_ref());
+    file.write_all("Micro-benchmark with different cardinality\n".as_ref()).unwrap();
     c_5k(file);
     // c_100k(file);
     // c_500k(file);
@@ -673,7 +689,7 @@ fn cardinality(mut file: &File) {
 
 // helper method to benchmark extend bucket number
 fn eo_b_number(mut file: &File) {
-    file.write_all("bucket number:\n".as_ref());
+    file.write_all("bucket number:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 7);
     let right_child = create_vec_tuple(50000, 7);
     // Linear Probe
@@ -688,11 +704,11 @@ fn eo_b_number(mut file: &File) {
         ExtendOption::ExtendBucketNumber,
         0.9,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -705,11 +721,11 @@ fn eo_b_number(mut file: &File) {
         ExtendOption::ExtendBucketNumber,
         0.9,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -722,11 +738,11 @@ fn eo_b_number(mut file: &File) {
         ExtendOption::ExtendBucketNumber,
         0.9,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -739,11 +755,11 @@ fn eo_b_number(mut file: &File) {
         ExtendOption::ExtendBucketNumber,
         0.9,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -815,7 +831,7 @@ fn eo_b_number(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         200,
@@ -826,13 +842,13 @@ fn eo_b_number(mut file: &File) {
         ExtendOption::ExtendBucketNumber,
         0.9,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         200,
@@ -843,13 +859,13 @@ fn eo_b_number(mut file: &File) {
         ExtendOption::ExtendBucketNumber,
         0.9,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         200,
@@ -860,13 +876,13 @@ fn eo_b_number(mut file: &File) {
         ExtendOption::ExtendBucketNumber,
         0.9,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         200,
@@ -877,15 +893,15 @@ fn eo_b_number(mut file: &File) {
         ExtendOption::ExtendBucketNumber,
         0.9,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // helper method to benchmark extend bucket number
 fn eo_b_size(mut file: &File) {
-    file.write_all("bucket size:\n".as_ref());
+    file.write_all("bucket size:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 7);
     let right_child = create_vec_tuple(50000, 7);
     // Linear Probe
@@ -900,11 +916,11 @@ fn eo_b_size(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.75,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // let mut linear_murmur_join = HashEqJoin::new(
     //     left_child.clone(),
@@ -1027,7 +1043,7 @@ fn eo_b_size(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // // Robin hood
-    // let mut RobinHood_farm_join = HashEqJoin::new(
+    // let mut robin_hood_farm_join = HashEqJoin::new(
     //     left_child.clone(),
     //     right_child.clone(),
     //     200,
@@ -1040,11 +1056,11 @@ fn eo_b_size(mut file: &File) {
     // );
     // file.write_all("RobinHood + Farm Hash:\n".as_ref());
     // let now = Instant::now();
-    // RobinHood_farm_join.join();
+    // robin_hood_farm_join.join();
     // file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
     // file.write_all("\n".as_ref());
     //
-    // let mut RobinHood_murmur_join = HashEqJoin::new(
+    // let mut robin_hood_murmur_join = HashEqJoin::new(
     //     left_child.clone(),
     //     right_child.clone(),
     //     200,
@@ -1057,11 +1073,11 @@ fn eo_b_size(mut file: &File) {
     // );
     // file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
     // let now = Instant::now();
-    // RobinHood_murmur_join.join();
+    // robin_hood_murmur_join.join();
     // file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
     // file.write_all("\n".as_ref());
     //
-    // let mut RobinHood_std_join = HashEqJoin::new(
+    // let mut robin_hood_std_join = HashEqJoin::new(
     //     left_child.clone(),
     //     right_child.clone(),
     //     200,
@@ -1074,11 +1090,11 @@ fn eo_b_size(mut file: &File) {
     // );
     // file.write_all("RobinHood + std Hash:\n".as_ref());
     // let now = Instant::now();
-    // RobinHood_std_join.join();
+    // robin_hood_std_join.join();
     // file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
     // file.write_all("\n".as_ref());
     //
-    // let mut RobinHood_t1ha_join = HashEqJoin::new(
+    // let mut robin_hood_t1ha_join = HashEqJoin::new(
     //     left_child.clone(),
     //     right_child.clone(),
     //     200,
@@ -1091,20 +1107,20 @@ fn eo_b_size(mut file: &File) {
     // );
     // file.write_all("RobinHood + T1ha Hash:\n".as_ref());
     // let now = Instant::now();
-    // RobinHood_t1ha_join.join();
+    // robin_hood_t1ha_join.join();
     // file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
     // file.write_all("\n".as_ref());
 }
 // method to benchmark different extend option with 12 permutations
 fn extend_option(mut file: &File) {
-    file.write_all("Micro-benchmark with different extend option\n".as_ref());
+    file.write_all("Micro-benchmark with different extend option\n".as_ref()).unwrap();
     // eo_b_number(file);
     eo_b_size(file);
 }
 
 // helper method to benchmark load factor 0.5
 fn lf_05(mut file: &File) {
-    file.write_all("0.5:\n".as_ref());
+    file.write_all("0.5:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 7);
     let right_child = create_vec_tuple(50000, 7);
     // Linear Probe
@@ -1119,11 +1135,11 @@ fn lf_05(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.5,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -1136,11 +1152,11 @@ fn lf_05(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.5,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -1153,11 +1169,11 @@ fn lf_05(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.5,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -1170,11 +1186,11 @@ fn lf_05(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.5,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -1246,7 +1262,7 @@ fn lf_05(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1257,13 +1273,13 @@ fn lf_05(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.5,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1274,13 +1290,13 @@ fn lf_05(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.5,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1291,13 +1307,13 @@ fn lf_05(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.5,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1308,15 +1324,15 @@ fn lf_05(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.5,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // helper method to benchmark load factor 0.75
 fn lf_07(mut file: &File) {
-    file.write_all("0.7:\n".as_ref());
+    file.write_all("0.7:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 7);
     let right_child = create_vec_tuple(50000, 7);
     // Linear Probe
@@ -1331,11 +1347,11 @@ fn lf_07(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.7,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -1348,11 +1364,11 @@ fn lf_07(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.7,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -1365,11 +1381,11 @@ fn lf_07(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.7,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -1382,11 +1398,11 @@ fn lf_07(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.7,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -1458,7 +1474,7 @@ fn lf_07(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1469,13 +1485,13 @@ fn lf_07(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.7,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1486,13 +1502,13 @@ fn lf_07(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.7,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1503,13 +1519,13 @@ fn lf_07(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.7,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1520,15 +1536,15 @@ fn lf_07(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.7,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // helper method to benchmark load factor 1.0
 fn lf_10(mut file: &File) {
-    file.write_all("1.0:\n".as_ref());
+    file.write_all("1.0:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 7);
     let right_child = create_vec_tuple(50000, 7);
     // Linear Probe
@@ -1543,11 +1559,11 @@ fn lf_10(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         1.0,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -1560,11 +1576,11 @@ fn lf_10(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         1.0,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -1577,11 +1593,11 @@ fn lf_10(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         1.0,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -1594,11 +1610,11 @@ fn lf_10(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         1.0,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -1670,7 +1686,7 @@ fn lf_10(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1681,13 +1697,13 @@ fn lf_10(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         1.0,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1698,13 +1714,13 @@ fn lf_10(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         1.0,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1715,13 +1731,13 @@ fn lf_10(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         1.0,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -1732,15 +1748,15 @@ fn lf_10(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         1.0,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // method to benchmark different load factor with 12 permutations
 fn load_factor(mut file: &File) {
-    file.write_all("Micro-benchmark with different load factor\n".as_ref());
+    file.write_all("Micro-benchmark with different load factor\n".as_ref()).unwrap();
     lf_05(file);
     lf_07(file);
     lf_10(file);
@@ -1748,7 +1764,7 @@ fn load_factor(mut file: &File) {
 
 // helper method to benchmark b_number 500 * b_size 200
 fn sn_500_200(mut file: &File) {
-    file.write_all("500 buckets * 200 slots/bucket:\n".as_ref());
+    file.write_all("500 buckets * 200 slots/bucket:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 7);
     let right_child = create_vec_tuple(50000, 7);
     // Linear Probe
@@ -1763,11 +1779,11 @@ fn sn_500_200(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -1780,11 +1796,11 @@ fn sn_500_200(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -1797,11 +1813,11 @@ fn sn_500_200(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -1814,11 +1830,11 @@ fn sn_500_200(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -1890,7 +1906,7 @@ fn sn_500_200(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         500,
@@ -1901,13 +1917,13 @@ fn sn_500_200(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         500,
@@ -1918,13 +1934,13 @@ fn sn_500_200(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         500,
@@ -1935,13 +1951,13 @@ fn sn_500_200(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         500,
@@ -1952,15 +1968,15 @@ fn sn_500_200(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // helper method to benchmark b_number 200 * b_size 500
 fn sn_200_500(mut file: &File) {
-    file.write_all("200 buckets * 500 slots/bucket:\n".as_ref());
+    file.write_all("200 buckets * 500 slots/bucket:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 7);
     let right_child = create_vec_tuple(50000, 7);
     // Linear Probe
@@ -1975,11 +1991,11 @@ fn sn_200_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -1992,11 +2008,11 @@ fn sn_200_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -2009,11 +2025,11 @@ fn sn_200_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -2026,11 +2042,11 @@ fn sn_200_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -2102,7 +2118,7 @@ fn sn_200_500(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         200,
@@ -2113,13 +2129,13 @@ fn sn_200_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         200,
@@ -2130,13 +2146,13 @@ fn sn_200_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         200,
@@ -2147,13 +2163,13 @@ fn sn_200_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         200,
@@ -2164,15 +2180,15 @@ fn sn_200_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // helper method to benchmark b_number 100 * b_size 1000
 fn sn_100_1000(mut file: &File) {
-    file.write_all("100 buckets * 1000 slots/bucket:\n".as_ref());
+    file.write_all("100 buckets * 1000 slots/bucket:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 7);
     let right_child = create_vec_tuple(50000, 7);
     // Linear Probe
@@ -2187,11 +2203,11 @@ fn sn_100_1000(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -2204,11 +2220,11 @@ fn sn_100_1000(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -2221,11 +2237,11 @@ fn sn_100_1000(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -2238,11 +2254,11 @@ fn sn_100_1000(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -2314,7 +2330,7 @@ fn sn_100_1000(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         100,
@@ -2325,13 +2341,13 @@ fn sn_100_1000(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         100,
@@ -2342,13 +2358,13 @@ fn sn_100_1000(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         100,
@@ -2359,13 +2375,13 @@ fn sn_100_1000(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         100,
@@ -2376,15 +2392,15 @@ fn sn_100_1000(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // method to benchmark different b_number and b_size with 12 permutations
 fn size_number(mut file: &File) {
-    file.write_all("Micro-benchmark with different b_number and b_size\n".as_ref());
+    file.write_all("Micro-benchmark with different b_number and b_size\n".as_ref()).unwrap();
     sn_500_200(file);
     sn_200_500(file);
     sn_100_1000(file);
@@ -2392,7 +2408,7 @@ fn size_number(mut file: &File) {
 
 // helper method to benchmark key length 20
 fn kl_20(mut file: &File) {
-    file.write_all("20 key length:\n".as_ref());
+    file.write_all("20 key length:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 20);
     let right_child = create_vec_tuple(50000, 20);
     // Linear Probe
@@ -2407,11 +2423,11 @@ fn kl_20(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -2424,11 +2440,11 @@ fn kl_20(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -2441,11 +2457,11 @@ fn kl_20(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -2458,11 +2474,11 @@ fn kl_20(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -2534,7 +2550,7 @@ fn kl_20(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -2545,13 +2561,13 @@ fn kl_20(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -2562,13 +2578,13 @@ fn kl_20(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -2579,13 +2595,13 @@ fn kl_20(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -2596,15 +2612,15 @@ fn kl_20(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // helper method to benchmark key length 100
 fn kl_100(mut file: &File) {
-    file.write_all("100 key length:\n".as_ref());
+    file.write_all("100 key length:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 100);
     let right_child = create_vec_tuple(50000, 100);
     // Linear Probe
@@ -2619,11 +2635,11 @@ fn kl_100(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -2636,11 +2652,11 @@ fn kl_100(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -2653,11 +2669,11 @@ fn kl_100(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -2670,11 +2686,11 @@ fn kl_100(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -2746,7 +2762,7 @@ fn kl_100(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -2757,13 +2773,13 @@ fn kl_100(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -2774,13 +2790,13 @@ fn kl_100(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -2791,13 +2807,13 @@ fn kl_100(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -2808,15 +2824,15 @@ fn kl_100(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // helper method to benchmark key length 500
 fn kl_500(mut file: &File) {
-    file.write_all("500 key length:\n".as_ref());
+    file.write_all("500 key length:\n".as_ref()).unwrap();
     let left_child = create_vec_tuple(50000, 500);
     let right_child = create_vec_tuple(50000, 500);
     // Linear Probe
@@ -2831,11 +2847,11 @@ fn kl_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Farm Hash:\n".as_ref());
+    file.write_all("Linear Probe + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_murmur_join = HashEqJoin::new(
         left_child.clone(),
@@ -2848,11 +2864,11 @@ fn kl_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref());
+    file.write_all("Linear Probe + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_std_join = HashEqJoin::new(
         left_child.clone(),
@@ -2865,11 +2881,11 @@ fn kl_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + std Hash:\n".as_ref());
+    file.write_all("Linear Probe + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     let mut linear_t1ha_join = HashEqJoin::new(
         left_child.clone(),
@@ -2882,11 +2898,11 @@ fn kl_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("Linear Probe + T1ha Hash:\n".as_ref());
+    file.write_all("Linear Probe + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    linear_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    linear_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
     // // Hopscotch
     // let mut hopscotch_farm_join = HashEqJoin::new(
@@ -2958,7 +2974,7 @@ fn kl_500(mut file: &File) {
     // file.write_all("\n".as_ref());
 
     // Robin hood
-    let mut RobinHood_farm_join = HashEqJoin::new(
+    let mut robin_hood_farm_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -2969,13 +2985,13 @@ fn kl_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Farm Hash:\n".as_ref());
+    file.write_all("RobinHood + Farm Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_farm_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_farm_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_murmur_join = HashEqJoin::new(
+    let mut robin_hood_murmur_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -2986,13 +3002,13 @@ fn kl_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref());
+    file.write_all("RobinHood + Murmur Hash 3:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_murmur_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_murmur_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_std_join = HashEqJoin::new(
+    let mut robin_hood_std_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -3003,13 +3019,13 @@ fn kl_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + std Hash:\n".as_ref());
+    file.write_all("RobinHood + std Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_std_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_std_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 
-    let mut RobinHood_t1ha_join = HashEqJoin::new(
+    let mut robin_hood_t1ha_join = HashEqJoin::new(
         left_child.clone(),
         right_child.clone(),
         1000,
@@ -3020,25 +3036,111 @@ fn kl_500(mut file: &File) {
         ExtendOption::ExtendBucketSize,
         0.9,
     );
-    file.write_all("RobinHood + T1ha Hash:\n".as_ref());
+    file.write_all("RobinHood + T1ha Hash:\n".as_ref()).unwrap();
     let now = Instant::now();
-    RobinHood_t1ha_join.join();
-    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref());
-    file.write_all("\n".as_ref());
+    robin_hood_t1ha_join.join().unwrap();
+    file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+    file.write_all("\n".as_ref()).unwrap();
 }
 // method to benchmark different key length with 12 permutations
 fn key_length(mut file: &File) {
-    file.write_all("Micro-benchmark with different key length\n".as_ref());
+    file.write_all("Micro-benchmark with different key length\n".as_ref()).unwrap();
     kl_20(file);
     kl_100(file);
     kl_500(file);
 }
 
+// method to benchmark (IntField, StringField) keys with every scheme and hash function
+fn mixed_keys(mut file: &File) {
+    file.write_all("Micro-benchmark with mixed Int/String keys\n".as_ref()).unwrap();
+    let mut common = create_mixed_vec_tuple(250, 7);
+    let mut left_child = create_mixed_vec_tuple(2250, 7);
+    let mut right_child = create_mixed_vec_tuple(2250, 7);
+    left_child.extend(common.iter().cloned());
+    right_child.append(&mut common);
+    let schemes = [HashScheme::LinearProbe, HashScheme::Hopscotch, HashScheme::RobinHood];
+    let functions = [
+        HashFunction::FarmHash,
+        HashFunction::MurmurHash3,
+        HashFunction::StdHash,
+        HashFunction::T1haHash,
+        HashFunction::MetroHash,
+        HashFunction::SeededSip,
+        HashFunction::WyHash,
+    ];
+    for scheme in schemes {
+        for function in functions {
+            let mut join = HashEqJoin::new(
+                left_child.clone(),
+                right_child.clone(),
+                50,
+                100,
+                function,
+                scheme,
+                10,
+                ExtendOption::ExtendBucketSize,
+                0.75,
+            );
+            file.write_all(format!("{:?} + {:?}:\n", scheme, function).as_ref()).unwrap();
+            let now = Instant::now();
+            join.join().unwrap();
+            file.write_all(now.elapsed().as_secs_f64().to_string().as_ref()).unwrap();
+            file.write_all("\n".as_ref()).unwrap();
+        }
+    }
+}
+
 fn main() {
-    let mut file = File::create("res2.txt").unwrap();
-    cardinality(&file);
-    // extend_option(&file);
-    // load_factor(&file);
-    // size_number(&file);
-    // key_length(&file);
-}
\ No newline at end of file
+    let file = File::create("res2.txt").unwrap();
+    // pick a scenario by name, e.g. `cargo run -- mixed_keys`
+    match std::env::args().nth(1).as_deref() {
+        Some("mixed_keys") => mixed_keys(&file),
+        Some("c_100k") => c_100k(&file),
+        Some("c_500k") => c_500k(&file),
+        Some("eo_b_number") => eo_b_number(&file),
+        Some("extend_option") => extend_option(&file),
+        Some("load_factor") => load_factor(&file),
+        Some("size_number") => size_number(&file),
+        Some("key_length") => key_length(&file),
+        _ => cardinality(&file),
+    }
+}
+
+#[cfg(test)]
+mod test_main {
+    use super::*;
+
+    // function to test the mixed key generator and a join over its tuples
+    fn test_mixed_vec_tuple() {
+        let tuples = create_mixed_vec_tuple(100, 7);
+        assert_eq!(100, tuples.len());
+        for (id, name) in tuples.iter() {
+            assert!(matches!(id, Field::IntField(_)));
+            assert_eq!(7, name.unwrap_string_field().len());
+        }
+
+        let mut right_child = create_mixed_vec_tuple(100, 7);
+        right_child.extend(tuples.iter().take(10).cloned());
+        let mut join = HashEqJoin::new(
+            tuples,
+            right_child,
+            5,
+            10,
+            HashFunction::MetroHash,
+            HashScheme::RobinHood,
+            4,
+            ExtendOption::ExtendBucketSize,
+            0.75,
+        );
+        assert!(join.join().unwrap().len() >= 10);
+    }
+
+    mod main {
+        use super::*;
+
+        #[test]
+        fn t_mixed_vec_tuple() {
+            test_mixed_vec_tuple();
+        }
+    }
+}
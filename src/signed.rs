@@ -0,0 +1,124 @@
+use crate::common::CrustyError;
+use crate::hash::{ExtendOption, HashFunction, HashScheme, HashTable, Key, KeyRef, OnDuplicate};
+
+/// Hash table accumulating signed values, e.g. debits and credits whose totals can go below zero.
+///
+/// The keys live in a HashTable whose value is the index of the key's total in `totals`, so every
+/// total is a full i64 on any target and the usize values of the inner table are never misread as counts.
+pub struct SignedHashTable {
+    table: HashTable,
+    totals: Vec<i64>,
+}
+
+impl SignedHashTable {
+    // initialize a table configured like HashTable::new
+    // returns an error for HashFunction::Custom, see HashTable::new
+    pub fn new(
+        b_size: usize,
+        b_num: usize,
+        func: HashFunction,
+        sche: HashScheme,
+        h: usize,
+        op: ExtendOption,
+        load_f: f64,
+    ) -> Result<Self, CrustyError> {
+        let mut table = HashTable::new(b_size, b_num, func, sche, h, op, load_f)?;
+        // the stored index of a key never changes once it's placed
+        table.set_on_duplicate(OnDuplicate::KeepFirst);
+        Ok(SignedHashTable { table, totals: Vec::new() })
+    }
+
+    // method to add a signed value to the total of a key, a total past the i64 range is an error and is left alone
+    pub fn insert(&mut self, new_key: impl Into<Key>, new_value: i64) -> Result<(), CrustyError> {
+        let new_key = new_key.into();
+        if let Some(&index) = self.table.get_value(&new_key) {
+            self.totals[index] = self.totals[index].checked_add(new_value)
+                .ok_or_else(|| CrustyError::ExecutionError(String::from("Accumulated value overflows i64")))?;
+            return Ok(());
+        }
+        self.table.insert(new_key, self.totals.len())?;
+        self.totals.push(new_value);
+        Ok(())
+    }
+
+    // method to get the total of a key
+    pub fn get_value<'a>(&self, key: impl Into<KeyRef<'a>>) -> Option<i64> {
+        self.table.get_value(key).map(|&index| self.totals[index])
+    }
+
+    // method to iterate over every key and its total in bucket order
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, i64)> {
+        self.table.iter().map(|(key, &index)| (key, self.totals[index]))
+    }
+
+    // method to copy the keys and totals out in bucket order
+    pub fn to_entries(&self) -> Vec<(Key, i64)> {
+        self.iter().map(|(key, total)| (key.clone(), total)).collect()
+    }
+
+    // method to add every total of other into this table
+    pub fn merge(&mut self, other: SignedHashTable) -> Result<(), CrustyError> {
+        for (key, total) in other.to_entries() {
+            self.insert(key, total)?;
+        }
+        Ok(())
+    }
+
+    // method to get the number of keys in the table
+    pub fn len(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test_signed {
+    use super::*;
+    use crate::hash::Field;
+
+    // function to test that positive and negative values sum to a signed total
+    fn test_signed_insert() {
+        let mut table = SignedHashTable::new(10, 2, HashFunction::FarmHash, HashScheme::RobinHood, 4,
+                                             ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        let account = (Field::StringField(String::from("account")), Field::IntField(1));
+        for amount in [10, -25, 3] {
+            table.insert(account.clone(), amount).unwrap();
+        }
+        assert_eq!(Some(-12), table.get_value((&account.0, &account.1)));
+        // enough keys to extend the inner table, totals keep their keys
+        for i in 0..100 {
+            table.insert((Field::StringField(String::from("other")), Field::IntField(i)), -(i as i64)).unwrap();
+        }
+        table.insert(account.clone(), 20).unwrap();
+        assert_eq!(Some(8), table.get_value((&account.0, &account.1)));
+        assert_eq!(Some(-99), table.get_value((&Field::StringField(String::from("other")), &Field::IntField(99))));
+        assert_eq!(None, table.get_value((&account.0, &Field::IntField(2))));
+        assert_eq!(101, table.len());
+        assert_eq!(-4942, table.iter().map(|(_, total)| total).sum::<i64>());
+
+        // an overflowing total is refused and keeps its value
+        table.insert(account.clone(), i64::MAX - 8).unwrap();
+        assert!(matches!(table.insert(account.clone(), 1), Err(CrustyError::ExecutionError(_))));
+        assert_eq!(Some(i64::MAX), table.get_value((&account.0, &account.1)));
+
+        let mut other = SignedHashTable::new(10, 2, HashFunction::FarmHash, HashScheme::RobinHood, 4,
+                                             ExtendOption::ExtendBucketSize, 0.9).unwrap();
+        other.insert(account.clone(), -i64::MAX).unwrap();
+        other.insert((Field::StringField(String::from("new")), Field::IntField(0)), -1).unwrap();
+        table.merge(other).unwrap();
+        assert_eq!(Some(0), table.get_value((&account.0, &account.1)));
+        assert_eq!(102, table.to_entries().len());
+    }
+
+    mod signed {
+        use super::*;
+
+        #[test]
+        fn t_signed_insert() {
+            test_signed_insert();
+        }
+    }
+}
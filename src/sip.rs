@@ -0,0 +1,134 @@
+use std::hash::Hasher;
+
+/// SipHash-1-3 keyed with two 64-bit words, the hash std's HashMap uses against collision attacks.
+///
+/// std keeps its own SipHasher13 unstable, so the few rounds it needs live here.
+#[derive(Debug, Clone)]
+pub struct SipHasher13 {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+    // bytes written that don't fill a whole word yet
+    tail: u64,
+    ntail: usize,
+    length: usize,
+}
+
+impl SipHasher13 {
+    pub fn new_with_keys(k0: u64, k1: u64) -> Self {
+        SipHasher13 {
+            v0: k0 ^ 0x736f6d6570736575,
+            v1: k1 ^ 0x646f72616e646f6d,
+            v2: k0 ^ 0x6c7967656e657261,
+            v3: k1 ^ 0x7465646279746573,
+            tail: 0,
+            ntail: 0,
+            length: 0,
+        }
+    }
+
+    // method to run one SipRound over the state
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13);
+        self.v1 ^= self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16);
+        self.v3 ^= self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21);
+        self.v3 ^= self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17);
+        self.v1 ^= self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    // method to compress one little endian message word
+    fn compress(&mut self, word: u64) {
+        self.v3 ^= word;
+        self.round();
+        self.v0 ^= word;
+    }
+}
+
+impl Hasher for SipHasher13 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.length += bytes.len();
+        for &byte in bytes {
+            self.tail |= (byte as u64) << (8 * self.ntail);
+            self.ntail += 1;
+            if self.ntail == 8 {
+                let word = self.tail;
+                self.compress(word);
+                self.tail = 0;
+                self.ntail = 0;
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = self.clone();
+        let last = ((self.length as u64 & 0xff) << 56) | self.tail;
+        state.compress(last);
+        state.v2 ^= 0xff;
+        for _ in 0..3 {
+            state.round();
+        }
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+}
+
+#[cfg(test)]
+mod test_sip {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    // function to test that zero keys give the same hash as std's DefaultHasher, which is SipHash-1-3 too
+    fn test_matches_default_hasher() {
+        for len in 0..40 {
+            let bytes: Vec<u8> = (0..len as u8).map(|i| i.wrapping_mul(37)).collect();
+            let mut ours = SipHasher13::new_with_keys(0, 0);
+            let mut std = DefaultHasher::new();
+            ours.write(&bytes);
+            std.write(&bytes);
+            assert_eq!(std.finish(), ours.finish(), "length {}", len);
+        }
+        // writes split at any point hash the same as one write
+        let mut whole = SipHasher13::new_with_keys(1, 2);
+        whole.write(b"hello sip hasher");
+        let mut pieces = SipHasher13::new_with_keys(1, 2);
+        pieces.write(b"hel");
+        pieces.write(b"lo sip has");
+        pieces.write(b"her");
+        assert_eq!(whole.finish(), pieces.finish());
+    }
+
+    // function to test that the keys change the hash
+    fn test_keys() {
+        let hash = |k0, k1| {
+            let mut hasher = SipHasher13::new_with_keys(k0, k1);
+            hasher.write(b"CS");
+            hasher.finish()
+        };
+        assert_ne!(hash(0, 0), hash(1, 0));
+        assert_ne!(hash(0, 0), hash(0, 1));
+        assert_eq!(hash(7, 9), hash(7, 9));
+    }
+
+    mod sip {
+        use super::*;
+
+        #[test]
+        fn t_matches_default_hasher() {
+            test_matches_default_hasher();
+        }
+
+        #[test]
+        fn t_keys() {
+            test_keys();
+        }
+    }
+}